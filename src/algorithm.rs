@@ -0,0 +1,526 @@
+use crate::hash::{
+    blake2b::Blake2b, blake2s::Blake2s, blake3::Blake3, constant_time_eq, md2::Md2, md4::Md4,
+    md5::Md5, ripemd128::Ripemd128, ripemd160::Ripemd160, ripemd256::Ripemd256,
+    ripemd320::Ripemd320, sha1::Sha1, sha1dc::Sha1Dc, sha224::Sha224, sha256::Sha256,
+    sha384::Sha384, sha3_224::Sha3_224, sha3_256::Sha3_256, sha3_384::Sha3_384, sha3_512::Sha3_512,
+    sha512::Sha512, sha512_224::Sha512_224, sha512_256::Sha512_256, shake128::Shake128,
+    shake256::Shake256, Digest, Hasher,
+};
+use crate::trace::{self, Observer};
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use core::{error::Error, fmt::Display, str::FromStr};
+
+/// A hashing algorithm which can be selected at runtime, e.g. by name from a configuration file.
+///
+/// Names are parsed case-insensitively, and both the canonical name and any alias is accepted.
+///
+/// Since every algorithm has a fixed digest size, the functions with a variable output length are only available
+/// with a single one:
+/// - BLAKE2b and BLAKE2s only with their largest digest, of 512 and 256 bits, as `b2sum` and most other tools
+///   use them, and other lengths with [`crate::blake2b`] and [`crate::blake2s`].
+/// - SHAKE128 and SHAKE256 only with 256 and 512 bits of output, which is twice their security level, so that the
+///   output isn't weaker than the function, and other lengths with [`crate::shake128`] and [`crate::shake256`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use lore::Algorithm;
+///
+/// let algorithm: Algorithm = "SHA-1".parse().unwrap();
+/// assert_eq!(algorithm, Algorithm::Sha1);
+/// assert_eq!(algorithm.to_string(), "sha1");
+///
+/// let mut hasher = algorithm.hasher();
+/// hasher.update(b"abc");
+/// assert_eq!(hasher.finalize(), Vec::from(lore::sha1("abc")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// MD2, see [`crate::md2`].
+    Md2,
+    /// MD4, see [`crate::md4`].
+    Md4,
+    /// MD5, see [`crate::md5`].
+    Md5,
+    /// RIPEMD-128, see [`crate::ripemd128`].
+    Ripemd128,
+    /// RIPEMD-160, see [`crate::ripemd160`].
+    Ripemd160,
+    /// RIPEMD-256, see [`crate::ripemd256`].
+    Ripemd256,
+    /// RIPEMD-320, see [`crate::ripemd320`].
+    Ripemd320,
+    /// SHA-1, see [`crate::sha1`].
+    Sha1,
+    /// SHA-1 with collision detection, see [`crate::sha1dc`].
+    ///
+    /// The digest is the regular SHA-1 digest, whether or not a collision is detected.
+    Sha1Dc,
+    /// SHA-224, see [`crate::sha224`].
+    Sha224,
+    /// SHA-256, see [`crate::sha256`].
+    Sha256,
+    /// SHA-384, see [`crate::sha384`].
+    Sha384,
+    /// SHA-512, see [`crate::sha512`].
+    Sha512,
+    /// SHA-512/224, see [`crate::sha512_224`].
+    Sha512_224,
+    /// SHA-512/256, see [`crate::sha512_256`].
+    Sha512_256,
+    /// SHA3-224, see [`crate::sha3_224`].
+    Sha3_224,
+    /// SHA3-256, see [`crate::sha3_256`].
+    Sha3_256,
+    /// SHA3-384, see [`crate::sha3_384`].
+    Sha3_384,
+    /// SHA3-512, see [`crate::sha3_512`].
+    Sha3_512,
+    /// SHAKE128 with 256 bits of output, see [`crate::shake128`].
+    Shake128,
+    /// SHAKE256 with 512 bits of output, see [`crate::shake256`].
+    Shake256,
+    /// BLAKE2b with a 512-bit digest, see [`crate::blake2b`].
+    Blake2b512,
+    /// BLAKE2s with a 256-bit digest, see [`crate::blake2s`].
+    Blake2s256,
+    /// BLAKE3, see [`crate::blake3`].
+    Blake3,
+}
+
+impl Algorithm {
+    /// Every algorithm available in the crate.
+    pub const ALL: [Algorithm; 24] = [
+        Self::Md2,
+        Self::Md4,
+        Self::Md5,
+        Self::Ripemd128,
+        Self::Ripemd160,
+        Self::Ripemd256,
+        Self::Ripemd320,
+        Self::Sha1,
+        Self::Sha1Dc,
+        Self::Sha224,
+        Self::Sha256,
+        Self::Sha384,
+        Self::Sha512,
+        Self::Sha512_224,
+        Self::Sha512_256,
+        Self::Sha3_224,
+        Self::Sha3_256,
+        Self::Sha3_384,
+        Self::Sha3_512,
+        Self::Shake128,
+        Self::Shake256,
+        Self::Blake2b512,
+        Self::Blake2s256,
+        Self::Blake3,
+    ];
+
+    /// The canonical name of the algorithm, which is also used by [`Display`].
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        self.aliases()[0]
+    }
+
+    /// All names which are accepted when parsing the algorithm, starting with the canonical name.
+    #[must_use]
+    pub fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Self::Md2 => &["md2"],
+            Self::Md4 => &["md4"],
+            Self::Md5 => &["md5"],
+            Self::Ripemd128 => &["ripemd128", "ripemd-128", "rmd128"],
+            Self::Ripemd160 => &["ripemd160", "ripemd-160", "rmd160"],
+            Self::Ripemd256 => &["ripemd256", "ripemd-256", "rmd256"],
+            Self::Ripemd320 => &["ripemd320", "ripemd-320", "rmd320"],
+            Self::Sha1 => &["sha1", "sha-1"],
+            Self::Sha1Dc => &["sha1dc", "sha1-dc", "sha-1dc"],
+            Self::Sha224 => &["sha224", "sha-224"],
+            Self::Sha256 => &["sha256", "sha-256"],
+            Self::Sha384 => &["sha384", "sha-384"],
+            Self::Sha512 => &["sha512", "sha-512"],
+            Self::Sha512_224 => &["sha512/224", "sha-512/224", "sha512-224", "sha512_224"],
+            Self::Sha512_256 => &["sha512/256", "sha-512/256", "sha512-256", "sha512_256"],
+            Self::Sha3_224 => &["sha3-224", "sha3_224"],
+            Self::Sha3_256 => &["sha3-256", "sha3_256"],
+            Self::Sha3_384 => &["sha3-384", "sha3_384"],
+            Self::Sha3_512 => &["sha3-512", "sha3_512"],
+            Self::Shake128 => &["shake128", "shake-128"],
+            Self::Shake256 => &["shake256", "shake-256"],
+            Self::Blake2b512 => &["blake2b-512", "blake2b512", "blake2b"],
+            Self::Blake2s256 => &["blake2s-256", "blake2s256", "blake2s"],
+            Self::Blake3 => &["blake3"],
+        }
+    }
+
+    /// The size of the digest produced by the algorithm, in bytes.
+    #[must_use]
+    pub fn output_size(self) -> usize {
+        match self {
+            Self::Md2 => Md2::OUTPUT_SIZE,
+            Self::Md4 => Md4::OUTPUT_SIZE,
+            Self::Md5 => Md5::OUTPUT_SIZE,
+            Self::Ripemd128 => Ripemd128::OUTPUT_SIZE,
+            Self::Ripemd160 => Ripemd160::OUTPUT_SIZE,
+            Self::Ripemd256 => Ripemd256::OUTPUT_SIZE,
+            Self::Ripemd320 => Ripemd320::OUTPUT_SIZE,
+            Self::Sha1 => Sha1::OUTPUT_SIZE,
+            Self::Sha1Dc => Sha1Dc::OUTPUT_SIZE,
+            Self::Sha224 => Sha224::OUTPUT_SIZE,
+            Self::Sha256 => Sha256::OUTPUT_SIZE,
+            Self::Sha384 => Sha384::OUTPUT_SIZE,
+            Self::Sha512 => Sha512::OUTPUT_SIZE,
+            Self::Sha512_224 => Sha512_224::OUTPUT_SIZE,
+            Self::Sha512_256 => Sha512_256::OUTPUT_SIZE,
+            Self::Sha3_224 => Sha3_224::OUTPUT_SIZE,
+            Self::Sha3_256 => Sha3_256::OUTPUT_SIZE,
+            Self::Sha3_384 => Sha3_384::OUTPUT_SIZE,
+            Self::Sha3_512 => Sha3_512::OUTPUT_SIZE,
+            Self::Shake128 => FixedShake128::OUTPUT_SIZE,
+            Self::Shake256 => FixedShake256::OUTPUT_SIZE,
+            Self::Blake2b512 => Blake2b::<64>::OUTPUT_SIZE,
+            Self::Blake2s256 => Blake2s::<32>::OUTPUT_SIZE,
+            Self::Blake3 => Blake3::OUTPUT_SIZE,
+        }
+    }
+
+    /// The size of the blocks consumed by the algorithm, in bytes.
+    #[must_use]
+    pub fn block_size(self) -> usize {
+        match self {
+            Self::Md2 => Md2::BLOCK_SIZE,
+            Self::Md4 => Md4::BLOCK_SIZE,
+            Self::Md5 => Md5::BLOCK_SIZE,
+            Self::Ripemd128 => Ripemd128::BLOCK_SIZE,
+            Self::Ripemd160 => Ripemd160::BLOCK_SIZE,
+            Self::Ripemd256 => Ripemd256::BLOCK_SIZE,
+            Self::Ripemd320 => Ripemd320::BLOCK_SIZE,
+            Self::Sha1 => Sha1::BLOCK_SIZE,
+            Self::Sha1Dc => Sha1Dc::BLOCK_SIZE,
+            Self::Sha224 => Sha224::BLOCK_SIZE,
+            Self::Sha256 => Sha256::BLOCK_SIZE,
+            Self::Sha384 => Sha384::BLOCK_SIZE,
+            Self::Sha512 => Sha512::BLOCK_SIZE,
+            Self::Sha512_224 => Sha512_224::BLOCK_SIZE,
+            Self::Sha512_256 => Sha512_256::BLOCK_SIZE,
+            Self::Sha3_224 => Sha3_224::BLOCK_SIZE,
+            Self::Sha3_256 => Sha3_256::BLOCK_SIZE,
+            Self::Sha3_384 => Sha3_384::BLOCK_SIZE,
+            Self::Sha3_512 => Sha3_512::BLOCK_SIZE,
+            Self::Shake128 => FixedShake128::BLOCK_SIZE,
+            Self::Shake256 => FixedShake256::BLOCK_SIZE,
+            Self::Blake2b512 => Blake2b::<64>::BLOCK_SIZE,
+            Self::Blake2s256 => Blake2s::<32>::BLOCK_SIZE,
+            Self::Blake3 => Blake3::BLOCK_SIZE,
+        }
+    }
+
+    /// Create a new streaming hasher for the algorithm.
+    #[must_use]
+    pub fn hasher(self) -> Box<dyn DynHasher> {
+        match self {
+            Self::Md2 => Box::new(Md2::new()),
+            Self::Md4 => Box::new(Md4::new()),
+            Self::Md5 => Box::new(Md5::new()),
+            Self::Ripemd128 => Box::new(Ripemd128::new()),
+            Self::Ripemd160 => Box::new(Ripemd160::new()),
+            Self::Ripemd256 => Box::new(Ripemd256::new()),
+            Self::Ripemd320 => Box::new(Ripemd320::new()),
+            Self::Sha1 => Box::new(Sha1::new()),
+            Self::Sha1Dc => Box::new(Sha1Dc::new()),
+            Self::Sha224 => Box::new(Sha224::new()),
+            Self::Sha256 => Box::new(Sha256::new()),
+            Self::Sha384 => Box::new(Sha384::new()),
+            Self::Sha512 => Box::new(Sha512::new()),
+            Self::Sha512_224 => Box::new(Sha512_224::new()),
+            Self::Sha512_256 => Box::new(Sha512_256::new()),
+            Self::Sha3_224 => Box::new(Sha3_224::new()),
+            Self::Sha3_256 => Box::new(Sha3_256::new()),
+            Self::Sha3_384 => Box::new(Sha3_384::new()),
+            Self::Sha3_512 => Box::new(Sha3_512::new()),
+            Self::Shake128 => Box::new(FixedShake128::default()),
+            Self::Shake256 => Box::new(FixedShake256::default()),
+            Self::Blake2b512 => Box::new(Blake2b::<64>::new()),
+            Self::Blake2s256 => Box::new(Blake2s::<32>::new()),
+            Self::Blake3 => Box::new(Blake3::new()),
+        }
+    }
+
+    /// Compute the digest of a complete message in one go.
+    #[must_use]
+    pub fn digest(self, message: impl AsRef<[u8]>) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(message.as_ref());
+        hasher.finalize()
+    }
+
+    /// Check whether the digest of the message is equal to the expected digest, in constant time.
+    #[must_use]
+    pub fn verify(self, message: impl AsRef<[u8]>, expected: impl AsRef<[u8]>) -> bool {
+        constant_time_eq(&self.digest(message), expected.as_ref())
+    }
+
+    /// Compute the digest of a complete message, reporting every intermediate value to the observer.
+    ///
+    /// See the [`trace`](crate::trace) module for the events which are reported.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::{trace::Event, Algorithm};
+    ///
+    /// let mut events = Vec::new();
+    /// let digest = Algorithm::Sha256.trace("abc", &mut events);
+    ///
+    /// assert_eq!(digest, Algorithm::Sha256.digest("abc"));
+    /// assert_eq!(events.last(), Some(&Event::Digest { bytes: digest }));
+    /// ```
+    pub fn trace(self, message: impl AsRef<[u8]>, observer: &mut dyn Observer) -> Vec<u8> {
+        let message = message.as_ref();
+        match self {
+            Self::Md2 => trace::md2(message, observer).as_ref().to_vec(),
+            Self::Md4 => trace::md4(message, observer).as_ref().to_vec(),
+            Self::Md5 => trace::md5(message, observer).as_ref().to_vec(),
+            Self::Ripemd128 => trace::ripemd128(message, observer).as_ref().to_vec(),
+            Self::Ripemd160 => trace::ripemd160(message, observer).as_ref().to_vec(),
+            Self::Ripemd256 => trace::ripemd256(message, observer).as_ref().to_vec(),
+            Self::Ripemd320 => trace::ripemd320(message, observer).as_ref().to_vec(),
+            // collision detection doesn't change the steps of SHA-1, only checks them afterwards
+            Self::Sha1 | Self::Sha1Dc => trace::sha1(message, observer).as_ref().to_vec(),
+            Self::Sha224 => trace::sha224(message, observer).as_ref().to_vec(),
+            Self::Sha256 => trace::sha256(message, observer).as_ref().to_vec(),
+            Self::Sha384 => trace::sha384(message, observer).as_ref().to_vec(),
+            Self::Sha512 => trace::sha512(message, observer).as_ref().to_vec(),
+            Self::Sha512_224 => trace::sha512_224(message, observer).as_ref().to_vec(),
+            Self::Sha512_256 => trace::sha512_256(message, observer).as_ref().to_vec(),
+            Self::Sha3_224 => trace::sha3_224(message, observer).as_ref().to_vec(),
+            Self::Sha3_256 => trace::sha3_256(message, observer).as_ref().to_vec(),
+            Self::Sha3_384 => trace::sha3_384(message, observer).as_ref().to_vec(),
+            Self::Sha3_512 => trace::sha3_512(message, observer).as_ref().to_vec(),
+            Self::Shake128 => trace::shake128::<32, _>(message, observer)
+                .as_ref()
+                .to_vec(),
+            Self::Shake256 => trace::shake256::<64, _>(message, observer)
+                .as_ref()
+                .to_vec(),
+            Self::Blake2b512 => trace::blake2b::<64, _>(message, observer).as_ref().to_vec(),
+            Self::Blake2s256 => trace::blake2s::<32, _>(message, observer).as_ref().to_vec(),
+            Self::Blake3 => trace::blake3(message, observer).as_ref().to_vec(),
+        }
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ParseAlgorithmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|algorithm| {
+                algorithm
+                    .aliases()
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(s))
+            })
+            .ok_or_else(|| ParseAlgorithmError(s.to_owned()))
+    }
+}
+
+/// The error returned when parsing an [`Algorithm`] from an unknown name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAlgorithmError(String);
+
+impl Display for ParseAlgorithmError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown hashing algorithm: {:?}", self.0)
+    }
+}
+
+impl Error for ParseAlgorithmError {}
+
+// SHAKE128 and SHAKE256 with the output length of their algorithm, which makes them a `Hasher`
+macro_rules! fixed_shake {
+    ($($name:ident($xof:ident, $block:literal, $size:literal)),*) => {
+        $(
+            #[derive(Debug, Clone, Default)]
+            struct $name($xof);
+
+            impl Hasher for $name {
+                const BLOCK_SIZE: usize = $block;
+                const OUTPUT_SIZE: usize = $size;
+
+                type Output = Digest<$size>;
+
+                fn update(&mut self, data: impl AsRef<[u8]>) {
+                    self.0.update(data);
+                }
+
+                fn finalize(self) -> Digest<$size> {
+                    self.0.finalize()
+                }
+            }
+        )*
+    };
+}
+
+fixed_shake!(
+    FixedShake128(Shake128, 168, 32),
+    FixedShake256(Shake256, 136, 64)
+);
+
+/// An object-safe version of [`Hasher`], for hashing with an algorithm chosen at runtime.
+///
+/// This is implemented for every [`Hasher`], and the digest is returned as a vector of bytes since its length is not known at compile time.
+pub trait DynHasher {
+    /// Append data to the message being hashed.
+    fn update(&mut self, data: &[u8]);
+
+    /// Pad the message and return the digest of everything passed to [`DynHasher::update`].
+    fn finalize(self: Box<Self>) -> Vec<u8>;
+
+    /// Discard the message hashed so far, returning the hasher to its initial state.
+    fn reset(&mut self);
+
+    /// The size of the digest, in bytes.
+    fn output_size(&self) -> usize;
+
+    /// The size of the blocks consumed by the compression function, in bytes.
+    fn block_size(&self) -> usize;
+
+    /// Clone the hasher, including the message hashed so far.
+    fn box_clone(&self) -> Box<dyn DynHasher>;
+}
+
+impl<H: Hasher + 'static> DynHasher for H {
+    fn update(&mut self, data: &[u8]) {
+        Hasher::update(self, data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        Hasher::finalize(*self).as_ref().to_vec()
+    }
+
+    fn reset(&mut self) {
+        Hasher::reset(self);
+    }
+
+    fn output_size(&self) -> usize {
+        H::OUTPUT_SIZE
+    }
+
+    fn block_size(&self) -> usize {
+        H::BLOCK_SIZE
+    }
+
+    fn box_clone(&self) -> Box<dyn DynHasher> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynHasher> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithm_parse() {
+        for algorithm in Algorithm::ALL {
+            assert_eq!(Ok(algorithm), algorithm.to_string().parse());
+            assert_eq!(Ok(algorithm), algorithm.name().to_uppercase().parse());
+            for alias in algorithm.aliases() {
+                assert_eq!(Ok(algorithm), alias.parse());
+            }
+        }
+
+        assert_eq!(Ok(Algorithm::Md5), "MD5".parse());
+        assert_eq!(Ok(Algorithm::Sha1), "Sha-1".parse());
+        assert_eq!(Ok(Algorithm::Sha512_224), "SHA-512/224".parse());
+        assert_eq!(
+            Err(ParseAlgorithmError("md6".to_owned())),
+            "md6".parse::<Algorithm>()
+        );
+    }
+
+    #[test]
+    fn algorithm_digest() {
+        assert_eq!(Vec::from(crate::md2("abc")), Algorithm::Md2.digest("abc"));
+        assert_eq!(Vec::from(crate::md4("abc")), Algorithm::Md4.digest("abc"));
+        assert_eq!(Vec::from(crate::md5("abc")), Algorithm::Md5.digest("abc"));
+        assert_eq!(
+            Vec::from(crate::ripemd160("abc")),
+            Algorithm::Ripemd160.digest("abc")
+        );
+        assert_eq!(Vec::from(crate::sha1("abc")), Algorithm::Sha1.digest("abc"));
+        assert_eq!(
+            Vec::from(crate::sha1dc("abc").digest),
+            Algorithm::Sha1Dc.digest("abc")
+        );
+        assert_eq!(
+            Vec::from(crate::sha512_256("abc")),
+            Algorithm::Sha512_256.digest("abc")
+        );
+        assert_eq!(
+            Vec::from(crate::sha3_256("abc")),
+            Algorithm::Sha3_256.digest("abc")
+        );
+        assert_eq!(
+            Vec::from(crate::shake128::<32>("abc")),
+            Algorithm::Shake128.digest("abc")
+        );
+        assert_eq!(
+            Vec::from(crate::shake256::<64>("abc")),
+            Algorithm::Shake256.digest("abc")
+        );
+        assert_eq!(
+            Vec::from(crate::blake2b::<64>("abc")),
+            Algorithm::Blake2b512.digest("abc")
+        );
+
+        for algorithm in Algorithm::ALL {
+            let hasher = algorithm.hasher();
+            assert_eq!(algorithm.output_size(), hasher.output_size());
+            assert_eq!(algorithm.block_size(), hasher.block_size());
+            assert_eq!(algorithm.output_size(), hasher.finalize().len());
+        }
+    }
+
+    #[test]
+    fn algorithm_verify() {
+        for algorithm in Algorithm::ALL {
+            let digest = algorithm.digest("lol xd");
+            assert!(algorithm.verify("lol xd", &digest));
+            assert!(!algorithm.verify("lol xD", &digest));
+            assert!(!algorithm.verify("lol xd", &digest[1..]));
+        }
+    }
+
+    #[test]
+    fn dyn_hasher_clone() {
+        let mut hasher = Algorithm::Md5.hasher();
+        hasher.update(b"lol");
+        let mut clone = hasher.clone();
+        hasher.update(b" xd");
+        clone.reset();
+        clone.update(b"lol xd");
+
+        assert_eq!(hasher.finalize(), clone.finalize());
+    }
+}
@@ -0,0 +1,410 @@
+//! Parsing and running test vectors in the response file format of the NIST Cryptographic Algorithm Validation
+//! Program (CAVP).
+//!
+//! A response file (`.rsp`) either lists messages with their digests, like the `ShortMsg` and `LongMsg` files, or
+//! the seed and the checkpoints of a Monte Carlo test, like the `Monte` files.
+//! The length of each message is given in bits, and only messages of whole bytes ("byte oriented") are checked, since
+//! there are no bit-oriented response files in the repository to test the other ones against.
+//!
+//! The crate is tested with the response files of the SHA validation system (SHAVS) for SHA-1 and SHA-2, which are in
+//! the `vectors/cavp` directory of the repository, with only the first few messages of each `LongMsg` file.
+//!
+//! # Examples
+//!
+//! Checking the digests of a response file:
+//!
+//! ```
+//! use lore::{cavp, Algorithm};
+//!
+//! let response = cavp::parse(
+//!     "[L = 20]
+//!
+//!     Len = 24
+//!     Msg = 616263
+//!     MD = a9993e364706816aba3e25717850c26c9cd0d89d",
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(response.messages.len(), 1);
+//! assert_eq!(cavp::run(&response, Algorithm::Sha1), Ok(1));
+//! assert!(cavp::run(&response, Algorithm::Sha256).is_err());
+//! ```
+
+use crate::Algorithm;
+use alloc::vec::Vec;
+use core::{error::Error, fmt::Display};
+
+/// The test vectors of a response file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Response {
+    /// The messages and their digests.
+    pub messages: Vec<Message>,
+    /// The Monte Carlo test, if the file contains one.
+    pub monte_carlo: Option<MonteCarlo>,
+}
+
+/// A message with its expected digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// The length of the message in bits.
+    pub bits: u64,
+    /// The bytes which contain the message, where only the first `bits` bits (most significant bit first) are used.
+    pub message: Vec<u8>,
+    /// The expected digest.
+    pub digest: Vec<u8>,
+}
+
+/// A Monte Carlo test, which hashes the seed over and over, and compares the digest after every 1000 iterations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonteCarlo {
+    /// The initial seed.
+    pub seed: Vec<u8>,
+    /// The expected digests at each checkpoint.
+    pub checkpoints: Vec<Vec<u8>>,
+}
+
+/// Parses a response file.
+///
+/// Comments, the bracketed parameters (like `[L = 20]`) and unknown keys are skipped.
+///
+/// # Errors
+///
+/// Returns an error if a line is neither a comment, a parameter nor a key and a value, if a value is not a valid number
+/// or hexadecimal string, or if a digest doesn't follow a message or a seed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let response = lore::cavp::parse("Seed = 00\n\nCOUNT = 0\nMD = 01\n\nCOUNT = 1\nMD = 02").unwrap();
+/// let monte_carlo = response.monte_carlo.unwrap();
+///
+/// assert_eq!(monte_carlo.seed, [0]);
+/// assert_eq!(monte_carlo.checkpoints, [[1], [2]]);
+/// ```
+pub fn parse(input: &str) -> Result<Response, ParseResponseError> {
+    let mut response = Response::default();
+    let mut bits = None;
+    let mut message = None;
+
+    for (index, line) in input.lines().enumerate() {
+        let error = |reason| ParseResponseError {
+            line: index + 1,
+            reason,
+        };
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .ok_or_else(|| error("expected a key and a value"))?;
+
+        match key {
+            "Len" => bits = Some(value.parse().map_err(|_| error("invalid length"))?),
+            // the Monte Carlo tests of SHA-3 give their seed as a message without a length
+            "Msg" if bits.is_none() => {
+                let seed = hex(value).ok_or_else(|| error("invalid hexadecimal string"))?;
+                response.monte_carlo = Some(MonteCarlo {
+                    seed,
+                    checkpoints: Vec::new(),
+                });
+            }
+            "Msg" => message = Some(hex(value).ok_or_else(|| error("invalid hexadecimal string"))?),
+            "Seed" => {
+                let seed = hex(value).ok_or_else(|| error("invalid hexadecimal string"))?;
+                response.monte_carlo = Some(MonteCarlo {
+                    seed,
+                    checkpoints: Vec::new(),
+                });
+            }
+            "MD" => {
+                let digest = hex(value).ok_or_else(|| error("invalid hexadecimal string"))?;
+                match (bits.take(), message.take(), &mut response.monte_carlo) {
+                    (Some(bits), Some(message), _) => {
+                        if bits > (message.len() as u64).wrapping_mul(8) {
+                            return Err(error("message is shorter than its length"));
+                        }
+                        response.messages.push(Message {
+                            bits,
+                            message,
+                            digest,
+                        });
+                    }
+                    (None, None, Some(monte_carlo)) => monte_carlo.checkpoints.push(digest),
+                    _ => return Err(error("digest without a message or seed")),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(response)
+}
+
+// decode a hexadecimal string of any even length
+fn hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Checks every message and Monte Carlo checkpoint of the response against the algorithm.
+///
+/// The Monte Carlo test is run as described by SHAVS for SHA-1 and SHA-2, where every digest is the digest of the
+/// previous three, so the Monte Carlo files of other validation systems, like SHA3VS, are not supported.
+///
+/// # Errors
+///
+/// Returns the first digest which doesn't match, or the first message which can't be checked because its length is
+/// not a whole number of bytes.
+/// Otherwise, the number of digests which were checked is returned.
+pub fn run(response: &Response, algorithm: Algorithm) -> Result<usize, Failure> {
+    for (index, message) in response.messages.iter().enumerate() {
+        if !message.bits.is_multiple_of(8) {
+            return Err(Failure::Unsupported {
+                index,
+                bits: message.bits,
+            });
+        }
+
+        let actual = algorithm.digest(&message.message[..(message.bits / 8) as usize]);
+        if actual != message.digest {
+            return Err(Failure::Message {
+                index,
+                expected: message.digest.clone(),
+                actual,
+            });
+        }
+    }
+
+    let checkpoints = response.monte_carlo.as_ref().map_or(Ok(0), |monte_carlo| {
+        let mut seed = monte_carlo.seed.clone();
+        for (count, expected) in monte_carlo.checkpoints.iter().enumerate() {
+            seed = checkpoint(algorithm, seed);
+            if seed != *expected {
+                return Err(Failure::MonteCarlo {
+                    count,
+                    expected: expected.clone(),
+                    actual: seed,
+                });
+            }
+        }
+
+        Ok(monte_carlo.checkpoints.len())
+    })?;
+
+    Ok(response.messages.len() + checkpoints)
+}
+
+// compute the next checkpoint of a Monte Carlo test from the previous one, where every digest is the digest of the
+// previous three
+fn checkpoint(algorithm: Algorithm, seed: Vec<u8>) -> Vec<u8> {
+    let mut digests = [seed.clone(), seed.clone(), seed];
+    for _ in 0..1000 {
+        let digest = algorithm.digest(digests.concat());
+        digests.rotate_left(1);
+        digests[2] = digest;
+    }
+
+    let [_, _, digest] = digests;
+    digest
+}
+
+/// The error returned when a response file can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseResponseError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseResponseError {
+    /// The line at which parsing failed, counting from 1.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Display for ParseResponseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid response file at line {}: {}",
+            self.line, self.reason
+        )
+    }
+}
+
+impl Error for ParseResponseError {}
+
+/// The first test vector of a response file which an algorithm failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The digest of a message is not the expected one.
+    Message {
+        /// The position of the message in the response file, counting from 0.
+        index: usize,
+        /// The expected digest.
+        expected: Vec<u8>,
+        /// The digest computed by the algorithm.
+        actual: Vec<u8>,
+    },
+    /// A checkpoint of the Monte Carlo test is not the expected one.
+    MonteCarlo {
+        /// The number of the checkpoint, counting from 0 like the `COUNT` of the response file.
+        count: usize,
+        /// The expected digest.
+        expected: Vec<u8>,
+        /// The digest computed by the algorithm.
+        actual: Vec<u8>,
+    },
+    /// The length of a message is not a whole number of bytes, which is not supported.
+    Unsupported {
+        /// The position of the message in the response file, counting from 0.
+        index: usize,
+        /// The length of the message in bits.
+        bits: u64,
+    },
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (expected, actual) = match self {
+            Self::Message {
+                index,
+                expected,
+                actual,
+            } => {
+                write!(f, "wrong digest of message {index}")?;
+                (expected, actual)
+            }
+            Self::MonteCarlo {
+                count,
+                expected,
+                actual,
+            } => {
+                write!(f, "wrong digest at Monte Carlo checkpoint {count}")?;
+                (expected, actual)
+            }
+            Self::Unsupported { index, bits } => {
+                return write!(
+                    f,
+                    "message {index} has {bits} bits, which is not a whole number of bytes"
+                );
+            }
+        };
+
+        f.write_str(": expected ")?;
+        expected
+            .iter()
+            .try_for_each(|byte| write!(f, "{byte:02x}"))?;
+        f.write_str(", found ")?;
+        actual.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl Error for Failure {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cavp_parse() {
+        let mut response = parse(
+            "#  CAVS 11.0
+            #  \"SHA-1 ShortMsg\" information
+
+            [L = 20]
+
+            Len = 5
+            Msg = 98
+            MD = 29826b003b906e660eff4027ce98af3531ac75ba
+
+            Len = 0
+            Msg = 00
+            MD = da39a3ee5e6b4b0d3255bfef95601890afd80709
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(None, response.monte_carlo);
+        assert_eq!(
+            Message {
+                bits: 5,
+                message: vec![0x98],
+                digest: hex("29826b003b906e660eff4027ce98af3531ac75ba").unwrap()
+            },
+            response.messages[0]
+        );
+        assert_eq!(
+            Err(Failure::Unsupported { index: 0, bits: 5 }),
+            run(&response, Algorithm::Sha1)
+        );
+
+        response.messages.remove(0);
+        assert_eq!(Ok(1), run(&response, Algorithm::Sha1));
+    }
+
+    #[test]
+    fn cavp_parse_errors() {
+        assert_eq!(3, parse("Len = 8\nMsg = 61\nMD").unwrap_err().line());
+        assert_eq!(1, parse("Len = eight").unwrap_err().line());
+        assert_eq!(2, parse("Len = 8\nMsg = 6").unwrap_err().line());
+        assert_eq!(3, parse("Len = 16\nMsg = 61\nMD = 00").unwrap_err().line());
+        assert_eq!(1, parse("MD = 00").unwrap_err().line());
+        assert_eq!(
+            "invalid response file at line 1: digest without a message or seed",
+            parse("MD = 00").unwrap_err().to_string()
+        );
+    }
+
+    // run every response file of the algorithm in the vectors directory
+    macro_rules! vectors {
+        ($algorithm:expr, $name:literal) => {
+            for file in [
+                include_str!(concat!("../vectors/cavp/", $name, "ShortMsg.rsp")),
+                include_str!(concat!("../vectors/cavp/", $name, "LongMsg.rsp")),
+                include_str!(concat!("../vectors/cavp/", $name, "Monte.rsp")),
+            ] {
+                let mut response = parse(file).unwrap();
+                let mut expected = response.messages.len();
+
+                // all 100 checkpoints of a Monte Carlo test take too long without optimizations, so only the
+                // first 10 are checked in debug builds
+                if let Some(monte_carlo) = &mut response.monte_carlo {
+                    assert_eq!(100, monte_carlo.checkpoints.len());
+                    #[cfg(debug_assertions)]
+                    monte_carlo.checkpoints.truncate(10);
+                    expected += monte_carlo.checkpoints.len();
+                }
+
+                assert_eq!(Ok(expected), run(&response, $algorithm), "{}", $name);
+            }
+        };
+    }
+
+    #[test]
+    fn cavp_sha1() {
+        vectors!(Algorithm::Sha1, "SHA1");
+    }
+
+    #[test]
+    fn cavp_sha2() {
+        vectors!(Algorithm::Sha224, "SHA224");
+        vectors!(Algorithm::Sha256, "SHA256");
+        vectors!(Algorithm::Sha384, "SHA384");
+        vectors!(Algorithm::Sha512, "SHA512");
+        vectors!(Algorithm::Sha512_224, "SHA512_224");
+        vectors!(Algorithm::Sha512_256, "SHA512_256");
+    }
+}
@@ -0,0 +1,299 @@
+//! Collision attacks on MD4 and MD5.
+//!
+//! Both attacks follow a differential path: a fixed difference between two message blocks, and conditions on the
+//! bits of the intermediate states which make the difference cancel out by the end of the compression function.
+//! The states are chosen to fulfil the conditions of the first round directly, and the message words are computed
+//! from them, which is called message modification.
+//!
+//! MD4 collisions use the path of Wang et al. (2005) and take a single block, found in milliseconds.
+//! MD5 collisions use two blocks, the first ending in a near-collision which the second one cancels, and the search
+//! speeds up the later rounds with the tunnels of Klima (2006).
+//! Finding an MD5 collision takes a few minutes on a single core, with an optimized build.
+//!
+//! Since both hash functions process messages block by block, the colliding messages can be preceded by any
+//! prefix (padded to whole blocks), and followed by any common suffix.
+//!
+//! # Examples
+//!
+//! Finding two different messages with the same MD4 digest:
+//!
+//! ```
+//! use lore::collision;
+//!
+//! let collision = collision::md4("lore", 0);
+//! let (first, second) = collision.messages();
+//!
+//! assert_ne!(first, second);
+//! assert_eq!(lore::md4(&first), lore::md4(&second));
+//!
+//! // appending the same suffix to both messages preserves the collision
+//! let suffix = b" and everything after";
+//! assert_eq!(lore::md4([first, suffix.to_vec()].concat()), lore::md4([second, suffix.to_vec()].concat()));
+//! ```
+
+mod md4;
+mod md5;
+
+use alloc::vec::Vec;
+
+/// Two different messages with the same digest, made of a common prefix followed by different blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collision {
+    /// The prefix shared by both messages, padded with zero bytes to a multiple of the block size.
+    pub prefix: Vec<u8>,
+    /// The blocks which follow the prefix in the first message.
+    pub first: Vec<u8>,
+    /// The blocks which follow the prefix in the second message.
+    pub second: Vec<u8>,
+}
+
+impl Collision {
+    /// Returns both colliding messages, i.e. the prefix followed by the blocks of each message.
+    #[must_use]
+    pub fn messages(&self) -> (Vec<u8>, Vec<u8>) {
+        (
+            [&self.prefix[..], &self.first].concat(),
+            [&self.prefix[..], &self.second].concat(),
+        )
+    }
+}
+
+/// Finds a pair of messages which start with the prefix and have the same MD4 digest.
+///
+/// The messages differ in a single block after the prefix. The seed determines the search, so the same seed and
+/// prefix always produce the same collision.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let collision = lore::collision::md4("", 1);
+///
+/// assert_eq!(collision.first.len(), 64);
+/// assert_eq!(lore::md4(&collision.first), lore::md4(&collision.second));
+/// ```
+#[must_use]
+pub fn md4(prefix: impl AsRef<[u8]>, seed: u64) -> Collision {
+    let prefix = pad(prefix);
+    let iv = prefix.as_chunks::<64>().0.iter().fold(
+        [
+            crate::hash::md4::A,
+            crate::hash::md4::B,
+            crate::hash::md4::C,
+            crate::hash::md4::D,
+        ],
+        crate::hash::md4::compress,
+    );
+    let (first, second) = md4::block(iv, &mut Rng(seed));
+
+    Collision {
+        prefix,
+        first: first.to_vec(),
+        second: second.to_vec(),
+    }
+}
+
+/// Finds a pair of messages which start with the prefix and have the same MD5 digest.
+///
+/// The messages differ in two blocks after the prefix. The seed determines the search, so the same seed and prefix
+/// always produce the same collision.
+///
+/// The search takes minutes, e.g. about 6 minutes (343 seconds) on a single core with an optimized build for the
+/// prefix `"lore"` and seed 1, while how long depends on the seed, and unoptimized builds are many times slower.
+///
+/// # Examples
+///
+/// Basic usage, which isn't run by the documentation tests since it takes minutes:
+///
+/// ```no_run
+/// let collision = lore::collision::md5("lore", 0);
+/// let (first, second) = collision.messages();
+///
+/// assert_eq!(collision.first.len(), 128);
+/// assert_eq!(lore::md5(first), lore::md5(second));
+/// ```
+#[must_use]
+pub fn md5(prefix: impl AsRef<[u8]>, seed: u64) -> Collision {
+    let prefix = pad(prefix);
+    let iv = prefix.as_chunks::<64>().0.iter().fold(
+        [
+            crate::hash::md5::A,
+            crate::hash::md5::B,
+            crate::hash::md5::C,
+            crate::hash::md5::D,
+        ],
+        crate::hash::md5::compress,
+    );
+    let mut rng = Rng(seed);
+    let (first, second) = md5::first_block(iv, &mut rng);
+    let (third, fourth) = md5::second_block(
+        crate::hash::md5::compress(iv, &first),
+        crate::hash::md5::compress(iv, &second),
+        &mut rng,
+    );
+
+    Collision {
+        prefix,
+        first: [first, third].concat(),
+        second: [second, fourth].concat(),
+    }
+}
+
+// pad the prefix with zeros to whole blocks
+fn pad(prefix: impl AsRef<[u8]>) -> Vec<u8> {
+    let mut prefix = prefix.as_ref().to_vec();
+    prefix.resize(prefix.len().div_ceil(64) * 64, 0);
+
+    prefix
+}
+
+// a small pseudorandom number generator (SplitMix64), so that every search can be reproduced from its seed
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u32 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+        ((z ^ (z >> 31)) >> 32) as u32
+    }
+}
+
+// the conditions on the bits of a state word, some of which relate it to the two preceding state words
+//
+// they are written as rows of 32 characters, most significant bit first, in the notation of Stevens (2006):
+// '.' is no condition, '0' and '1' (or '+' and '-' where the two messages differ) are fixed values, '^' and '!'
+// mean equal to and different from the preceding word, 'm' and '#' equal to and different from the one before that
+#[derive(Debug, Clone, Copy, Default)]
+struct Condition {
+    mask: u32,
+    one: u32,
+    previous: u32,
+    not_previous: u32,
+    second: u32,
+    not_second: u32,
+}
+
+impl Condition {
+    fn parse(row: &str) -> Self {
+        row.bytes()
+            .rev()
+            .enumerate()
+            .fold(Self::default(), |mut condition, (bit, c)| {
+                let bit = 1 << bit;
+                match c {
+                    b'.' => return condition,
+                    b'0' | b'+' => {}
+                    b'1' | b'-' => condition.one |= bit,
+                    b'^' => condition.previous |= bit,
+                    b'!' => condition.not_previous |= bit,
+                    b'm' => condition.second |= bit,
+                    b'#' => condition.not_second |= bit,
+                    _ => panic!("Invalid bit condition: {}", c as char),
+                }
+                condition.mask |= bit;
+
+                condition
+            })
+    }
+
+    // the bits of the preceding and second preceding word which this condition refers to
+    fn relations(&self) -> (u32, u32) {
+        (
+            self.previous | self.not_previous,
+            self.second | self.not_second,
+        )
+    }
+
+    // the value which the conditioned bits must have
+    fn value(&self, previous: u32, second: u32) -> u32 {
+        self.one
+            | (previous & self.previous)
+            | (!previous & self.not_previous)
+            | (second & self.second)
+            | (!second & self.not_second)
+    }
+
+    // change the word as little as possible to fulfil the condition
+    fn fix(&self, word: u32, previous: u32, second: u32) -> u32 {
+        (word & !self.mask) | self.value(previous, second)
+    }
+
+    fn holds(&self, word: u32, previous: u32, second: u32) -> bool {
+        (word ^ self.value(previous, second)) & self.mask == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collision_condition() {
+        let condition = Condition::parse("1.0.^!m#........................");
+
+        assert_eq!(0xaf000000, condition.mask);
+        assert_eq!(0x80000000, condition.one);
+        assert_eq!((0x0c000000, 0x03000000), condition.relations());
+        assert!(condition.holds(0x8c000000, 0x08000000, 0x01000000));
+        assert!(!condition.holds(0x8c000000, 0x00000000, 0x01000000));
+        assert_eq!(0xd5ffffff, condition.fix(0xffffffff, 0, 0));
+    }
+
+    #[test]
+    fn collision_messages() {
+        let collision = md4("abc", 7);
+        let (first, second) = collision.messages();
+
+        assert_eq!(64, collision.prefix.len());
+        assert!(collision.prefix.starts_with(b"abc"));
+        assert_ne!(first, second);
+        assert_eq!(crate::md4(&first), crate::md4(&second));
+    }
+
+    // the blocks after the prefix "lore" of the MD5 collision found with seed 1, which were checked with Python's hashlib
+    const MD5_FIRST: &str = "758c11e71bd9fd8f1b4b2f2be7bf19f9c62fc153dfa182d7720458e2c3fb7f28d56d3306058694c35f9e5a78740b3b15e0be5d91c42ac93fa75d221344fb84c634d8752a14b13173600da2dd3fc9e5c1a256a0b55426c4fc20c961f7ce972edf4530ff7b11bbac08561d3ea6754c021ed6b0c229c6ab932ba381e056a4351060";
+    const MD5_SECOND: &str = "758c11e71bd9fd8f1b4b2f2be7bf19f9c62fc1d3dfa182d7720458e2c3fb7f28d56d3306058694c35f9e5a78748b3b15e0be5d91c42ac93fa75d229344fb84c634d8752a14b13173600da2dd3fc9e5c1a256a0355426c4fc20c961f7ce972edf4530ff7b11bbac08561d3ea675cc011ed6b0c229c6ab932ba381e0d6a4351060";
+
+    // the collision found with seed 1 for the prefix "lore"
+    pub(super) fn md5_lore() -> Collision {
+        let blocks = |hex: &str| hex.parse::<crate::Digest<128>>().unwrap().as_ref().to_vec();
+
+        Collision {
+            prefix: pad("lore"),
+            first: blocks(MD5_FIRST),
+            second: blocks(MD5_SECOND),
+        }
+    }
+
+    #[test]
+    fn collision_md5_known() {
+        let collision = md5_lore();
+        let (first, second) = collision.messages();
+
+        assert_ne!(first, second);
+        assert_eq!(crate::md5(&first), crate::md5(&second));
+        assert_eq!(
+            "ec98e87f314bcb79f2fec10008e627fe",
+            crate::md5(&first).to_string()
+        );
+
+        // the first blocks only lead to a near-collision
+        assert_ne!(crate::md5(&first[..128]), crate::md5(&second[..128]));
+    }
+
+    #[test]
+    #[ignore = "takes minutes, even with an optimized build"]
+    fn collision_md5() {
+        let collision = md5("lore", 1);
+        let (first, second) = collision.messages();
+
+        assert_eq!(128, collision.first.len());
+        assert_ne!(first, second);
+        assert_eq!(crate::md5(&first), crate::md5(&second));
+        assert_eq!(md5_lore(), collision);
+    }
+}
@@ -0,0 +1,171 @@
+use super::{Condition, Rng};
+use crate::hash::{
+    bytes_to_words_le,
+    md4::{compress, f, g, h, C2, C3, S, W},
+    words_to_bytes_le,
+};
+use alloc::vec::Vec;
+
+// the sufficient conditions of Wang et al. on the states Q[-3] to Q[48], where Q[-3], Q[-2], Q[-1] and Q[0] are the
+// chaining value (a, d, c, b), and Q[i + 1] is the word computed in step i
+const CONDITIONS: [&str; 52] = [
+    "................................", // Q-3
+    "................................", // Q-2
+    "................................", // Q-1
+    "................................", // Q0
+    ".........................^......", // Q1
+    ".....................^..^0......", // Q2
+    "......^..............0..11......", // Q3
+    "......0..............0..01......", // Q4
+    "......0...........^..1..1.......", // Q5
+    "......1...^^^^....0.............", // Q6
+    "..........0100...^0^............", // Q7
+    "..........0000.^.011............", // Q8
+    "......^..^1000.0.111............", // Q9
+    "..^...1..0110..0.111............", // Q10
+    "^.1...0..0000..1................", // Q11
+    "0.0...1..^110...................", // Q12
+    "0.1^.^0..0......................", // Q13
+    "1.01.10..0......................", // Q14
+    "..00.01..1...^..................", // Q15
+    "..01.1^......0..................", // Q16
+    "1..1.01......m..................", // Q17
+    "m..m.mm......^..................", // Q18
+    "^.^^.^^.........................", // Q19
+    "0.1^............................", // Q20
+    "1..1............................", // Q21
+    "...m............................", // Q22
+    "!.!^............................", // Q23
+    "................................", // Q24
+    "................................", // Q25
+    "................................", // Q26
+    "................................", // Q27
+    "................................", // Q28
+    "................................", // Q29
+    "................................", // Q30
+    "................................", // Q31
+    "................................", // Q32
+    "................................", // Q33
+    "................................", // Q34
+    "................................", // Q35
+    "1...............................", // Q36
+    "1...............................", // Q37
+    "................................", // Q38
+    "................................", // Q39
+    "................................", // Q40
+    "................................", // Q41
+    "................................", // Q42
+    "................................", // Q43
+    "................................", // Q44
+    "................................", // Q45
+    "................................", // Q46
+    "................................", // Q47
+    "................................", // Q48
+];
+
+// the difference between the two message blocks, added to the words of the first block
+const DIFFERENCE: [u32; 16] = [
+    0, 0x80000000, 0x70000000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xffff0000, 0, 0, 0,
+];
+
+// find two different blocks which compress to the same chaining value
+pub fn block(iv: [u32; 4], rng: &mut Rng) -> ([u8; 64], [u8; 64]) {
+    let conditions: Vec<_> = CONDITIONS.iter().map(|row| Condition::parse(row)).collect();
+    let [a, b, c, d] = iv;
+    let mut q = [0; 52];
+    q[..4].copy_from_slice(&[a, d, c, b]);
+    let mut m = [0; 16];
+
+    loop {
+        // single-step modification: choose the states of the first round, and compute the message words from them
+        for i in 0..16 {
+            q[i + 4] = conditions[i + 4].fix(rng.next(), q[i + 3], q[i + 2]);
+            m[i] = backward(&q, i);
+        }
+
+        // multi-step modification: fix the first states of the second round by changing the first round
+        if !(16..19).all(|i| correct(&mut q, &mut m, &conditions, i)) {
+            continue;
+        }
+
+        // the remaining conditions are left to chance
+        let first: [u8; 64] = words_to_bytes_le(m);
+        let second: [u8; 64] = words_to_bytes_le(
+            m.iter()
+                .zip(DIFFERENCE)
+                .map(|(word, difference)| word.wrapping_add(difference))
+                .collect::<Vec<_>>(),
+        );
+        if compress(iv, &first) == compress(iv, &second) {
+            debug_assert_eq!(m, bytes_to_words_le(first));
+            return (first, second);
+        }
+    }
+}
+
+// compute Q[i + 1] from the preceding states and the message
+fn forward(q: &[u32; 52], m: &[u32; 16], i: usize) -> u32 {
+    let (f, k) = function(i);
+
+    f(q[i + 3], q[i + 2], q[i + 1])
+        .wrapping_add(q[i])
+        .wrapping_add(m[W[i]])
+        .wrapping_add(k)
+        .rotate_left(S[i])
+}
+
+// compute the message word which leads from the preceding states to Q[i + 1]
+fn backward(q: &[u32; 52], i: usize) -> u32 {
+    let (f, k) = function(i);
+
+    q[i + 4]
+        .rotate_right(S[i])
+        .wrapping_sub(f(q[i + 3], q[i + 2], q[i + 1]))
+        .wrapping_sub(q[i])
+        .wrapping_sub(k)
+}
+
+fn function(i: usize) -> (fn(u32, u32, u32) -> u32, u32) {
+    match i {
+        0..=15 => (f, 0),
+        16..=31 => (g, C2),
+        _ => (h, C3),
+    }
+}
+
+// make Q[i + 1] of the second round fulfil its conditions, by changing the message word of step i and computing
+// the state of the first round which uses the same word again, then the following four message words so that the
+// rest of the first round is unchanged
+fn correct(q: &mut [u32; 52], m: &mut [u32; 16], conditions: &[Condition], i: usize) -> bool {
+    let value = forward(q, m, i);
+    q[i + 4] = conditions[i + 4].fix(value, q[i + 3], q[i + 2]);
+    if q[i + 4] == value {
+        return true;
+    }
+
+    let j = W[i];
+    m[j] = backward(q, i);
+    q[j + 4] = forward(q, m, j);
+    for (k, word) in m.iter_mut().enumerate().skip(j + 1).take(4) {
+        *word = backward(q, k);
+    }
+
+    // the new state must still fulfil its own conditions, and those of the two states which refer to it
+    (j..j + 3).all(|k| conditions[k + 4].holds(q[k + 4], q[k + 3], q[k + 2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn md4_collision_block() {
+        for seed in 0..4 {
+            let iv = [0x01234567, 0x89abcdef, 0xfedcba98, 0x76543210];
+            let (first, second) = block(iv, &mut Rng(seed));
+
+            assert_ne!(first, second);
+            assert_eq!(compress(iv, &first), compress(iv, &second));
+        }
+    }
+}
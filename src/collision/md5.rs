@@ -0,0 +1,540 @@
+use super::{Condition, Rng};
+use crate::hash::{
+    md5::{compress, round_function, K, S, W},
+    words_to_bytes_le,
+};
+use alloc::vec::Vec;
+
+// a differential path for a single block: the difference between the two message blocks, the states which differ
+// in their most significant bit ('x'), and the conditions on the states Q[-3] to Q[64], where Q[-3], Q[-2], Q[-1]
+// and Q[0] are the chaining value (a, d, c, b), and Q[i + 1] is the word computed in step i
+//
+// both paths were derived from the first MD5 collision published by Wang et al. (2004)
+struct Path {
+    message: [u32; 16],
+    msb: &'static str,
+    conditions: [&'static str; 68],
+}
+
+// the path of the first block, from the chaining value to a near-collision with the differences which the second
+// block expects
+const FIRST: Path = Path {
+    message: [
+        0, 0, 0, 0, 0x80000000, 0, 0, 0, 0, 0, 0, 0x00008000, 0, 0, 0x80000000, 0,
+    ],
+    msb: ".........xx.xxxxxxxxxxxxxx............xxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+    conditions: [
+        "................................", // Q-3
+        "................................", // Q-2
+        "................................", // Q-1
+        "................................", // Q0
+        "................................", // Q1
+        "................................", // Q2
+        ".........01001110011001100......", // Q3
+        "........001011110011101100......", // Q4
+        "^00010000-++++++++++++++++100101", // Q5
+        "!0000010+1111111101111000-000001", // Q6
+        "0+++++---11111101111-+++++-+++++", // Q7
+        "00000001-..-+++-+.0101010100000+", // Q8
+        "11111011...100000.11111-++1111+-", // Q9
+        "01......0..1111111+-...001....00", // Q10
+        "0+....10....00011100...011....10", // Q11
+        "00....10....-++++++1...-+.......", // Q12
+        "01....+-....1111111....00...1...", // Q13
+        "0.0...00....1011111....11...1...", // Q14
+        "..1...01........-...........+...", // Q15
+        "^.-.............1...........0...", // Q16
+        "^.1...........0.1...........0...", // Q17
+        "^.1...........1.................", // Q18
+        "^.............+.................", // Q19
+        "^.............0.................", // Q20
+        "^.............0.................", // Q21
+        "^...............................", // Q22
+        "0...............................", // Q23
+        "1...............................", // Q24
+        "................................", // Q25
+        "................................", // Q26
+        "................................", // Q27
+        "................................", // Q28
+        "................................", // Q29
+        "................................", // Q30
+        "................................", // Q31
+        "................................", // Q32
+        "................................", // Q33
+        "................................", // Q34
+        "................................", // Q35
+        "................................", // Q36
+        "................................", // Q37
+        "................................", // Q38
+        "................................", // Q39
+        "................................", // Q40
+        "................................", // Q41
+        "................................", // Q42
+        "................................", // Q43
+        "................................", // Q44
+        "................................", // Q45
+        "................................", // Q46
+        "................................", // Q47
+        "m...............................", // Q48
+        "m...............................", // Q49
+        "#...............................", // Q50
+        "m...............................", // Q51
+        "m...............................", // Q52
+        "m...............................", // Q53
+        "m...............................", // Q54
+        "m...............................", // Q55
+        "m...............................", // Q56
+        "m...............................", // Q57
+        "m...............................", // Q58
+        "m...............................", // Q59
+        "#.....0.........................", // Q60
+        "m.....1.........................", // Q61
+        "m.....+.........................", // Q62
+        "m.....+.........................", // Q63
+        "......+.........................", // Q64
+    ],
+};
+
+// the path of the second block, from the near-collision back to equal chaining values
+const SECOND: Path = Path {
+    message: [
+        0, 0, 0, 0, 0x80000000, 0, 0, 0, 0, 0, 0, 0xffff8000, 0, 0, 0x80000000, 0,
+    ],
+    msb: "xxxxxxxxxxxxxxxxxxxxxxxxxx............xxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+    conditions: [
+        "................................", // Q-3
+        ".....0+.........................", // Q-2
+        "^....+-.........................", // Q-1
+        "^....0+...................0.....", // Q0
+        "!10101+...101111...10...000.....", // Q1
+        "^10111+...001111...11...00+0000.", // Q2
+        "^+-----...+-----...+-101+--00111", // Q3
+        "^0111+-...000100...0010000+-+++1", // Q4
+        "!10010....101111...+---++-01000+", // Q5
+        "^..001010.-+..-+0..0110001010110", // Q6
+        "!..-+1110.00..010..111-+++....01", // Q7
+        "^..00-+++.11..-+-..0..1111....00", // Q8
+        "^..111000.....010..0..+---....+-", // Q9
+        "^....1111...1011111+..1111....00", // Q10
+        "^0100001....11011110..1111....11", // Q11
+        "^0100001....-++++++1....-.......", // Q12
+        "!+------....1111111.....0...1...", // Q13
+        "^1000000....1011111.....1...1...", // Q14
+        ".1111101........+...........+...", // Q15
+        "^.-.............1...........0...", // Q16
+        "^.1...........0.1...........0...", // Q17
+        "^.1...........1.................", // Q18
+        "^.............+.................", // Q19
+        "^.............0.................", // Q20
+        "^.............0.................", // Q21
+        "^...............................", // Q22
+        "0...............................", // Q23
+        "1...............................", // Q24
+        "................................", // Q25
+        "................................", // Q26
+        "................................", // Q27
+        "................................", // Q28
+        "................................", // Q29
+        "................................", // Q30
+        "................................", // Q31
+        "................................", // Q32
+        "................................", // Q33
+        "................................", // Q34
+        "................................", // Q35
+        "................................", // Q36
+        "................................", // Q37
+        "................................", // Q38
+        "................................", // Q39
+        "................................", // Q40
+        "................................", // Q41
+        "................................", // Q42
+        "................................", // Q43
+        "................................", // Q44
+        "................................", // Q45
+        "................................", // Q46
+        "................................", // Q47
+        "m...............................", // Q48
+        "m...............................", // Q49
+        "#...............................", // Q50
+        "m...............................", // Q51
+        "m...............................", // Q52
+        "m...............................", // Q53
+        "m...............................", // Q54
+        "m...............................", // Q55
+        "m...............................", // Q56
+        "m...............................", // Q57
+        "m...............................", // Q58
+        "m...............................", // Q59
+        "#.....0.........................", // Q60
+        "m.....1.........................", // Q61
+        "m.....-.........................", // Q62
+        "m.....-.........................", // Q63
+        "...-+++.........................", // Q64
+    ],
+};
+
+// the states whose free bits are made into tunnels, in order of precedence
+const TUNNELS: [usize; 3] = [9, 10, 4];
+
+// find the first block, which leads to the near-collision which the second block cancels
+pub fn first_block(iv: [u32; 4], rng: &mut Rng) -> ([u8; 64], [u8; 64]) {
+    let mut search = Search::new(&FIRST, iv, Some(&SECOND));
+
+    loop {
+        search.base(rng);
+        for _ in 0..1 << 16 {
+            if search.first_round_two(rng) && search.tunnels() {
+                return search.blocks();
+            }
+        }
+    }
+}
+
+// find the second block, which cancels the differences between the chaining values of the two messages
+pub fn second_block(iv: [u32; 4], other: [u32; 4], rng: &mut Rng) -> ([u8; 64], [u8; 64]) {
+    let mut search = Search::new(&SECOND, iv, None);
+    assert!(
+        search.holds_iv() && search.chaining_value(true) == other,
+        "The chaining values must have the differences which the second block expects"
+    );
+
+    loop {
+        search.base(rng);
+        for _ in 0..1 << 20 {
+            if search.second_round_two(rng) && search.tunnels() {
+                return search.blocks();
+            }
+        }
+    }
+}
+
+// the state of the search for a single block, with the states Q[t] stored at index t + 3
+struct Search {
+    conditions: [Condition; 68],
+    // the xor difference of each state between the two messages
+    difference: [u32; 68],
+    // the bits of each state which neither have conditions, nor are referred to by the conditions of later states
+    free: [u32; 68],
+    message: [u32; 16],
+    // the path which the chaining value after this block has to fit, if any
+    next: Option<&'static Path>,
+    q: [u32; 68],
+    m: [u32; 16],
+}
+
+impl Search {
+    fn new(path: &Path, [a, b, c, d]: [u32; 4], next: Option<&'static Path>) -> Self {
+        let conditions = path.conditions.map(Condition::parse);
+        let difference = core::array::from_fn(|t| {
+            let msb = u32::from(path.msb.as_bytes()[t] == b'x') << 31;
+            path.conditions[t].bytes().fold(0, |difference, c| {
+                (difference << 1) | u32::from(matches!(c, b'+' | b'-'))
+            }) | msb
+        });
+        let free = core::array::from_fn(|t| {
+            let (previous, _) = conditions.get(t + 1).map_or((0, 0), Condition::relations);
+            let (_, second) = conditions.get(t + 2).map_or((0, 0), Condition::relations);
+            !conditions[t].mask & !previous & !second
+        });
+        let mut q = [0; 68];
+        q[..4].copy_from_slice(&[a, d, c, b]);
+
+        Self {
+            conditions,
+            difference,
+            free,
+            message: path.message,
+            next,
+            q,
+            m: [0; 16],
+        }
+    }
+
+    // compute Q[i + 1] of the first message from the preceding states
+    fn forward(&self, i: usize) -> u32 {
+        let q = &self.q;
+
+        round_function(i, q[i + 3], q[i + 2], q[i + 1])
+            .wrapping_add(q[i])
+            .wrapping_add(K[i])
+            .wrapping_add(self.m[W[i]])
+            .rotate_left(S[i])
+            .wrapping_add(q[i + 3])
+    }
+
+    // compute the message word which leads from the preceding states to Q[i + 1]
+    fn backward(&self, i: usize) -> u32 {
+        let q = &self.q;
+
+        q[i + 4]
+            .wrapping_sub(q[i + 3])
+            .rotate_right(S[i])
+            .wrapping_sub(round_function(i, q[i + 3], q[i + 2], q[i + 1]))
+            .wrapping_sub(q[i])
+            .wrapping_sub(K[i])
+    }
+
+    // whether Q[i + 1] fulfils its conditions
+    fn holds(&self, i: usize) -> bool {
+        self.conditions[i + 4].holds(self.q[i + 4], self.q[i + 3], self.q[i + 2])
+    }
+
+    // whether step i of the second message leads to the states which the path expects
+    fn differential(&self, i: usize) -> bool {
+        let q = |t: usize| self.q[t] ^ self.difference[t];
+        let word = self.m[W[i]].wrapping_add(self.message[W[i]]);
+
+        round_function(i, q(i + 3), q(i + 2), q(i + 1))
+            .wrapping_add(q(i))
+            .wrapping_add(K[i])
+            .wrapping_add(word)
+            .rotate_left(S[i])
+            .wrapping_add(q(i + 3))
+            == q(i + 4)
+    }
+
+    // compute the states of the given steps, and check that they follow the path
+    fn recompute(&mut self, steps: core::ops::Range<usize>) -> bool {
+        steps.into_iter().all(|i| {
+            self.q[i + 4] = self.forward(i);
+            self.holds(i) && self.differential(i)
+        })
+    }
+
+    // choose the states of the first round so that they fulfil their conditions, and compute the message from them
+    fn base(&mut self, rng: &mut Rng) {
+        'base: loop {
+            for i in 0..16 {
+                let t = i + 4;
+                let mut tries = 0;
+                loop {
+                    self.q[t] = self.conditions[t].fix(rng.next(), self.q[t - 1], self.q[t - 2]);
+                    self.m[i] = self.backward(i);
+                    if self.differential(i) {
+                        break;
+                    }
+
+                    tries += 1;
+                    if tries == 256 {
+                        continue 'base;
+                    }
+                }
+            }
+
+            return self.plant();
+        }
+    }
+
+    // turn as many free bits of Q[t] as possible into tunnel bits, by setting the same bits of Q[t + 1] to zero and
+    // of Q[t + 2] to one, as long as the first round still follows the path
+    fn plant(&mut self) {
+        let mut claimed = [0u32; 68];
+        for t in TUNNELS {
+            let free = self.free[t + 3];
+            for bit in (0..32).map(|i| 1 << i).filter(|bit| free & bit != 0) {
+                let (q, m) = (self.q, self.m);
+                for (k, value) in [(t + 4, 0), (t + 5, bit)] {
+                    if self.free[k] & !claimed[k] & bit != 0 {
+                        self.q[k] = (self.q[k] & !bit) | value;
+                    }
+                }
+                for i in t..16.min(t + 6) {
+                    self.m[i] = self.backward(i);
+                }
+
+                if self.q[t + 4] & bit == 0
+                    && self.q[t + 5] & bit != 0
+                    && (t..16.min(t + 6)).all(|i| self.differential(i))
+                {
+                    claimed[t + 4] |= bit;
+                    claimed[t + 5] |= bit;
+                } else {
+                    self.q = q;
+                    self.m = m;
+                }
+            }
+        }
+    }
+
+    // choose Q[17], and compute m[1] and Q[2] from it, which works for a path without conditions on Q[2]
+    fn first_round_two(&mut self, rng: &mut Rng) -> bool {
+        // Q[18], Q[19] and Q[20] only depend on Q[17] and the message words m[6], m[11] and m[0], which stay the same
+        self.q[20] = self.conditions[20].fix(rng.next(), self.q[19], self.q[18]);
+        if !self.recompute(17..20) {
+            return false;
+        }
+
+        self.m[1] = self.backward(16);
+        self.q[5] = self.forward(1);
+        if !(1..4).all(|i| self.holds(i)) {
+            return false;
+        }
+        for i in 2..6 {
+            self.m[i] = self.backward(i);
+        }
+
+        (0..6).all(|i| self.differential(i)) && self.differential(16) && self.recompute(20..21)
+    }
+
+    // change the free bits of Q[16], which only changes m[15], and compute the states up to Q[24] again
+    fn second_round_two(&mut self, rng: &mut Rng) -> bool {
+        self.q[19] = (self.q[19] & !self.free[19]) | (rng.next() & self.free[19]);
+        self.m[15] = self.backward(15);
+        self.q[20] = self.forward(16);
+
+        self.holds(16) && self.differential(15) && self.differential(16) && self.recompute(17..21)
+    }
+
+    // try every combination of the tunnels, which keep the states up to Q[24] (mostly) unchanged
+    fn tunnels(&mut self) -> bool {
+        self.tunnel(10, &mut |search| {
+            search.recompute(21..23)
+                && search.tunnel(4, &mut |search| {
+                    search.recompute(23..24) && search.tunnel(9, &mut Self::finish)
+                })
+        })
+    }
+
+    // try every combination of the tunnel bits of Q[t], which are the free bits where Q[t + 1] is zero and Q[t + 2]
+    // is one, so that only the message words of steps t - 1, t and t + 3 change
+    fn tunnel(&mut self, t: usize, body: &mut dyn FnMut(&mut Self) -> bool) -> bool {
+        let (q, m) = (self.q, self.m);
+        let mask = self.free[t + 3] & !q[t + 4] & q[t + 5];
+
+        let mut bits = 0u32;
+        loop {
+            self.q[t + 3] = q[t + 3] ^ bits;
+            for i in [t - 1, t, t + 3] {
+                self.m[i] = self.backward(i);
+            }
+            if self.differential(t - 1) && self.differential(t) && body(self) {
+                return true;
+            }
+
+            // the next subset of the tunnel bits
+            bits = bits.wrapping_sub(mask) & mask;
+            if bits == 0 {
+                self.q = q;
+                self.m = m;
+                return false;
+            }
+        }
+    }
+
+    // compute the remaining steps, and check that the block really ends in the chaining values the path leads to
+    fn finish(&mut self) -> bool {
+        for i in 24..64 {
+            self.q[i + 4] = self.forward(i);
+            if self.conditions[i + 4].mask != 0 && !self.holds(i) {
+                return false;
+            }
+        }
+
+        let (first, second) = self.blocks();
+        let value = compress(self.chaining_value(false), &first);
+        let other = compress(self.chaining_value(true), &second);
+        match self.next {
+            Some(path) => {
+                let next = Self::new(path, value, None);
+                next.holds_iv() && next.chaining_value(true) == other
+            }
+            None => value == other,
+        }
+    }
+
+    // the chaining value before this block, of the first or second message
+    fn chaining_value(&self, second: bool) -> [u32; 4] {
+        let q = |t: usize| self.q[t] ^ if second { self.difference[t] } else { 0 };
+
+        [q(0), q(3), q(2), q(1)]
+    }
+
+    // whether the chaining value fulfils the conditions of the path
+    fn holds_iv(&self) -> bool {
+        (1..4).all(|t| {
+            self.conditions[t].holds(self.q[t], self.q[t - 1], self.q[t.saturating_sub(2)])
+        })
+    }
+
+    // the message blocks of both messages
+    fn blocks(&self) -> ([u8; 64], [u8; 64]) {
+        let other: Vec<_> = self
+            .m
+            .iter()
+            .zip(self.message)
+            .map(|(word, difference)| word.wrapping_add(difference))
+            .collect();
+
+        (words_to_bytes_le(self.m), words_to_bytes_le(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::md5::{A, B, C, D};
+    use crate::Digest;
+    use core::convert::TryInto;
+
+    // the first MD5 collision, published by Wang et al. (2004)
+    const FIRST_MESSAGE: &str = "d131dd02c5e6eec4693d9a0698aff95c2fcab58712467eab4004583eb8fb7f8955ad340609f4b30283e488832571415a085125e8f7cdc99fd91dbdf280373c5bd8823e3156348f5bae6dacd436c919c6dd53e2b487da03fd02396306d248cda0e99f33420f577ee8ce54b67080a80d1ec69821bcb6a8839396f9652b6ff72a70";
+    const SECOND_MESSAGE: &str = "d131dd02c5e6eec4693d9a0698aff95c2fcab50712467eab4004583eb8fb7f8955ad340609f4b30283e4888325f1415a085125e8f7cdc99fd91dbd7280373c5bd8823e3156348f5bae6dacd436c919c6dd53e23487da03fd02396306d248cda0e99f33420f577ee8ce54b67080280d1ec69821bcb6a8839396f965ab6ff72a70";
+
+    fn blocks(message: &str) -> ([u8; 64], [u8; 64]) {
+        let message: Digest<128> = message.parse().unwrap();
+        let (first, second) = message.as_ref().split_at(64);
+
+        (first.try_into().unwrap(), second.try_into().unwrap())
+    }
+
+    // compute every state of the block, and check that both messages follow the path
+    fn follows(path: &'static Path, iv: [u32; 4], block: &[u8; 64], other: &[u8; 64]) -> bool {
+        let mut search = Search::new(path, iv, None);
+        search.m = crate::hash::bytes_to_words_le(block);
+
+        search.holds_iv()
+            && search.blocks().1 == *other
+            && (0..64).all(|i| {
+                search.q[i + 4] = search.forward(i);
+                search.holds(i) && search.differential(i)
+            })
+    }
+
+    #[test]
+    fn md5_collision_paths() {
+        let (first, third) = blocks(FIRST_MESSAGE);
+        let (second, fourth) = blocks(SECOND_MESSAGE);
+        let iv = [A, B, C, D];
+        let (value, other) = (compress(iv, &first), compress(iv, &second));
+
+        assert!(follows(&FIRST, iv, &first, &second));
+        assert!(follows(&SECOND, value, &third, &fourth));
+        assert_eq!(compress(value, &third), compress(other, &fourth));
+    }
+
+    #[test]
+    fn md5_collision_blocks() {
+        // the blocks of a collision found by the search, which follow both paths from the chaining value of a prefix
+        let collision = crate::collision::tests::md5_lore();
+        let (first, third) = collision.first.split_at(64);
+        let (second, fourth) = collision.second.split_at(64);
+        let (first, second, third, fourth): ([u8; 64], [u8; 64], [u8; 64], [u8; 64]) = (
+            first.try_into().unwrap(),
+            second.try_into().unwrap(),
+            third.try_into().unwrap(),
+            fourth.try_into().unwrap(),
+        );
+        let iv = compress([A, B, C, D], &collision.prefix.try_into().unwrap());
+        let (value, other) = (compress(iv, &first), compress(iv, &second));
+
+        assert!(follows(&FIRST, iv, &first, &second));
+
+        // the near-collision is the one which the second block cancels
+        let search = Search::new(&SECOND, value, None);
+        assert!(search.holds_iv());
+        assert_eq!(other, search.chaining_value(true));
+
+        assert!(follows(&SECOND, value, &third, &fourth));
+        assert_eq!(compress(value, &third), compress(other, &fourth));
+    }
+}
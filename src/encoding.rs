@@ -0,0 +1,350 @@
+use crate::hash::ParseDigestError;
+use alloc::{string::String, vec::Vec};
+
+// based on RFC 4648, The Base16, Base32, and Base64 Data Encodings
+pub const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+pub const BASE64URL: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+pub const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+// the alphabet used by bitcoin, which leaves out 0, O, I and l since they are easily confused
+pub const BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// whether the output is padded with '=' to a multiple of the group size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    Required,
+    Optional,
+}
+
+// the number of characters needed to encode the given number of bytes, without padding
+fn unpadded_length(length: usize, bits: usize) -> usize {
+    (length * 8).div_ceil(bits)
+}
+
+// the number of characters in a padded group, which encodes a whole number of bytes
+fn group_length(bits: usize) -> usize {
+    if bits == 6 {
+        4
+    } else {
+        8
+    }
+}
+
+// encode bytes with an alphabet of 2^bits characters, most significant bits first
+pub fn encode(bytes: &[u8], alphabet: &[u8], padding: Padding) -> String {
+    let bits = alphabet.len().trailing_zeros() as usize;
+    let mask = alphabet.len() - 1;
+
+    let mut output = String::with_capacity(unpadded_length(bytes.len(), bits));
+    let (mut buffer, mut filled) = (0usize, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as usize;
+        filled += 8;
+        while filled >= bits {
+            filled -= bits;
+            output.push(alphabet[(buffer >> filled) & mask] as char);
+        }
+    }
+
+    // the last character is filled up with zero bits
+    if filled > 0 {
+        output.push(alphabet[(buffer << (bits - filled)) & mask] as char);
+    }
+
+    if padding == Padding::Required {
+        while !output.len().is_multiple_of(group_length(bits)) {
+            output.push('=');
+        }
+    }
+
+    output
+}
+
+// decode a string which should encode exactly the given number of bytes
+pub fn decode(
+    s: &str,
+    length: usize,
+    alphabet: &[u8],
+    padding: Padding,
+) -> Result<Vec<u8>, ParseDigestError> {
+    let bits = alphabet.len().trailing_zeros() as usize;
+    let unpadded = unpadded_length(length, bits);
+    let padded = unpadded.next_multiple_of(group_length(bits));
+
+    // check every character first, like when parsing hexadecimal strings
+    let mut values = Vec::with_capacity(unpadded);
+    for (position, (index, character)) in s.char_indices().enumerate() {
+        // alphabets without lowercase letters are case-insensitive
+        let value = alphabet
+            .iter()
+            .position(|&c| c as char == character)
+            .or_else(|| {
+                let upper = character.to_ascii_uppercase();
+                alphabet.iter().position(|&c| c as char == upper)
+            });
+        match (value, character) {
+            (Some(value), _) if position < unpadded => values.push(value),
+            (Some(_), _) | (None, '=') if position >= unpadded => {}
+            _ => return Err(ParseDigestError::InvalidCharacter { character, index }),
+        }
+    }
+
+    let found = s.chars().count();
+    let valid = match padding {
+        Padding::Required => found == padded,
+        Padding::Optional => found == padded || found == unpadded,
+    };
+    if !valid {
+        return Err(ParseDigestError::InvalidLength {
+            expected: if padding == Padding::Required {
+                padded
+            } else {
+                unpadded
+            },
+            found,
+        });
+    }
+
+    // the characters after the encoded bytes can only be padding
+    if let Some((index, character)) = s.char_indices().skip(unpadded).find(|&(_, c)| c != '=') {
+        return Err(ParseDigestError::InvalidCharacter { character, index });
+    }
+
+    let mut output = Vec::with_capacity(length);
+    let (mut buffer, mut filled) = (0usize, 0);
+    for value in values {
+        buffer = (buffer << bits) | value;
+        filled += bits;
+        if filled >= 8 {
+            filled -= 8;
+            output.push((buffer >> filled) as u8);
+        }
+    }
+
+    // the bits which fill up the last character must be zero, so that every digest has a single encoding
+    if buffer & ((1 << filled) - 1) != 0 {
+        let (index, character) = s.char_indices().nth(unpadded - 1).unwrap();
+        return Err(ParseDigestError::InvalidCharacter { character, index });
+    }
+
+    Ok(output)
+}
+
+// encode bytes as a big-endian number in base 58, where every leading zero byte is encoded as a leading '1'
+pub fn encode_base58(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+
+    // repeatedly divide the number by 58, collecting the remainders as little-endian digits
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in &bytes[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    core::iter::repeat_n('1', zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|&digit| BASE58[digit as usize] as char),
+        )
+        .collect()
+}
+
+// decode a base 58 string, which should encode exactly the given number of bytes
+pub fn decode_base58(s: &str, length: usize) -> Result<Vec<u8>, ParseDigestError> {
+    let zeros = s.chars().take_while(|&c| c == '1').count();
+
+    // multiply the number by 58 for every digit, keeping the bytes in little-endian order
+    let mut bytes: Vec<u8> = Vec::new();
+    for (index, character) in s.char_indices().skip(zeros) {
+        let mut carry = BASE58
+            .iter()
+            .position(|&c| c as char == character)
+            .ok_or(ParseDigestError::InvalidCharacter { character, index })?
+            as u32;
+        for byte in &mut bytes {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let found = zeros + bytes.len();
+    if found != length {
+        return Err(ParseDigestError::InvalidLength {
+            expected: length,
+            found,
+        });
+    }
+
+    Ok(core::iter::repeat_n(0, zeros)
+        .chain(bytes.into_iter().rev())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // test vectors from RFC 4648
+    const RFC4648: [(&str, &str, &str); 7] = [
+        ("", "", ""),
+        ("f", "Zg==", "MY======"),
+        ("fo", "Zm8=", "MZXQ===="),
+        ("foo", "Zm9v", "MZXW6==="),
+        ("foob", "Zm9vYg==", "MZXW6YQ="),
+        ("fooba", "Zm9vYmE=", "MZXW6YTB"),
+        ("foobar", "Zm9vYmFy", "MZXW6YTBOI======"),
+    ];
+
+    #[test]
+    fn base64_works() {
+        for (input, base64, _) in RFC4648 {
+            assert_eq!(base64, encode(input.as_bytes(), BASE64, Padding::Required));
+            assert_eq!(
+                Ok(input.as_bytes().to_vec()),
+                decode(base64, input.len(), BASE64, Padding::Required)
+            );
+            assert_eq!(
+                base64.trim_end_matches('='),
+                encode(input.as_bytes(), BASE64URL, Padding::Optional)
+            );
+        }
+
+        assert_eq!("-_8", encode(&[0xfb, 0xff], BASE64URL, Padding::Optional));
+        assert_eq!("+/8=", encode(&[0xfb, 0xff], BASE64, Padding::Required));
+        assert_eq!(
+            Ok(vec![0xfb, 0xff]),
+            decode("-_8=", 2, BASE64URL, Padding::Optional)
+        );
+        assert_eq!(
+            Ok(vec![0xfb, 0xff]),
+            decode("-_8", 2, BASE64URL, Padding::Optional)
+        );
+    }
+
+    #[test]
+    fn base64_errors() {
+        assert_eq!(
+            Err(ParseDigestError::InvalidLength {
+                expected: 4,
+                found: 3
+            }),
+            decode("Zm8", 2, BASE64, Padding::Required)
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidLength {
+                expected: 4,
+                found: 8
+            }),
+            decode("Zm9vYmFy", 2, BASE64, Padding::Required)
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: '-',
+                index: 1
+            }),
+            decode("Z-8=", 2, BASE64, Padding::Required)
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: '=',
+                index: 1
+            }),
+            decode("Z=8=", 2, BASE64, Padding::Required)
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: 'A',
+                index: 3
+            }),
+            decode("Zm8A", 2, BASE64, Padding::Required)
+        );
+
+        // the unused bits of the last character must be zero
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: '9',
+                index: 2
+            }),
+            decode("Zm9=", 2, BASE64, Padding::Required)
+        );
+    }
+
+    #[test]
+    fn base32_works() {
+        for (input, _, base32) in RFC4648 {
+            assert_eq!(base32, encode(input.as_bytes(), BASE32, Padding::Required));
+            assert_eq!(
+                Ok(input.as_bytes().to_vec()),
+                decode(base32, input.len(), BASE32, Padding::Required)
+            );
+            assert_eq!(
+                Ok(input.as_bytes().to_vec()),
+                decode(
+                    &base32.to_lowercase(),
+                    input.len(),
+                    BASE32,
+                    Padding::Required
+                )
+            );
+        }
+
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: '1',
+                index: 2
+            }),
+            decode("MZ1W6===", 3, BASE32, Padding::Required)
+        );
+    }
+
+    #[test]
+    fn base58_works() {
+        let vectors: [(&[u8], &str); 6] = [
+            (b"", ""),
+            (b"\0", "1"),
+            (b"\0\0\x01", "112"),
+            (b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+            (
+                b"The quick brown fox jumps over the lazy dog.",
+                "USm3fpXnKG5EUBx2ndxBDMPVciP5hGey2Jh4NDv6gmeo1LkMeiKrLJUUBk6Z",
+            ),
+            (&[0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+        ];
+
+        for (bytes, base58) in vectors {
+            assert_eq!(base58, encode_base58(bytes));
+            assert_eq!(Ok(bytes.to_vec()), decode_base58(base58, bytes.len()));
+        }
+
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: '0',
+                index: 3
+            }),
+            decode_base58("112033QC4", 6)
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidLength {
+                expected: 7,
+                found: 6
+            }),
+            decode_base58("11233QC4", 7)
+        );
+    }
+}
@@ -1,11 +1,42 @@
+#[cfg(feature = "alloc")]
+use crate::encoding::{self, Padding as Encoding};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::{
+    convert::{TryFrom, TryInto},
+    error::Error,
+    fmt::{Debug, Display, LowerHex, UpperHex},
+    hash::Hash,
+    str::FromStr,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, hash::Hash};
 
+pub mod blake2b;
+pub mod blake2s;
+pub mod blake3;
+pub mod keccak;
 pub mod md2;
 pub mod md4;
 pub mod md5;
+pub mod ripemd128;
+pub mod ripemd160;
+pub mod ripemd256;
+pub mod ripemd320;
 pub mod sha1;
+pub mod sha1dc;
+pub mod sha224;
+pub mod sha256;
+pub mod sha384;
+pub mod sha3_224;
+pub mod sha3_256;
+pub mod sha3_384;
+pub mod sha3_512;
+pub mod sha512;
+pub mod sha512_224;
+pub mod sha512_256;
+pub mod shake128;
+pub mod shake256;
 
 /// A variable-size digest, which can easily be converted into a hexadecimal string for user-facing output.
 ///
@@ -19,20 +50,180 @@ pub mod sha1;
 /// let digest = lore::md5("example");
 /// println!("Digest: {}", digest); // -> Digest: 1a79a4d60de6718e8e5b326e338ae533
 /// ```
+///
+/// A hexadecimal string in either upper or lower case can be parsed back into a [`Digest`], e.g. to compare it with a known checksum:
+///
+/// ```rust
+/// let expected: lore::Digest<16> = "1A79A4D60DE6718E8E5B326E338AE533".parse().unwrap();
+/// assert_eq!(lore::md5("example"), expected);
+/// ```
+///
+/// # Constant-time comparison
+///
+/// Comparing digests with `==` or ordering them stops at the first differing byte, which leaks through timing where the bytes differ.
+/// This is fine for checksums, but message authentication codes and password hashes should be compared in constant time instead.
+/// The following comparisons in the crate run in time which only depends on the length of the inputs:
+///
+/// -   [`Digest::constant_time_eq`]
+/// -   [`Hasher::verify`], which is available for every hasher, e.g. `lore::Md5::verify`
+/// -   [`crate::Shake128::verify`] and [`crate::Shake256::verify`]
+#[cfg_attr(feature = "alloc", doc = "-   [`crate::Algorithm::verify`]")]
+/// -   [`crate::Hmac::verify`]
+///
+/// Parsing and encoding digests, e.g. with [`str::parse`] or `Digest::to_base64`, is not constant-time.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Digest<const S: usize>([u8; S]);
 
 /// Convert the digest into a hexadecimal string representation.
 impl<const S: usize> Display for Digest<S> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(
-            &self
-                .0
-                .iter()
-                .map(|u| format!("{:02x}", u))
-                .collect::<String>(),
-        )
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.iter().try_for_each(|u| write!(f, "{u:02x}"))
+    }
+}
+
+/// Format the digest as lowercase hexadecimal, with a `0x` prefix in the alternate form (`{:#x}`).
+impl<const S: usize> LowerHex for Digest<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        self.0.iter().try_for_each(|u| write!(f, "{u:02x}"))
+    }
+}
+
+/// Format the digest as uppercase hexadecimal, with a `0x` prefix in the alternate form (`{:#X}`).
+impl<const S: usize> UpperHex for Digest<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        self.0.iter().try_for_each(|u| write!(f, "{u:02X}"))
+    }
+}
+
+impl<const S: usize> Digest<S> {
+    /// Check whether the digest is equal to the other bytes, in time which doesn't depend on where they differ.
+    ///
+    /// Inputs of a different length are never equal, and the length itself is not kept secret.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let digest = lore::sha256("message");
+    ///
+    /// assert!(digest.constant_time_eq(lore::sha256("message")));
+    /// assert!(!digest.constant_time_eq(lore::sha256("massage")));
+    /// assert!(!digest.constant_time_eq(&digest.as_ref()[..16]));
+    /// ```
+    #[must_use]
+    pub fn constant_time_eq(&self, other: impl AsRef<[u8]>) -> bool {
+        constant_time_eq(&self.0, other.as_ref())
+    }
+
+    /// Encode the digest as padded Base64, as used by e.g. `Content-MD5` headers and subresource integrity.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// assert_eq!(lore::md5("").to_base64(), "1B2M2Y8AsgTpgAmY7PhCfg==");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_base64(&self) -> String {
+        encoding::encode(&self.0, encoding::BASE64, Encoding::Required)
+    }
+
+    /// Decode a digest from padded Base64.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not the padded Base64 encoding of `S` bytes.
+    #[cfg(feature = "alloc")]
+    pub fn from_base64(s: &str) -> Result<Self, ParseDigestError> {
+        encoding::decode(s, S, encoding::BASE64, Encoding::Required)
+            .and_then(|bytes| Self::try_from(&bytes[..]))
+    }
+
+    /// Encode the digest as unpadded Base64url, which can be used in URLs and file names.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// assert_eq!(lore::md5("").to_base64url(), "1B2M2Y8AsgTpgAmY7PhCfg");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_base64url(&self) -> String {
+        encoding::encode(&self.0, encoding::BASE64URL, Encoding::Optional)
+    }
+
+    /// Decode a digest from Base64url, with or without padding.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not the Base64url encoding of `S` bytes.
+    #[cfg(feature = "alloc")]
+    pub fn from_base64url(s: &str) -> Result<Self, ParseDigestError> {
+        encoding::decode(s, S, encoding::BASE64URL, Encoding::Optional)
+            .and_then(|bytes| Self::try_from(&bytes[..]))
+    }
+
+    /// Encode the digest as padded, uppercase Base32.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// assert_eq!(lore::md5("").to_base32(), "2QOYZWMPACZAJ2MABGMOZ6CCPY======");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_base32(&self) -> String {
+        encoding::encode(&self.0, encoding::BASE32, Encoding::Required)
+    }
+
+    /// Decode a digest from padded Base32, in either upper or lower case.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not the padded Base32 encoding of `S` bytes.
+    #[cfg(feature = "alloc")]
+    pub fn from_base32(s: &str) -> Result<Self, ParseDigestError> {
+        encoding::decode(s, S, encoding::BASE32, Encoding::Required)
+            .and_then(|bytes| Self::try_from(&bytes[..]))
+    }
+
+    /// Encode the digest as Base58, using the alphabet of Bitcoin.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// assert_eq!(lore::md5("").to_base58(), "TCByYo9r1su7nMQP3WHDFK");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_base58(&self) -> String {
+        encoding::encode_base58(&self.0)
+    }
+
+    /// Decode a digest from Base58, using the alphabet of Bitcoin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string contains characters outside the alphabet, or doesn't encode exactly `S` bytes.
+    #[cfg(feature = "alloc")]
+    pub fn from_base58(s: &str) -> Result<Self, ParseDigestError> {
+        encoding::decode_base58(s, S).and_then(|bytes| Self::try_from(&bytes[..]))
     }
 }
 
@@ -48,6 +239,7 @@ impl<'a, const S: usize> From<&'a Digest<S>> for &'a [u8] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<const S: usize> From<Digest<S>> for Vec<u8> {
     fn from(digest: Digest<S>) -> Self {
         digest.0.to_vec()
@@ -60,46 +252,737 @@ impl<const S: usize> AsRef<[u8]> for Digest<S> {
     }
 }
 
-pub fn bytes_to_words_le(bytes: impl AsRef<[u8]>) -> Vec<u32> {
+/// Parse the digest from a hexadecimal string representation, in either upper or lower case.
+impl<const S: usize> FromStr for Digest<S> {
+    type Err = ParseDigestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // check every character first, so an invalid character is reported even if the length is wrong as well
+        let mut digest = [0; S];
+        let mut found = 0;
+        for (index, character) in s.char_indices() {
+            let nibble = character
+                .to_digit(16)
+                .ok_or(ParseDigestError::InvalidCharacter { character, index })?;
+            if let Some(byte) = digest.get_mut(found / 2) {
+                *byte = (*byte << 4) | nibble as u8;
+            }
+            found += 1;
+        }
+
+        if found != 2 * S {
+            return Err(ParseDigestError::InvalidLength {
+                expected: 2 * S,
+                found,
+            });
+        }
+
+        Ok(Digest(digest))
+    }
+}
+
+impl<const S: usize> TryFrom<&str> for Digest<S> {
+    type Error = ParseDigestError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<const S: usize> TryFrom<&[u8]> for Digest<S> {
+    type Error = ParseDigestError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        bytes
+            .try_into()
+            .map(Digest)
+            .map_err(|_| ParseDigestError::InvalidLength {
+                expected: S,
+                found: bytes.len(),
+            })
+    }
+}
+
+/// The error returned when parsing a [`Digest`] from an encoded string, or converting it from a slice of bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseDigestError {
+    /// The input is too short or too long.
+    ///
+    /// The length is counted in characters for hexadecimal, Base64 and Base32 strings, and in bytes for slices and Base58 strings, since the length of a Base58 string depends on its value.
+    InvalidLength {
+        /// The length of the digest.
+        expected: usize,
+        /// The length of the input.
+        found: usize,
+    },
+    /// The input contains a character which is not valid in the encoding, e.g. a character which is not a hexadecimal digit.
+    InvalidCharacter {
+        /// The invalid character.
+        character: char,
+        /// The position of the character in the input, in bytes.
+        index: usize,
+    },
+}
+
+impl Display for ParseDigestError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidLength { expected, found } => {
+                write!(
+                    f,
+                    "invalid digest length: expected {expected}, found {found}"
+                )
+            }
+            Self::InvalidCharacter { character, index } => {
+                write!(f, "invalid character {character:?} at position {index}")
+            }
+        }
+    }
+}
+
+impl Error for ParseDigestError {}
+
+/// A hash function which consumes its input incrementally.
+///
+/// This trait is implemented by every hasher in the crate, which makes it possible to write code once for any algorithm.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use lore::Hasher;
+///
+/// fn checksum<H: Hasher>(parts: &[&str]) -> String {
+///     let mut hasher = H::default();
+///     for part in parts {
+///         hasher.update(part);
+///     }
+///     hasher.finalize().to_string()
+/// }
+///
+/// assert_eq!(checksum::<lore::Md5>(&["lol", " xd"]), "982d7f24f8985a6baa5cf129acc73561");
+/// assert_eq!(checksum::<lore::Sha1>(&["a", "bc"]), lore::Sha1::digest("abc").to_string());
+/// ```
+pub trait Hasher: Default + Clone {
+    /// The size of the blocks consumed by the compression function, in bytes.
+    const BLOCK_SIZE: usize;
+    /// The size of the digest, in bytes.
+    const OUTPUT_SIZE: usize;
+
+    /// The digest returned by the hasher.
+    type Output: AsRef<[u8]> + Clone + Eq + Debug + Display;
+
+    /// Append data to the message being hashed.
+    fn update(&mut self, data: impl AsRef<[u8]>);
+
+    /// Pad the message and return the digest of everything passed to [`Hasher::update`].
+    fn finalize(self) -> Self::Output;
+
+    /// Discard the message hashed so far, returning the hasher to its initial state.
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Compute the digest of a complete message in one go.
+    fn digest(message: impl AsRef<[u8]>) -> Self::Output {
+        let mut hasher = Self::default();
+        hasher.update(message);
+        hasher.finalize()
+    }
+
+    /// Check whether the digest of the message is equal to the expected digest, in constant time.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::Hasher;
+    ///
+    /// let expected = lore::sha1("lol xd");
+    ///
+    /// assert!(lore::Sha1::verify("lol xd", &expected));
+    /// assert!(!lore::Sha1::verify("lol xD", &expected));
+    /// ```
+    fn verify(message: impl AsRef<[u8]>, expected: impl AsRef<[u8]>) -> bool {
+        constant_time_eq(Self::digest(message).as_ref(), expected.as_ref())
+    }
+
+    /// Compute the digest of everything the reader produces, until it reaches the end.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the reader, other than [`std::io::ErrorKind::Interrupted`], which is retried.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::Hasher;
+    ///
+    /// let reader = "lol xd".as_bytes();
+    ///
+    /// assert_eq!(lore::Sha1::hash_reader(reader).unwrap(), lore::sha1("lol xd"));
+    /// ```
+    #[cfg(feature = "std")]
+    fn hash_reader(mut reader: impl std::io::Read) -> std::io::Result<Self::Output> {
+        let mut hasher = crate::io::Update(Self::default());
+        std::io::copy(&mut reader, &mut hasher)?;
+
+        Ok(hasher.0.finalize())
+    }
+
+    /// Compute the digest of a file, which is read in chunks, so it never has to fit in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened or read.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::Hasher;
+    ///
+    /// let digest = lore::Sha256::hash_file("Cargo.toml").unwrap();
+    ///
+    /// assert_eq!(digest, lore::sha256(std::fs::read("Cargo.toml").unwrap()));
+    /// ```
+    #[cfg(feature = "std")]
+    fn hash_file(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self::Output, crate::io::HashFileError> {
+        let path = path.as_ref();
+        std::fs::File::open(path)
+            .and_then(Self::hash_reader)
+            .map_err(|source| crate::io::HashFileError::new(path, source))
+    }
+
+    /// Compute the digest of a file by mapping it into memory, which is usually faster for large files.
+    ///
+    /// The file must not be truncated or modified by another process while it is hashed, which may crash the
+    /// program or give a wrong digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened or mapped.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::Hasher;
+    ///
+    /// let digest = lore::Blake3::hash_file_mmap("Cargo.toml").unwrap();
+    ///
+    /// assert_eq!(digest, lore::Blake3::hash_file("Cargo.toml").unwrap());
+    /// ```
+    #[cfg(feature = "mmap")]
+    fn hash_file_mmap(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self::Output, crate::io::HashFileError> {
+        Ok(Self::digest(&crate::io::map(path.as_ref())?[..]))
+    }
+}
+
+/// Buffers input until a full block of `N` bytes is available, keeping track of the total length of the input.
+///
+/// This is shared by the streaming hashers, which only ever need to hold on to a single partial block.
+#[derive(Debug, Clone)]
+pub(crate) struct Buffer<const N: usize> {
+    block: [u8; N],
+    filled: usize,
+    length: u64,
+}
+
+impl<const N: usize> Buffer<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            block: [0; N],
+            filled: 0,
+            length: 0,
+        }
+    }
+
+    /// An empty buffer for a message of which the first `length` bytes have already been compressed.
+    pub(crate) fn with_length(length: u64) -> Self {
+        Self {
+            block: [0; N],
+            filled: 0,
+            length,
+        }
+    }
+
+    /// Append data to the buffer, calling `compress` for every block that is completed.
+    pub(crate) fn update(&mut self, mut data: &[u8], mut compress: impl FnMut(&[u8; N])) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+
+        // fill up the partial block first
+        if self.filled > 0 {
+            let count = (N - self.filled).min(data.len());
+            self.block[self.filled..self.filled + count].copy_from_slice(&data[..count]);
+            self.filled += count;
+            data = &data[count..];
+
+            if self.filled < N {
+                return;
+            }
+            compress(&self.block);
+            self.filled = 0;
+        }
+
+        // compress whole blocks directly from the input, and keep the rest
+        let chunks = data.chunks_exact(N);
+        let rest = chunks.remainder();
+        for chunk in chunks {
+            compress(chunk.try_into().unwrap());
+        }
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.filled = rest.len();
+    }
+
+    /// Like [`Buffer::update`], but a completed block is only compressed once more data arrives.
+    ///
+    /// This is needed by algorithms which treat the final block differently, even when it is a full block.
+    pub(crate) fn update_lazy(&mut self, mut data: &[u8], mut compress: impl FnMut(&[u8; N])) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+
+        while !data.is_empty() {
+            if self.filled == N {
+                compress(&self.block);
+                self.filled = 0;
+            }
+
+            let count = (N - self.filled).min(data.len());
+            self.block[self.filled..self.filled + count].copy_from_slice(&data[..count]);
+            self.filled += count;
+            data = &data[count..];
+        }
+    }
+
+    /// The data which has been buffered, but not yet passed to the compression function.
+    pub(crate) fn remaining(&self) -> &[u8] {
+        &self.block[..self.filled]
+    }
+
+    /// The total number of bytes which have been passed to the buffer.
+    pub(crate) fn length(&self) -> u64 {
+        self.length
+    }
+}
+
+// compare two byte strings in time which only depends on their lengths, so that secrets aren't leaked through timing
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    // accumulate the differences without branching, and keep the optimizer from short-circuiting
+    let difference = a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y));
+    core::hint::black_box(difference) == 0
+}
+
+/// The padding which follows a message, kept on the stack since it is never longer than `N` bytes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Padding<const N: usize> {
+    bytes: [u8; N],
+    length: usize,
+}
+
+impl<const N: usize> Padding<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            length: 0,
+        }
+    }
+
+    /// Append a single byte to the padding.
+    pub(crate) fn push(&mut self, byte: u8) {
+        self.bytes[self.length] = byte;
+        self.length += 1;
+    }
+
+    /// The number of bytes in the padding.
+    pub(crate) fn len(&self) -> usize {
+        self.length
+    }
+}
+
+impl<const N: usize> Extend<u8> for Padding<N> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, bytes: I) {
+        bytes.into_iter().for_each(|byte| self.push(byte));
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Padding<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for Padding<N> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes[..self.length]
+    }
+}
+
+// the conversions between bytes and words read the first `N` words of the bytes, and write the first `M` bytes of
+// the words, which truncates the state to the digest size where it is larger
+pub fn bytes_to_words_le<const N: usize>(bytes: impl AsRef<[u8]>) -> [u32; N] {
+    let chunks = bytes.as_ref().as_chunks::<4>().0;
+    core::array::from_fn(|i| u32::from_le_bytes(chunks[i]))
+}
+
+pub fn words_to_bytes_le<const M: usize>(words: impl AsRef<[u32]>) -> [u8; M] {
+    let mut bytes = [0; M];
+    for (chunk, word) in bytes.chunks_mut(4).zip(words.as_ref()) {
+        chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+    }
+
     bytes
-        .as_ref()
-        .array_chunks::<4>()
-        .map(|chunk| u32::from_le_bytes(*chunk))
-        .collect()
 }
 
-pub fn words_to_bytes_le(words: impl AsRef<[u32]>) -> Vec<u8> {
-    words
-        .as_ref()
-        .iter()
-        .flat_map(|w| w.to_le_bytes())
-        .collect()
+pub fn bytes_to_words_be<const N: usize>(bytes: impl AsRef<[u8]>) -> [u32; N] {
+    let chunks = bytes.as_ref().as_chunks::<4>().0;
+    core::array::from_fn(|i| u32::from_be_bytes(chunks[i]))
+}
+
+pub fn words_to_bytes_be<const M: usize>(words: impl AsRef<[u32]>) -> [u8; M] {
+    let mut bytes = [0; M];
+    for (chunk, word) in bytes.chunks_mut(4).zip(words.as_ref()) {
+        chunk.copy_from_slice(&word.to_be_bytes()[..chunk.len()]);
+    }
+
+    bytes
+}
+
+pub fn bytes_to_words64_be<const N: usize>(bytes: impl AsRef<[u8]>) -> [u64; N] {
+    let chunks = bytes.as_ref().as_chunks::<8>().0;
+    core::array::from_fn(|i| u64::from_be_bytes(chunks[i]))
 }
 
-pub fn bytes_to_words_be(bytes: impl AsRef<[u8]>) -> Vec<u32> {
+pub fn words64_to_bytes_be<const M: usize>(words: impl AsRef<[u64]>) -> [u8; M] {
+    let mut bytes = [0; M];
+    for (chunk, word) in bytes.chunks_mut(8).zip(words.as_ref()) {
+        chunk.copy_from_slice(&word.to_be_bytes()[..chunk.len()]);
+    }
+
     bytes
-        .as_ref()
-        .array_chunks::<4>()
-        .map(|chunk| u32::from_be_bytes(*chunk))
-        .collect()
 }
 
-pub fn words_to_bytes_be(words: impl AsRef<[u32]>) -> Vec<u8> {
+pub fn bytes_to_words64_le<const N: usize>(bytes: impl AsRef<[u8]>) -> [u64; N] {
+    let chunks = bytes.as_ref().as_chunks::<8>().0;
+    core::array::from_fn(|i| u64::from_le_bytes(chunks[i]))
+}
+
+pub fn words64_to_bytes_le<const M: usize>(words: impl AsRef<[u64]>) -> [u8; M] {
+    let mut bytes = [0; M];
+    for (chunk, word) in bytes.chunks_mut(8).zip(words.as_ref()) {
+        chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+    }
+
+    bytes
+}
+
+// the const functions below can't loop over iterators or call trait methods, so they have their own versions of the
+// conversions, and of the padding of MD4, MD5 and SHA-1 one block at a time
+pub const fn bytes_to_words_le_const<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    let chunks = bytes.as_chunks::<4>().0;
+    let mut words = [0; N];
+    let mut i = 0;
+    while i < N {
+        words[i] = u32::from_le_bytes(chunks[i]);
+        i += 1;
+    }
+
     words
-        .as_ref()
-        .iter()
-        .flat_map(|w| w.to_be_bytes())
-        .collect()
 }
 
+pub const fn bytes_to_words_be_const<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    let chunks = bytes.as_chunks::<4>().0;
+    let mut words = [0; N];
+    let mut i = 0;
+    while i < N {
+        words[i] = u32::from_be_bytes(chunks[i]);
+        i += 1;
+    }
+
+    words
+}
+
+pub const fn words_to_bytes_le_const<const M: usize>(words: &[u32]) -> [u8; M] {
+    let mut bytes = [0; M];
+    let mut i = 0;
+    while i < M {
+        bytes[i] = words[i / 4].to_le_bytes()[i % 4];
+        i += 1;
+    }
+
+    bytes
+}
+
+pub const fn words_to_bytes_be_const<const M: usize>(words: &[u32]) -> [u8; M] {
+    let mut bytes = [0; M];
+    let mut i = 0;
+    while i < M {
+        bytes[i] = words[i / 4].to_be_bytes()[i % 4];
+        i += 1;
+    }
+
+    bytes
+}
+
+// the number of 512-bit blocks in a message padded with a 1 bit, 0 bits and its 64-bit length
+pub const fn padded_blocks(length: usize) -> usize {
+    (length + 8) / 64 + 1
+}
+
+// the block at the index of the padded message, given the encoded length in bits which ends the last block
+pub const fn padded_block(message: &[u8], index: usize, length: [u8; 8]) -> [u8; 64] {
+    let end = padded_blocks(message.len()) * 64;
+    let mut block = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        let position = index * 64 + i;
+        block[i] = if position < message.len() {
+            message[position]
+        } else if position == message.len() {
+            0x80
+        } else if position >= end - 8 {
+            length[position + 8 - end]
+        } else {
+            0
+        };
+        i += 1;
+    }
+
+    block
+}
+
+// define the hash function, the trace function and the streaming hasher of a SHA-2 function which is another one with
+// different initial values and a truncated digest, like SHA-224 of SHA-256, in the module of the function
+macro_rules! truncated_sha2 {
+    (
+        $name:literal,
+        $hash:ident,
+        $hasher:ident,
+        $size:literal,
+        $abc:literal,
+        base: $base:ident,
+        initial: $initial:ident,
+        state: [$word:ty; 8],
+        block: $block:literal,
+        words: $bytes_to_words:ident,
+        bytes: $words_to_bytes:ident $(,)?
+    ) => {
+        #[doc = concat!("Computes the ", $name, " hash value (digest) of the input bytes.")]
+        ///
+        #[doc = concat!(
+            "Returns a ",
+            stringify!($size),
+            "-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation."
+        )]
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rust
+        /// let input = "abc";
+        #[doc = concat!("let digest = lore::", stringify!($hash), "(input);")]
+        ///
+        /// assert_eq!(
+        ///     digest.to_string(),
+        #[doc = concat!("    \"", $abc, "\"")]
+        /// )
+        /// ```
+        pub fn hash(message: impl AsRef<[u8]>) -> $crate::hash::Digest<$size> {
+            let mut hasher = $hasher::new();
+            hasher.update(message);
+            hasher.finalize()
+        }
+
+        #[doc = concat!(
+            "Computes the ",
+            $name,
+            " hash value (digest) of the input bytes, reporting every intermediate value to the observer."
+        )]
+        ///
+        /// See the [`trace`](crate::trace) module for the events which are reported.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// let mut events = Vec::new();
+        #[doc = concat!("let digest = lore::trace::", stringify!($hash), "(\"abc\", &mut events);")]
+        ///
+        #[doc = concat!("assert_eq!(digest, lore::", stringify!($hash), "(\"abc\"));")]
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn trace<O: $crate::trace::Observer + ?Sized>(
+            message: impl AsRef<[u8]>,
+            observer: &mut O,
+        ) -> $crate::hash::Digest<$size> {
+            // the padding and compression function are shared with the base function
+            let padded = $crate::hash::$base::pad(message);
+            let buffer = $crate::trace::compress_blocks(
+                &padded,
+                $initial,
+                observer,
+                $crate::hash::$bytes_to_words::<16>,
+                $crate::hash::$base::compress_observed,
+            );
+            let digest = $crate::hash::$words_to_bytes(buffer);
+
+            $crate::trace::digest($crate::hash::Digest(digest), observer)
+        }
+
+        #[doc = concat!(
+            "A streaming ",
+            $name,
+            " hasher, for hashing messages which are not available all at once."
+        )]
+        ///
+        /// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        #[doc = concat!("let mut hasher = lore::", stringify!($hasher), "::new();")]
+        /// hasher.update("a");
+        /// hasher.update("bc");
+        ///
+        #[doc = concat!("assert_eq!(hasher.finalize(), lore::", stringify!($hash), "(\"abc\"));")]
+        /// ```
+        #[derive(Debug, Clone)]
+        pub struct $hasher {
+            state: [$word; 8],
+            buffer: $crate::hash::Buffer<$block>,
+        }
+
+        impl $hasher {
+            /// Create a new hasher with an empty message.
+            #[must_use]
+            pub fn new() -> Self {
+                Self {
+                    state: $initial,
+                    buffer: $crate::hash::Buffer::new(),
+                }
+            }
+
+            /// Append data to the message being hashed.
+            pub fn update(&mut self, data: impl AsRef<[u8]>) {
+                let Self { state, buffer } = self;
+                buffer.update(data.as_ref(), |block| {
+                    *state = $crate::hash::$base::compress(*state, block);
+                });
+            }
+
+            #[doc = concat!(
+                "Pad the message and return the digest of everything passed to [`",
+                stringify!($hasher),
+                "::update`]."
+            )]
+            #[must_use]
+            pub fn finalize(mut self) -> $crate::hash::Digest<$size> {
+                self.update($crate::hash::$base::padding(self.buffer.length()));
+                let digest = $crate::hash::$words_to_bytes(self.state);
+
+                $crate::hash::Digest(digest)
+            }
+        }
+
+        impl Default for $hasher {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $crate::hash::Hasher for $hasher {
+            const BLOCK_SIZE: usize = $block;
+            const OUTPUT_SIZE: usize = $size;
+
+            type Output = $crate::hash::Digest<$size>;
+
+            fn update(&mut self, data: impl AsRef<[u8]>) {
+                self.update(data);
+            }
+
+            fn finalize(self) -> $crate::hash::Digest<$size> {
+                self.finalize()
+            }
+        }
+    };
+}
+pub(crate) use truncated_sha2;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn hasher_works<H: Hasher>() {
+        let mut hasher = H::default();
+        hasher.update("garbage");
+        hasher.reset();
+        hasher.update("lo");
+        hasher.update("l");
+
+        assert_eq!(H::digest("lol"), hasher.finalize());
+        assert_eq!(H::OUTPUT_SIZE, H::digest("").as_ref().len());
+    }
+
+    #[test]
+    fn hasher_generic() {
+        hasher_works::<md2::Md2>();
+        hasher_works::<md4::Md4>();
+        hasher_works::<md5::Md5>();
+        hasher_works::<ripemd128::Ripemd128>();
+        hasher_works::<ripemd160::Ripemd160>();
+        hasher_works::<ripemd256::Ripemd256>();
+        hasher_works::<ripemd320::Ripemd320>();
+        hasher_works::<sha1::Sha1>();
+        hasher_works::<sha1dc::Sha1Dc>();
+        hasher_works::<sha224::Sha224>();
+        hasher_works::<sha256::Sha256>();
+        hasher_works::<sha384::Sha384>();
+        hasher_works::<sha512::Sha512>();
+        hasher_works::<sha512_224::Sha512_224>();
+        hasher_works::<sha512_256::Sha512_256>();
+        hasher_works::<sha3_224::Sha3_224>();
+        hasher_works::<sha3_256::Sha3_256>();
+        hasher_works::<sha3_384::Sha3_384>();
+        hasher_works::<sha3_512::Sha3_512>();
+        hasher_works::<blake2b::Blake2b<64>>();
+        hasher_works::<blake2b::Blake2b<20>>();
+        hasher_works::<blake2s::Blake2s<32>>();
+        hasher_works::<blake3::Blake3>();
+
+        assert_eq!(16, md2::Md2::BLOCK_SIZE);
+        assert_eq!(64, md4::Md4::BLOCK_SIZE);
+        assert_eq!(64, md5::Md5::BLOCK_SIZE);
+        assert_eq!(64, ripemd160::Ripemd160::BLOCK_SIZE);
+        assert_eq!(64, sha1::Sha1::BLOCK_SIZE);
+        assert_eq!(64, sha256::Sha256::BLOCK_SIZE);
+        assert_eq!(128, sha512::Sha512::BLOCK_SIZE);
+        assert_eq!(136, sha3_256::Sha3_256::BLOCK_SIZE);
+        assert_eq!(128, blake2b::Blake2b::<64>::BLOCK_SIZE);
+        assert_eq!(64, blake2s::Blake2s::<32>::BLOCK_SIZE);
+        assert_eq!(64, blake3::Blake3::BLOCK_SIZE);
+    }
+
     #[test]
     fn bytes_to_words_le_works() {
         assert_eq!(
-            vec![0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476],
+            [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476],
             bytes_to_words_le([
                 0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54,
                 0x32, 0x10
@@ -107,10 +990,224 @@ mod tests {
         );
     }
 
+    #[test]
+    fn buffer_works() {
+        let mut buffer = Buffer::<4>::new();
+        let mut blocks = vec![];
+
+        buffer.update(&[1, 2, 3], |block| blocks.push(*block));
+        assert!(blocks.is_empty());
+        buffer.update(&[4, 5, 6, 7, 8, 9, 10], |block| blocks.push(*block));
+        assert_eq!(vec![[1, 2, 3, 4], [5, 6, 7, 8]], blocks);
+        buffer.update(&[], |block| blocks.push(*block));
+        assert_eq!(2, blocks.len());
+        assert_eq!(10, buffer.length());
+        assert_eq!([9, 10], buffer.remaining());
+    }
+
+    #[test]
+    fn digest_parse() {
+        let digest = crate::md5("lol xd");
+        assert_eq!(
+            Ok(digest.clone()),
+            "982d7f24f8985a6baa5cf129acc73561".parse()
+        );
+        assert_eq!(
+            Ok(digest.clone()),
+            "982D7F24F8985A6BAA5CF129ACC73561".parse()
+        );
+        assert_eq!(
+            Ok(digest.clone()),
+            Digest::try_from("982d7f24F8985A6Baa5cf129acc73561")
+        );
+        assert_eq!(Ok(digest.clone()), digest.to_string().parse());
+        assert_eq!(Ok(Digest([])), "".parse::<Digest<0>>());
+
+        assert_eq!(
+            Err(ParseDigestError::InvalidLength {
+                expected: 32,
+                found: 30
+            }),
+            "982d7f24f8985a6baa5cf129acc735".parse::<Digest<16>>()
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidLength {
+                expected: 32,
+                found: 34
+            }),
+            "982d7f24f8985a6baa5cf129acc7356100".parse::<Digest<16>>()
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: 'g',
+                index: 3
+            }),
+            "982g7f24f8985a6baa5cf129acc73561".parse::<Digest<16>>()
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: 'ø',
+                index: 2
+            }),
+            Digest::<16>::try_from("98ø")
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: ' ',
+                index: 32
+            }),
+            "982d7f24f8985a6baa5cf129acc73561 ".parse::<Digest<16>>()
+        );
+    }
+
+    #[test]
+    fn digest_try_from_bytes() {
+        let digest = crate::sha1("lol xd");
+        assert_eq!(Ok(digest.clone()), Digest::try_from(digest.as_ref()));
+        assert_eq!(
+            Err(ParseDigestError::InvalidLength {
+                expected: 20,
+                found: 19
+            }),
+            Digest::<20>::try_from(&digest.as_ref()[..19])
+        );
+        assert_eq!(
+            "invalid digest length: expected 20, found 19",
+            Digest::<20>::try_from(&[0; 19][..])
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "invalid character 'x' at position 1",
+            "0x".parse::<Digest<1>>().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn digest_hex_formatting() {
+        let digest = crate::md5("lol 17");
+        assert_eq!("dab79f0bbd6b299605c3e9bd01fd87fc", format!("{digest:x}"));
+        assert_eq!("DAB79F0BBD6B299605C3E9BD01FD87FC", format!("{digest:X}"));
+        assert_eq!("0xdab79f0bbd6b299605c3e9bd01fd87fc", format!("{digest:#x}"));
+        assert_eq!("0xDAB79F0BBD6B299605C3E9BD01FD87FC", format!("{digest:#X}"));
+        assert_eq!(Ok(digest.clone()), format!("{digest:X}").parse());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn digest_encodings() {
+        let digest = crate::md5("lol 17");
+        assert_eq!("2refC71rKZYFw+m9Af2H/A==", digest.to_base64());
+        assert_eq!("2refC71rKZYFw-m9Af2H_A", digest.to_base64url());
+        assert_eq!("3K3Z6C55NMUZMBOD5G6QD7MH7Q======", digest.to_base32());
+        assert_eq!("U1UPQmNd5Dks9vw3hMzgSo", digest.to_base58());
+
+        assert_eq!(
+            Ok(digest.clone()),
+            Digest::from_base64("2refC71rKZYFw+m9Af2H/A==")
+        );
+        assert_eq!(
+            Ok(digest.clone()),
+            Digest::from_base64url("2refC71rKZYFw-m9Af2H_A")
+        );
+        assert_eq!(
+            Ok(digest.clone()),
+            Digest::from_base64url("2refC71rKZYFw-m9Af2H_A==")
+        );
+        assert_eq!(
+            Ok(digest.clone()),
+            Digest::from_base32("3K3Z6C55NMUZMBOD5G6QD7MH7Q======")
+        );
+        assert_eq!(
+            Ok(digest.clone()),
+            Digest::from_base32("3k3z6c55nmuzmbod5g6qd7mh7q======")
+        );
+        assert_eq!(
+            Ok(digest.clone()),
+            Digest::from_base58("U1UPQmNd5Dks9vw3hMzgSo")
+        );
+
+        let digest = crate::sha1("lol xd");
+        assert_eq!("BtEWt3fIEMN6iYlqhJdvKWOlAx4=", digest.to_base64());
+        assert_eq!("A3IRNN3XZAIMG6UJRFVIJF3PFFR2KAY6", digest.to_base32());
+        assert_eq!("6WUZL51JuSRgjA2wPAC7AsKfAB7", digest.to_base58());
+        assert_eq!(Ok(digest.clone()), Digest::from_base32(&digest.to_base32()));
+        assert_eq!(Ok(digest.clone()), Digest::from_base58(&digest.to_base58()));
+
+        // the standard and url-safe alphabets are not interchangeable
+        assert_eq!(
+            Err(ParseDigestError::InvalidCharacter {
+                character: '-',
+                index: 13
+            }),
+            Digest::<16>::from_base64("2refC71rKZYFw-m9Af2H_A==")
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidLength {
+                expected: 24,
+                found: 22
+            }),
+            Digest::<16>::from_base64("2refC71rKZYFw+m9Af2H/A")
+        );
+        assert_eq!(
+            Err(ParseDigestError::InvalidLength {
+                expected: 16,
+                found: 20
+            }),
+            Digest::<16>::from_base58("6WUZL51JuSRgjA2wPAC7AsKfAB7")
+        );
+    }
+
+    #[test]
+    fn digest_constant_time_eq() {
+        let digest = crate::md5("lol xd");
+        assert!(digest.constant_time_eq(crate::md5("lol xd")));
+        assert!(digest.constant_time_eq(Vec::from(digest.as_ref())));
+        assert!(!digest.constant_time_eq(crate::md5("lol xD")));
+        assert!(!digest.constant_time_eq(crate::sha1("lol xd")));
+        assert!(!digest.constant_time_eq([]));
+    }
+
+    #[test]
+    fn hasher_verify() {
+        assert!(md5::Md5::verify("lol xd", crate::md5("lol xd")));
+        assert!(!md5::Md5::verify("lol xd", crate::md5("lol xD")));
+        assert!(sha3_256::Sha3_256::verify("", crate::sha3_256("")));
+        assert!(!sha3_256::Sha3_256::verify(
+            "",
+            &crate::sha3_256("").as_ref()[..31]
+        ));
+        assert!(blake2b::Blake2b::<20>::verify(
+            "abc",
+            crate::blake2b::<20>("abc")
+        ));
+    }
+
+    #[test]
+    fn constant_time_eq_works() {
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(b"lol xd", b"lol xd"));
+        assert!(!constant_time_eq(b"lol xd", b"lol xD"));
+        assert!(!constant_time_eq(b"lol", b"lol xd"));
+    }
+
+    #[test]
+    fn buffer_lazy_works() {
+        let mut buffer = Buffer::<4>::new();
+        let mut blocks = vec![];
+
+        buffer.update_lazy(&[1, 2, 3, 4], |block| blocks.push(*block));
+        assert!(blocks.is_empty());
+        assert_eq!([1, 2, 3, 4], buffer.remaining());
+        buffer.update_lazy(&[5, 6, 7, 8, 9], |block| blocks.push(*block));
+        assert_eq!(vec![[1, 2, 3, 4], [5, 6, 7, 8]], blocks);
+        assert_eq!([9], buffer.remaining());
+    }
+
     #[test]
     fn words_to_bytes_le_works() {
         assert_eq!(
-            vec![
+            [
                 0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54,
                 0x32, 0x10
             ],
@@ -0,0 +1,571 @@
+use crate::hash::{bytes_to_words64_le, words64_to_bytes_le, Buffer, Digest, Hasher};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+
+// based on RFC 7693, The BLAKE2 Cryptographic Hash and Message Authentication Code (MAC)
+
+// the initialization vector is the same as the SHA-512 initial hash value
+#[rustfmt::skip]
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+// the message word permutations, one for each round (rounds 10 and 11 reuse the first two)
+pub const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+// rotation constants for the mixing function
+const R: [u32; 4] = [32, 24, 16, 63];
+
+// the mixing function, which mixes two message words into four words of the working vector
+pub fn g(mut v: [u64; 16], [a, b, c, d]: [usize; 4], x: u64, y: u64) -> [u64; 16] {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(R[0]);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(R[1]);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(R[2]);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(R[3]);
+
+    v
+}
+
+// compute a single round, which mixes the columns and then the diagonals of the working vector
+pub fn round(v: [u64; 16], m: &[u64], i: usize) -> [u64; 16] {
+    let s = SIGMA[i % 10];
+
+    let v = g(v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+    let v = g(v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+    let v = g(v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+    let v = g(v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+
+    let v = g(v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+    let v = g(v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+    let v = g(v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+    g(v, [3, 4, 9, 14], m[s[14]], m[s[15]])
+}
+
+// set up the working vector from the state, the byte counter and the final block flag
+pub fn initialize(h: [u64; 8], t: u128, last: bool) -> [u64; 16] {
+    let mut v = [0; 16];
+    v[..8].copy_from_slice(&h);
+    v[8..].copy_from_slice(&IV);
+
+    // the counter is split into its low and high words
+    v[12] ^= t as u64;
+    v[13] ^= (t >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    v
+}
+
+// the compression function F, which processes a single 1024-bit block
+pub fn compress(h: [u64; 8], block: &[u8; 128], t: u128, last: bool) -> [u64; 8] {
+    compress_observed(h, block, t, last, &mut ())
+}
+
+// the compression function F, reporting each round to the observer
+pub fn compress_observed<O: Observer + ?Sized>(
+    previous: [u64; 8],
+    block: &[u8; 128],
+    t: u128,
+    last: bool,
+    observer: &mut O,
+) -> [u64; 8] {
+    let m: [u64; 16] = bytes_to_words64_le(block);
+    let v = (0..12).fold(initialize(previous, t, last), |v, i| {
+        let v = round(v, &m, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: v.into(),
+            }
+        );
+        v
+    });
+
+    let mut h = previous;
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: previous.into(),
+            working: v.into(),
+            next: h.into(),
+        }
+    );
+
+    h
+}
+
+/// Computes the BLAKE2b hash value (digest) of the input bytes, with a digest length of `S` bytes.
+///
+/// Returns an `S`-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+/// The digest length must be between 1 and 64 bytes, which is checked at compile time.
+/// For keyed hashing, a salt or personalization, use [`Blake2b::with_params`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let input = "abc";
+/// let digest = lore::blake2b::<64>(input);
+///
+/// assert_eq!(
+///     digest.to_string(),
+///     "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+/// )
+/// ```
+pub fn hash<const S: usize>(message: impl AsRef<[u8]>) -> Digest<S> {
+    let mut hasher = Blake2b::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the BLAKE2b hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// The message is zero-padded to a whole number of blocks, with at least one block for the empty message.
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::blake2b::<32, _>("abc", &mut events);
+///
+/// assert_eq!(digest, lore::blake2b::<32>("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<const S: usize, O: Observer + ?Sized>(
+    message: impl AsRef<[u8]>,
+    observer: &mut O,
+) -> Digest<S> {
+    let message = message.as_ref();
+    let length = message.len();
+    let blocks = length.div_ceil(128).max(1);
+    let mut padded = message.to_vec();
+    padded.resize(blocks * 128, 0);
+
+    // the counter holds the number of message bytes up to the end of the block, and the last block is flagged
+    let (state, _) = compress_blocks(
+        &padded,
+        (Blake2b::<S>::new().state, 0),
+        observer,
+        |block: [u8; 128]| bytes_to_words64_le::<16>(block),
+        |(h, i), block, observer| {
+            let last = i + 1 == blocks;
+            let t = if last { length } else { (i + 1) * 128 };
+            (
+                compress_observed(h, block, t as u128, last, observer),
+                i + 1,
+            )
+        },
+    );
+
+    trace::digest(Digest(words64_to_bytes_le(state)), observer)
+}
+
+/// A streaming BLAKE2b hasher with a digest length of `S` bytes, for hashing messages which are not available all at once.
+///
+/// Only a single block is buffered, so arbitrarily long messages can be hashed in constant memory.
+/// The digest length must be between 1 and 64 bytes, which is checked at compile time.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Blake2b::<64>::new();
+/// hasher.update("a");
+/// hasher.update("bc");
+///
+/// assert_eq!(hasher.finalize(), lore::blake2b::<64>("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Blake2b<const S: usize> {
+    state: [u64; 8],
+    buffer: Buffer<128>,
+    counter: u128,
+    // the state after the parameter block, and the padded key, which are kept for resetting the hasher
+    parameters: [u64; 8],
+    key: Option<[u8; 128]>,
+}
+
+impl<const S: usize> Blake2b<S> {
+    // the digest length is part of the parameter block, so it is checked when the hasher is created
+    const VALID_LENGTH: () = assert!(
+        S >= 1 && S <= 64,
+        "BLAKE2b digests must be between 1 and 64 bytes long"
+    );
+
+    /// Create a new unkeyed hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_params([], [], [])
+    }
+
+    /// Create a new keyed hasher with an empty message, for use as a message authentication code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is longer than 64 bytes.
+    #[must_use]
+    pub fn with_key(key: impl AsRef<[u8]>) -> Self {
+        Self::with_params(key, [], [])
+    }
+
+    /// Create a new hasher with an optional key, salt and personalization, any of which may be empty.
+    ///
+    /// Shorter salts and personalizations are padded with zeros.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is longer than 64 bytes, or if the salt or personalization is longer than 16 bytes.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let mut hasher = lore::Blake2b::<16>::with_params("key", "salt", "me");
+    /// hasher.update("abc");
+    ///
+    /// assert_eq!(hasher.finalize().to_string(), "7b47c9e380d1d76fe27590c7c799fe35");
+    /// ```
+    #[must_use]
+    pub fn with_params(
+        key: impl AsRef<[u8]>,
+        salt: impl AsRef<[u8]>,
+        personalization: impl AsRef<[u8]>,
+    ) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_LENGTH;
+        let (key, salt, personalization) = (key.as_ref(), salt.as_ref(), personalization.as_ref());
+        assert!(key.len() <= 64, "BLAKE2b keys can be at most 64 bytes long");
+        assert!(
+            salt.len() <= 16,
+            "BLAKE2b salts can be at most 16 bytes long"
+        );
+        assert!(
+            personalization.len() <= 16,
+            "BLAKE2b personalizations can be at most 16 bytes long"
+        );
+
+        // xor the parameter block into the initialization vector
+        let mut state = IV;
+        state[0] ^= 0x0101_0000 ^ ((key.len() as u64) << 8) ^ S as u64;
+        for (offset, parameter) in [(4, salt), (6, personalization)] {
+            let mut padded = [0u8; 16];
+            padded[..parameter.len()].copy_from_slice(parameter);
+            for (i, word) in bytes_to_words64_le::<2>(padded).iter().enumerate() {
+                state[offset + i] ^= word;
+            }
+        }
+
+        // the key is padded to a full block, and processed before the message
+        let key = (!key.is_empty()).then(|| {
+            let mut block = [0u8; 128];
+            block[..key.len()].copy_from_slice(key);
+            block
+        });
+
+        let mut hasher = Self {
+            state,
+            buffer: Buffer::new(),
+            counter: 0,
+            parameters: state,
+            key,
+        };
+        hasher.reset();
+
+        hasher
+    }
+
+    /// Discard the message hashed so far, keeping the key, salt and personalization.
+    pub fn reset(&mut self) {
+        self.state = self.parameters;
+        self.buffer = Buffer::new();
+        self.counter = 0;
+        if let Some(key) = self.key {
+            self.update(key);
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self {
+            state,
+            buffer,
+            counter,
+            ..
+        } = self;
+
+        // the last block is compressed differently, so blocks are only compressed once more data arrives
+        buffer.update_lazy(data.as_ref(), |block| {
+            *counter = counter.wrapping_add(128);
+            *state = compress(*state, block, *counter, false);
+        });
+    }
+
+    /// Pad the final block and return the digest of everything passed to [`Blake2b::update`].
+    #[must_use]
+    pub fn finalize(self) -> Digest<S> {
+        let remaining = self.buffer.remaining();
+        let mut block = [0u8; 128];
+        block[..remaining.len()].copy_from_slice(remaining);
+
+        let counter = self.counter.wrapping_add(remaining.len() as u128);
+        let state = compress(self.state, &block, counter, true);
+
+        Digest(words64_to_bytes_le(state))
+    }
+}
+
+impl<const S: usize> Default for Blake2b<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const S: usize> Hasher for Blake2b<S> {
+    const BLOCK_SIZE: usize = 128;
+    const OUTPUT_SIZE: usize = S;
+
+    type Output = Digest<S>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<S> {
+        self.finalize()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the working vector for the single block of "abc", before the first round
+    #[rustfmt::skip]
+    const ABC_V: [u64; 16] = [
+        0x6a09e667f2bdc948, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+        0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+        0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+        0x510e527fade682d2, 0x9b05688c2b3e6c1f, 0xe07c265404be4294, 0x5be0cd19137e2179,
+    ];
+
+    // the working vector after mixing the first column
+    #[rustfmt::skip]
+    const ABC_G: [u64; 16] = [
+        0xf0c9aa0f86491dea, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+        0xb7defc3d0277e11f, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+        0xee5e0f39647a9fff, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+        0x7772fc2886a76c5f, 0x9b05688c2b3e6c1f, 0xe07c265404be4294, 0x5be0cd19137e2179,
+    ];
+
+    // the working vector after the first round
+    #[rustfmt::skip]
+    const ABC_ROUND0: [u64; 16] = [
+        0x86b7c1568029bb79, 0xc12cbcc809ff59f3, 0xc6a5214cc0eaca8e, 0x0c87cd524c14cc5d,
+        0x44ee6039bd86a9f7, 0xa447c850aa694a7e, 0xde080f1bb1c0f84b, 0x595cb8a9a1aca66c,
+        0xbec3ae837eac4887, 0x6267fc79df9d6ad1, 0xfa87b01273fa6dbe, 0x521a715c63e08d8a,
+        0xe02d0975b8d37a83, 0x1c7b754f08b7d193, 0x8f885a76b6e578fe, 0x2318a24e2140fc64,
+    ];
+
+    // the working vector after the last round
+    #[rustfmt::skip]
+    const ABC_ROUNDS: [u64; 16] = [
+        0x12ef8a641ec4f6d6, 0xbced5de977c9faf5, 0x733ca476c5148639, 0x97df596b0610f6fc,
+        0xf42c16519ad5afa7, 0xaa5ac1888e10467e, 0x217d930aa51787f3, 0x906a6ff19e573942,
+        0x75ab709bd3dcbf24, 0xee7ce1f345947aa4, 0xf8960d6c2faf5f5e, 0xe332538a36b6d246,
+        0x885bef040ef6aa0b, 0xa4939a417bfb78a3, 0x646cbb7af6dce980, 0xe813a23c60af3b82,
+    ];
+
+    // the state after compressing the single block of "abc"
+    #[rustfmt::skip]
+    const ABC_H: [u64; 8] = [
+        0x0d4d1c983fa580ba, 0xe9f6129fb697276a, 0xb7c45a68142f214c, 0xd1a2ffdb6fbb124b,
+        0x2d79ab2a39c5877d, 0x95cc3345ded552c2, 0x5a92f1dba88ad318, 0x239900d4ed8623b9,
+    ];
+
+    #[test]
+    fn blake2b_g() {
+        let v = g(ABC_V, [0, 4, 8, 12], 0x0000000000636261, 0x0000000000000000);
+        assert_eq!(ABC_G, v);
+
+        // only the four selected words are changed
+        for i in (0..16).filter(|i| i % 4 != 0) {
+            assert_eq!(ABC_V[i], v[i]);
+        }
+    }
+
+    #[test]
+    fn blake2b_rounds() {
+        let mut block = [0u8; 128];
+        block[..3].copy_from_slice(b"abc");
+        let m: [u64; 16] = bytes_to_words64_le(block);
+
+        let mut h = IV;
+        h[0] ^= 0x0101_0000 ^ 64;
+        assert_eq!(ABC_V, initialize(h, 3, true));
+        assert_eq!(ABC_ROUND0, round(ABC_V, &m, 0));
+        assert_eq!(ABC_ROUNDS, (0..12).fold(ABC_V, |v, i| round(v, &m, i)));
+        assert_eq!(ABC_H, compress(h, &block, 3, true));
+    }
+
+    #[test]
+    fn blake2b_hash() {
+        assert_eq!(
+            hash::<64>("").to_string(),
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+        );
+        assert_eq!(
+            hash::<64>("abc").to_string(),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+        assert_eq!(
+            hash::<64>("The quick brown fox jumps over the lazy dog").to_string(),
+            "a8add4bdddfd93e4877d2746e62817b116364a1fa7bc148d95090bc7333b3673f82401cf7aa2e4cb1ecd90296e3f14cb5413f8ed77be73045b13914cdcd6a918"
+        );
+        assert_eq!(
+            hash::<20>("abc").to_string(),
+            "384264f676f39536840523f284921cdc68b6846b"
+        );
+    }
+
+    #[test]
+    fn blake2b_params() {
+        let mut hasher = Blake2b::<64>::with_params("key", "", "");
+        hasher.update("abc");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "5c6a9a4ae911c02fb7e71a991eb9aea371ae993d4842d206e6020d46f5e41358c6d5c277c110ef86c959ed63e6ecaaaceaaff38019a43264ae06acf73b9550b1"
+        );
+        let mut hasher = Blake2b::<64>::with_params("", "salt", "");
+        hasher.update("abc");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "e6844fcf0fbb2ef59cf0297642f11b32179f05c960ec5c14ad73910e6de2fdacff2134c8cf75a5083bf29167996ba043ae7d5ee599beb3d72c12e3f92ef24596"
+        );
+        let mut hasher = Blake2b::<64>::with_params("", "", "personal");
+        hasher.update("abc");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "5f2d1331bc605dd83fce40bc02036378187da29e61b14e4af1ebc9c3d85710c790f0520a6ba40299dc7db7d02156dff003b2268f208ee30630560961fea2ed1a"
+        );
+        let mut hasher = Blake2b::<24>::with_params(
+            "kkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkk",
+            "ssssssssssssssss",
+            "pppppppppppppppp",
+        );
+        hasher.update("");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "77884bd85def4c0b3e193fc8eb0793c628e554192855c0d0"
+        );
+
+        // a key with an empty message is a single, final block
+        assert_eq!(
+            Blake2b::<64>::with_key("key").finalize().to_string(),
+            "5b3cfd8f422b490b764b55eceb330b500c79cbefa9a928ad00202b8b3c5dd778a81122570434a2e3b8bfd028d105dfefd0a9576e88ed66de742ca9fbb5f8d2b6"
+        );
+    }
+
+    // deterministic input sequence for the RFC 7693 self-test
+    fn selftest_seq(length: usize, seed: u32) -> Vec<u8> {
+        let mut a = 0xdead4badu32.wrapping_mul(seed);
+        let mut b = 1u32;
+
+        (0..length)
+            .map(|_| {
+                let t = a.wrapping_add(b);
+                a = b;
+                b = t;
+                (t >> 24) as u8
+            })
+            .collect()
+    }
+
+    // hash the unkeyed and keyed digests of each input length into the grand hash
+    fn selftest_digests<const S: usize>(grand: &mut Blake2b<32>) {
+        for length in [0, 3, 128, 129, 255, 1024] {
+            let input = selftest_seq(length, length as u32);
+            grand.update(hash::<S>(&input));
+
+            let mut hasher = Blake2b::<S>::with_key(selftest_seq(S, S as u32));
+            hasher.update(&input);
+            grand.update(hasher.finalize());
+        }
+    }
+
+    #[test]
+    fn blake2b_selftest() {
+        let mut grand = Blake2b::<32>::new();
+        selftest_digests::<20>(&mut grand);
+        selftest_digests::<32>(&mut grand);
+        selftest_digests::<48>(&mut grand);
+        selftest_digests::<64>(&mut grand);
+
+        assert_eq!(
+            grand.finalize().to_string(),
+            "c23a7800d98123bd10f506c61e29da5603d763b8bbad2e737f5e765a7bccd475"
+        );
+    }
+
+    #[test]
+    fn blake2b_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, 128 - 1, 128, 128 + 1, 2 * 128, 599, 600] {
+            let mut hasher = Blake2b::<64>::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash::<64>(message), hasher.finalize());
+
+            let mut hasher = Blake2b::<20>::with_key("key");
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            let mut reference = Blake2b::<20>::with_key("key");
+            reference.update(message);
+            assert_eq!(reference.finalize(), hasher.finalize());
+        }
+        assert_eq!(hash::<64>(""), Blake2b::<64>::new().finalize());
+    }
+
+    #[test]
+    fn blake2b_reset() {
+        let mut hasher = Blake2b::<20>::with_params("key", "salt", "me");
+        hasher.update("lol");
+        hasher.reset();
+        hasher.update("abc");
+
+        let mut reference = Blake2b::<20>::with_params("key", "salt", "me");
+        reference.update("abc");
+        assert_eq!(reference.finalize(), hasher.finalize());
+    }
+}
@@ -0,0 +1,556 @@
+use crate::hash::{blake2b::SIGMA, bytes_to_words_le, words_to_bytes_le, Buffer, Digest, Hasher};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+
+// based on RFC 7693, The BLAKE2 Cryptographic Hash and Message Authentication Code (MAC)
+
+// the initialization vector is the same as the SHA-256 initial hash value
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// rotation constants for the mixing function
+const R: [u32; 4] = [16, 12, 8, 7];
+
+// the mixing function, which mixes two message words into four words of the working vector
+pub fn g(mut v: [u32; 16], [a, b, c, d]: [usize; 4], x: u32, y: u32) -> [u32; 16] {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(R[0]);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(R[1]);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(R[2]);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(R[3]);
+
+    v
+}
+
+// compute a single round, which mixes the columns and then the diagonals of the working vector
+pub fn round(v: [u32; 16], m: &[u32], i: usize) -> [u32; 16] {
+    let s = SIGMA[i % 10];
+
+    let v = g(v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+    let v = g(v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+    let v = g(v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+    let v = g(v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+
+    let v = g(v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+    let v = g(v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+    let v = g(v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+    g(v, [3, 4, 9, 14], m[s[14]], m[s[15]])
+}
+
+// set up the working vector from the state, the byte counter and the final block flag
+pub fn initialize(h: [u32; 8], t: u64, last: bool) -> [u32; 16] {
+    let mut v = [0; 16];
+    v[..8].copy_from_slice(&h);
+    v[8..].copy_from_slice(&IV);
+
+    // the counter is split into its low and high words
+    v[12] ^= t as u32;
+    v[13] ^= (t >> 32) as u32;
+    if last {
+        v[14] = !v[14];
+    }
+
+    v
+}
+
+// the compression function F, which processes a single 512-bit block
+pub fn compress(h: [u32; 8], block: &[u8; 64], t: u64, last: bool) -> [u32; 8] {
+    compress_observed(h, block, t, last, &mut ())
+}
+
+// the compression function F, reporting each round to the observer
+pub fn compress_observed<O: Observer + ?Sized>(
+    previous: [u32; 8],
+    block: &[u8; 64],
+    t: u64,
+    last: bool,
+    observer: &mut O,
+) -> [u32; 8] {
+    let m: [u32; 16] = bytes_to_words_le(block);
+    let v = (0..10).fold(initialize(previous, t, last), |v, i| {
+        let v = round(v, &m, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: v.into(),
+            }
+        );
+        v
+    });
+
+    let mut h = previous;
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: previous.into(),
+            working: v.into(),
+            next: h.into(),
+        }
+    );
+
+    h
+}
+
+/// Computes the BLAKE2s hash value (digest) of the input bytes, with a digest length of `S` bytes.
+///
+/// Returns an `S`-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+/// The digest length must be between 1 and 32 bytes, which is checked at compile time.
+/// For keyed hashing, a salt or personalization, use [`Blake2s::with_params`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let input = "abc";
+/// let digest = lore::blake2s::<32>(input);
+///
+/// assert_eq!(
+///     digest.to_string(),
+///     "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+/// )
+/// ```
+pub fn hash<const S: usize>(message: impl AsRef<[u8]>) -> Digest<S> {
+    let mut hasher = Blake2s::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the BLAKE2s hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// The message is zero-padded to a whole number of blocks, with at least one block for the empty message.
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::blake2s::<32, _>("abc", &mut events);
+///
+/// assert_eq!(digest, lore::blake2s::<32>("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<const S: usize, O: Observer + ?Sized>(
+    message: impl AsRef<[u8]>,
+    observer: &mut O,
+) -> Digest<S> {
+    let message = message.as_ref();
+    let length = message.len();
+    let blocks = length.div_ceil(64).max(1);
+    let mut padded = message.to_vec();
+    padded.resize(blocks * 64, 0);
+
+    // the counter holds the number of message bytes up to the end of the block, and the last block is flagged
+    let (state, _) = compress_blocks(
+        &padded,
+        (Blake2s::<S>::new().state, 0),
+        observer,
+        |block: [u8; 64]| bytes_to_words_le::<16>(block),
+        |(h, i), block, observer| {
+            let last = i + 1 == blocks;
+            let t = if last { length } else { (i + 1) * 64 };
+            (compress_observed(h, block, t as u64, last, observer), i + 1)
+        },
+    );
+
+    trace::digest(Digest(words_to_bytes_le(state)), observer)
+}
+
+/// A streaming BLAKE2s hasher with a digest length of `S` bytes, for hashing messages which are not available all at once.
+///
+/// Only a single block is buffered, so arbitrarily long messages can be hashed in constant memory.
+/// The digest length must be between 1 and 32 bytes, which is checked at compile time.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Blake2s::<32>::new();
+/// hasher.update("a");
+/// hasher.update("bc");
+///
+/// assert_eq!(hasher.finalize(), lore::blake2s::<32>("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Blake2s<const S: usize> {
+    state: [u32; 8],
+    buffer: Buffer<64>,
+    counter: u64,
+    // the state after the parameter block, and the padded key, which are kept for resetting the hasher
+    parameters: [u32; 8],
+    key: Option<[u8; 64]>,
+}
+
+impl<const S: usize> Blake2s<S> {
+    // the digest length is part of the parameter block, so it is checked when the hasher is created
+    const VALID_LENGTH: () = assert!(
+        S >= 1 && S <= 32,
+        "BLAKE2s digests must be between 1 and 32 bytes long"
+    );
+
+    /// Create a new unkeyed hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_params([], [], [])
+    }
+
+    /// Create a new keyed hasher with an empty message, for use as a message authentication code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is longer than 32 bytes.
+    #[must_use]
+    pub fn with_key(key: impl AsRef<[u8]>) -> Self {
+        Self::with_params(key, [], [])
+    }
+
+    /// Create a new hasher with an optional key, salt and personalization, any of which may be empty.
+    ///
+    /// Shorter salts and personalizations are padded with zeros.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is longer than 32 bytes, or if the salt or personalization is longer than 8 bytes.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let mut hasher = lore::Blake2s::<16>::with_params("key", "salt", "me");
+    /// hasher.update("abc");
+    ///
+    /// assert_eq!(hasher.finalize().to_string(), "6ce549b6209a8994c7272010531992c5");
+    /// ```
+    #[must_use]
+    pub fn with_params(
+        key: impl AsRef<[u8]>,
+        salt: impl AsRef<[u8]>,
+        personalization: impl AsRef<[u8]>,
+    ) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_LENGTH;
+        let (key, salt, personalization) = (key.as_ref(), salt.as_ref(), personalization.as_ref());
+        assert!(key.len() <= 32, "BLAKE2s keys can be at most 32 bytes long");
+        assert!(salt.len() <= 8, "BLAKE2s salts can be at most 8 bytes long");
+        assert!(
+            personalization.len() <= 8,
+            "BLAKE2s personalizations can be at most 8 bytes long"
+        );
+
+        // xor the parameter block into the initialization vector
+        let mut state = IV;
+        state[0] ^= 0x0101_0000 ^ ((key.len() as u32) << 8) ^ S as u32;
+        for (offset, parameter) in [(4, salt), (6, personalization)] {
+            let mut padded = [0u8; 8];
+            padded[..parameter.len()].copy_from_slice(parameter);
+            for (i, word) in bytes_to_words_le::<2>(padded).iter().enumerate() {
+                state[offset + i] ^= word;
+            }
+        }
+
+        // the key is padded to a full block, and processed before the message
+        let key = (!key.is_empty()).then(|| {
+            let mut block = [0u8; 64];
+            block[..key.len()].copy_from_slice(key);
+            block
+        });
+
+        let mut hasher = Self {
+            state,
+            buffer: Buffer::new(),
+            counter: 0,
+            parameters: state,
+            key,
+        };
+        hasher.reset();
+
+        hasher
+    }
+
+    /// Discard the message hashed so far, keeping the key, salt and personalization.
+    pub fn reset(&mut self) {
+        self.state = self.parameters;
+        self.buffer = Buffer::new();
+        self.counter = 0;
+        if let Some(key) = self.key {
+            self.update(key);
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self {
+            state,
+            buffer,
+            counter,
+            ..
+        } = self;
+
+        // the last block is compressed differently, so blocks are only compressed once more data arrives
+        buffer.update_lazy(data.as_ref(), |block| {
+            *counter = counter.wrapping_add(64);
+            *state = compress(*state, block, *counter, false);
+        });
+    }
+
+    /// Pad the final block and return the digest of everything passed to [`Blake2s::update`].
+    #[must_use]
+    pub fn finalize(self) -> Digest<S> {
+        let remaining = self.buffer.remaining();
+        let mut block = [0u8; 64];
+        block[..remaining.len()].copy_from_slice(remaining);
+
+        let counter = self.counter.wrapping_add(remaining.len() as u64);
+        let state = compress(self.state, &block, counter, true);
+
+        Digest(words_to_bytes_le(state))
+    }
+}
+
+impl<const S: usize> Default for Blake2s<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const S: usize> Hasher for Blake2s<S> {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = S;
+
+    type Output = Digest<S>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<S> {
+        self.finalize()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the working vector for the single block of "abc", before the first round
+    #[rustfmt::skip]
+    const ABC_V: [u32; 16] = [
+        0x6b08e647, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527c, 0x9b05688c, 0xe07c2654, 0x5be0cd19,
+    ];
+
+    // the working vector after mixing the first column
+    #[rustfmt::skip]
+    const ABC_G: [u32; 16] = [
+        0xd6c0c1df, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x7f898693, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+        0xde856f07, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0xab1f9b2c, 0x9b05688c, 0xe07c2654, 0x5be0cd19,
+    ];
+
+    // the working vector after the first round
+    #[rustfmt::skip]
+    const ABC_ROUND0: [u32; 16] = [
+        0x16a3242e, 0xd7b5e238, 0xce8ce24b, 0x927aede1, 0xa7b430d9, 0x93a4a14e, 0xa44e7c31, 0x41d4759b,
+        0x95bf33d3, 0x9a99c181, 0x608a3a6b, 0xb666383e, 0x7a8dd50f, 0xbe378ed7, 0x353d1ee6, 0x3bb44c6b,
+    ];
+
+    // the working vector after the last round
+    #[rustfmt::skip]
+    const ABC_ROUNDS: [u32; 16] = [
+        0xd9c994aa, 0xcfec3aa6, 0x700d0ab2, 0x2c38670e, 0xaf6a1f66, 0x1d023ef3, 0x1d9ec27d, 0x945357a5,
+        0x3e9ffebd, 0x969fe811, 0xef485e21, 0xa632797a, 0xdeef082e, 0xaf3d80e1, 0x4e86829b, 0x4deafd3a,
+    ];
+
+    // the state after compressing the single block of "abc"
+    #[rustfmt::skip]
+    const ABC_H: [u32; 8] = [
+        0x8c5e8c50, 0xe2147c32, 0xa32ba7e1, 0x2f45eb4e, 0x208b4537, 0x293ad69e, 0x4c9b994d, 0x82596786,
+    ];
+
+    #[test]
+    fn blake2s_g() {
+        let v = g(ABC_V, [0, 4, 8, 12], 0x00636261, 0x00000000);
+        assert_eq!(ABC_G, v);
+
+        // only the four selected words are changed
+        for i in (0..16).filter(|i| i % 4 != 0) {
+            assert_eq!(ABC_V[i], v[i]);
+        }
+    }
+
+    #[test]
+    fn blake2s_rounds() {
+        let mut block = [0u8; 64];
+        block[..3].copy_from_slice(b"abc");
+        let m: [u32; 16] = bytes_to_words_le(block);
+
+        let mut h = IV;
+        h[0] ^= 0x0101_0000 ^ 32;
+        assert_eq!(ABC_V, initialize(h, 3, true));
+        assert_eq!(ABC_ROUND0, round(ABC_V, &m, 0));
+        assert_eq!(ABC_ROUNDS, (0..10).fold(ABC_V, |v, i| round(v, &m, i)));
+        assert_eq!(ABC_H, compress(h, &block, 3, true));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn blake2s_trace() {
+        let mut events = Vec::new();
+        trace::<32, _>("abc", &mut events);
+
+        let mut h = IV;
+        h[0] ^= 0x0101_0000 ^ 32;
+        assert!(events.contains(&Event::Step {
+            index: 9,
+            state: ABC_ROUNDS.into()
+        }));
+        assert!(events.contains(&Event::ChainingValue {
+            previous: h.into(),
+            working: ABC_ROUNDS.into(),
+            next: ABC_H.into(),
+        }));
+    }
+
+    #[test]
+    fn blake2s_hash() {
+        assert_eq!(
+            hash::<32>("").to_string(),
+            "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9"
+        );
+        assert_eq!(
+            hash::<32>("abc").to_string(),
+            "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+        );
+        assert_eq!(
+            hash::<32>("The quick brown fox jumps over the lazy dog").to_string(),
+            "606beeec743ccbeff6cbcdf5d5302aa855c256c29b88c8ed331ea1a6bf3c8812"
+        );
+        assert_eq!(
+            hash::<20>("abc").to_string(),
+            "5ae3b99be29b01834c3b508521ede60438f8de17"
+        );
+    }
+
+    #[test]
+    fn blake2s_params() {
+        let mut hasher = Blake2s::<32>::with_params("key", "", "");
+        hasher.update("abc");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "3f9723437b033bf0c1f4df43cafd0776068cb0a95912de13f3b2952a3aba764d"
+        );
+        let mut hasher = Blake2s::<32>::with_params("", "salt", "");
+        hasher.update("abc");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "ecb80331dbd0a6df3d6fb1b22183ad4079559805b191c1435aa32a05c683f17c"
+        );
+        let mut hasher = Blake2s::<32>::with_params("", "", "personal");
+        hasher.update("abc");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "760d673e5c2f4e339601f57e1f796762ab4a1af327c374164c732f55beab7009"
+        );
+        let mut hasher =
+            Blake2s::<24>::with_params("kkkkkkkkkkkkkkkkkkkkkkkkkkkkkkkk", "ssssssss", "pppppppp");
+        hasher.update("");
+        assert_eq!(
+            hasher.finalize().to_string(),
+            "bd1b49895f58f5708529ea38614a0b7107b566d95bef0cdb"
+        );
+
+        // a key with an empty message is a single, final block
+        assert_eq!(
+            Blake2s::<32>::with_key("key").finalize().to_string(),
+            "a65f92611fdc3722a305edf1ed575947aa86209290344f817e45c3a4edfddad9"
+        );
+    }
+
+    // deterministic input sequence for the RFC 7693 self-test
+    fn selftest_seq(length: usize, seed: u32) -> Vec<u8> {
+        let mut a = 0xdead4badu32.wrapping_mul(seed);
+        let mut b = 1u32;
+
+        (0..length)
+            .map(|_| {
+                let t = a.wrapping_add(b);
+                a = b;
+                b = t;
+                (t >> 24) as u8
+            })
+            .collect()
+    }
+
+    // hash the unkeyed and keyed digests of each input length into the grand hash
+    fn selftest_digests<const S: usize>(grand: &mut Blake2s<32>) {
+        for length in [0, 3, 64, 65, 255, 1024] {
+            let input = selftest_seq(length, length as u32);
+            grand.update(hash::<S>(&input));
+
+            let mut hasher = Blake2s::<S>::with_key(selftest_seq(S, S as u32));
+            hasher.update(&input);
+            grand.update(hasher.finalize());
+        }
+    }
+
+    #[test]
+    fn blake2s_selftest() {
+        let mut grand = Blake2s::<32>::new();
+        selftest_digests::<16>(&mut grand);
+        selftest_digests::<20>(&mut grand);
+        selftest_digests::<28>(&mut grand);
+        selftest_digests::<32>(&mut grand);
+
+        assert_eq!(
+            grand.finalize().to_string(),
+            "6a411f08ce25adcdfb02aba641451cec53c598b24f4fc787fbdc88797f4c1dfe"
+        );
+    }
+
+    #[test]
+    fn blake2s_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, 64 - 1, 64, 64 + 1, 2 * 64, 599, 600] {
+            let mut hasher = Blake2s::<32>::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash::<32>(message), hasher.finalize());
+
+            let mut hasher = Blake2s::<20>::with_key("key");
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            let mut reference = Blake2s::<20>::with_key("key");
+            reference.update(message);
+            assert_eq!(reference.finalize(), hasher.finalize());
+        }
+        assert_eq!(hash::<32>(""), Blake2s::<32>::new().finalize());
+    }
+
+    #[test]
+    fn blake2s_reset() {
+        let mut hasher = Blake2s::<20>::with_params("key", "salt", "me");
+        hasher.update("lol");
+        hasher.reset();
+        hasher.update("abc");
+
+        let mut reference = Blake2s::<20>::with_params("key", "salt", "me");
+        reference.update("abc");
+        assert_eq!(reference.finalize(), hasher.finalize());
+    }
+}
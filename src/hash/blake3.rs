@@ -0,0 +1,984 @@
+use crate::hash::{blake2s::g, bytes_to_words_le, words_to_bytes_le, Buffer, Digest, Hasher};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, Event};
+use crate::trace::{emit, Observer};
+use core::convert::TryInto;
+
+// based on the BLAKE3 specification and its reference implementation
+
+// the size of the chunks which form the leaves of the tree, in bytes
+pub const CHUNK_LEN: usize = 1024;
+
+// the initialization vector is the same as the SHA-256 initial hash value, and is used as the key when hashing without one
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// the permutation which is applied to the message words after each round
+const PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+// domain separation flags, which distinguish the kinds of compressions from each other
+pub const CHUNK_START: u32 = 1 << 0;
+pub const CHUNK_END: u32 = 1 << 1;
+pub const PARENT: u32 = 1 << 2;
+pub const ROOT: u32 = 1 << 3;
+pub const KEYED_HASH: u32 = 1 << 4;
+pub const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+pub const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+// compute a single round, which mixes the columns and then the diagonals using the same mixing function as BLAKE2s
+pub fn round(v: [u32; 16], m: &[u32; 16]) -> [u32; 16] {
+    let v = g(v, [0, 4, 8, 12], m[0], m[1]);
+    let v = g(v, [1, 5, 9, 13], m[2], m[3]);
+    let v = g(v, [2, 6, 10, 14], m[4], m[5]);
+    let v = g(v, [3, 7, 11, 15], m[6], m[7]);
+
+    let v = g(v, [0, 5, 10, 15], m[8], m[9]);
+    let v = g(v, [1, 6, 11, 12], m[10], m[11]);
+    let v = g(v, [2, 7, 8, 13], m[12], m[13]);
+    g(v, [3, 4, 9, 14], m[14], m[15])
+}
+
+// reorder the message words for the next round
+pub fn permute(m: [u32; 16]) -> [u32; 16] {
+    PERMUTATION.map(|i| m[i])
+}
+
+// set up the working vector from the chaining value, the counter, the length of the block and the flags
+pub fn initialize(cv: [u32; 8], counter: u64, block_len: u32, flags: u32) -> [u32; 16] {
+    let mut v = [0; 16];
+    v[..8].copy_from_slice(&cv);
+    v[8..12].copy_from_slice(&IV[..4]);
+    v[12] = counter as u32;
+    v[13] = (counter >> 32) as u32;
+    v[14] = block_len;
+    v[15] = flags;
+
+    v
+}
+
+// the compression function, which returns all 16 words since the extendable output needs them
+pub fn compress(
+    cv: [u32; 8],
+    block: &[u8; 64],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    compress_observed(cv, block, counter, block_len, flags, &mut ())
+}
+
+// the compression function, reporting each round to the observer
+#[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+pub fn compress_observed<O: Observer + ?Sized>(
+    cv: [u32; 8],
+    block: &[u8; 64],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+    observer: &mut O,
+) -> [u32; 16] {
+    let m = bytes_to_words_le(block);
+    let (v, _) = (0..7).fold(
+        (initialize(cv, counter, block_len, flags), m),
+        |(v, m), i| {
+            let v = round(v, &m);
+            emit!(
+                observer,
+                Event::Step {
+                    index: i,
+                    state: v.into(),
+                }
+            );
+            (v, permute(m))
+        },
+    );
+
+    let mut output = v;
+    for i in 0..8 {
+        output[i] ^= v[i + 8];
+        output[i + 8] ^= cv[i];
+    }
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: cv.into(),
+            working: v.into(),
+            next: output.into(),
+        }
+    );
+
+    output
+}
+
+// the chaining value is the first half of the output of the compression function
+fn truncate(output: [u32; 16]) -> [u32; 8] {
+    output[..8].try_into().unwrap()
+}
+
+// the block of a parent node, which holds the chaining values of its children
+fn parent_block(left: [u32; 8], right: [u32; 8]) -> [u8; 64] {
+    let mut words = [0; 16];
+    words[..8].copy_from_slice(&left);
+    words[8..].copy_from_slice(&right);
+
+    words_to_bytes_le(words)
+}
+
+/// Computes the BLAKE3 hash value (digest) of the input bytes.
+///
+/// Returns a 32-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+/// For output of any other length, use [`Blake3::finalize_xof`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let input = "abc";
+/// let digest = lore::blake3(input);
+///
+/// assert_eq!(
+///     digest.to_string(),
+///     "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+/// )
+/// ```
+pub fn hash(message: impl AsRef<[u8]>) -> Digest<32> {
+    let mut hasher = Blake3::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the BLAKE3 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// Every compression is reported as a block, in the order in which the tree is built, ending with the root.
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::blake3("abc", &mut events);
+///
+/// assert_eq!(digest, lore::blake3("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<32> {
+    let message = message.as_ref();
+
+    // only the last block of the last chunk can be partial
+    let mut padded = message.to_vec();
+    padded.resize(message.len().div_ceil(64).max(1) * 64, 0);
+    emit!(observer, Event::Padded { message: padded });
+
+    let mut tracer = Tracer {
+        observer,
+        blocks: 0,
+    };
+    let root = tracer.subtree(0, message);
+    let output = tracer.compress(&root, 0, ROOT);
+    let digest = Digest(words_to_bytes_le(output));
+
+    trace::digest(digest, tracer.observer)
+}
+
+/// Computes the keyed BLAKE3 hash value of the input bytes, for use as a message authentication code.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let key = *b"whats the Elvish word for friend";
+/// let digest = lore::blake3_keyed_hash(key, "abc");
+///
+/// assert_eq!(
+///     digest.to_string(),
+///     "157f8b4b104070014ab0b3b7aff364f794e010e92b1c976318e892f380b53406"
+/// )
+/// ```
+pub fn keyed_hash(key: [u8; 32], message: impl AsRef<[u8]>) -> Digest<32> {
+    let mut hasher = Blake3::with_key(key);
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Derives a 32-byte key from the key material, with a context string which should be hardcoded, globally unique and application-specific.
+///
+/// For keys of any other length, use [`Blake3::with_context`] and [`Blake3::finalize_xof`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let context = "lore 2022-09-14 example context";
+/// let key = lore::blake3_derive_key(context, "secret");
+///
+/// assert_eq!(
+///     key.to_string(),
+///     "521227bbd551cd8f81a3841ddbe708eb70166afbc0f5f0503c2c594267fa0ff4"
+/// )
+/// ```
+pub fn derive_key(context: &str, key_material: impl AsRef<[u8]>) -> Digest<32> {
+    let mut hasher = Blake3::with_context(context);
+    hasher.update(key_material);
+    hasher.finalize()
+}
+
+/// A streaming BLAKE3 hasher, for hashing messages which are not available all at once.
+///
+/// The message is split into chunks of 1024 bytes, which form the leaves of a binary tree.
+/// Only the current chunk and the chaining values of one subtree per level are kept, so arbitrarily long messages can be hashed in little memory.
+///
+/// The nodes of the tree can also be computed directly with [`Blake3::chunk`], [`Blake3::parent`] and [`Blake3::subtree`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Blake3::new();
+/// hasher.update("a");
+/// hasher.update("bc");
+///
+/// assert_eq!(hasher.finalize(), lore::blake3("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Blake3 {
+    key: [u32; 8],
+    flags: u32,
+    chunk: Chunk,
+    // the chaining values of the complete subtrees to the left of the current chunk, from the largest to the smallest,
+    // of which there can be at most 54, since there are at most 2^54 chunks in a message of 2^64 bytes
+    stack: [[u32; 8]; 54],
+    depth: usize,
+}
+
+impl Blake3 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_words(IV, 0)
+    }
+
+    /// Create a new keyed hasher with an empty message, for use as a message authentication code.
+    #[must_use]
+    pub fn with_key(key: [u8; 32]) -> Self {
+        Self::with_words(bytes_to_words_le(key), KEYED_HASH)
+    }
+
+    /// Create a new key derivation hasher for the given context, to which the key material is passed.
+    ///
+    /// The context string should be hardcoded, globally unique and application-specific.
+    #[must_use]
+    pub fn with_context(context: &str) -> Self {
+        let mut hasher = Self::with_words(IV, DERIVE_KEY_CONTEXT);
+        hasher.update(context);
+        let key = truncate(hasher.root().output(0));
+
+        Self::with_words(key, DERIVE_KEY_MATERIAL)
+    }
+
+    fn with_words(key: [u32; 8], flags: u32) -> Self {
+        Self {
+            key,
+            flags,
+            chunk: Chunk::new(key, 0, flags),
+            stack: [[0; 8]; 54],
+            depth: 0,
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let mut data = data.as_ref();
+
+        while !data.is_empty() {
+            // the last chunk is finalized differently, so full chunks are only added to the tree once more data arrives
+            if self.chunk.len() == CHUNK_LEN {
+                let chunks = self.chunk.counter + 1;
+                self.push(self.chunk.node().chaining_words(), chunks);
+                self.chunk = Chunk::new(self.key, chunks, self.flags);
+            }
+
+            let (head, tail) = data.split_at(data.len().min(CHUNK_LEN - self.chunk.len()));
+            self.chunk.update(head);
+            data = tail;
+        }
+    }
+
+    // add the chaining value of a full chunk to the tree, merging every subtree which is now complete
+    fn push(&mut self, mut cv: [u32; 8], mut chunks: u64) {
+        while chunks.is_multiple_of(2) {
+            self.depth -= 1;
+            cv = self
+                .parent_words(self.stack[self.depth], cv)
+                .chaining_words();
+            chunks /= 2;
+        }
+        self.stack[self.depth] = cv;
+        self.depth += 1;
+    }
+
+    // merge the current chunk with the subtrees on the stack, from the smallest to the largest
+    fn root(&self) -> Node {
+        self.stack[..self.depth]
+            .iter()
+            .rev()
+            .fold(self.chunk.node(), |node, &left| {
+                self.parent_words(left, node.chaining_words())
+            })
+    }
+
+    /// Return the digest of everything passed to [`Blake3::update`].
+    #[must_use]
+    pub fn finalize(self) -> Digest<32> {
+        self.root().root_hash()
+    }
+
+    /// Return a reader, from which any amount of output can be read.
+    ///
+    /// The first 32 bytes of the output are the same as the digest.
+    #[must_use]
+    pub fn finalize_xof(self) -> Reader {
+        self.root().root_output()
+    }
+
+    /// Discard the message hashed so far, keeping the key or context.
+    pub fn reset(&mut self) {
+        self.chunk = Chunk::new(self.key, 0, self.flags);
+        self.depth = 0;
+    }
+
+    /// The node of a single chunk of at most 1024 bytes, which is at the given index in the message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chunk is longer than 1024 bytes.
+    #[must_use]
+    pub fn chunk(&self, index: u64, data: impl AsRef<[u8]>) -> Node {
+        let data = data.as_ref();
+        assert!(
+            data.len() <= CHUNK_LEN,
+            "BLAKE3 chunks can be at most 1024 bytes long"
+        );
+
+        let mut chunk = Chunk::new(self.key, index, self.flags);
+        chunk.update(data);
+        chunk.node()
+    }
+
+    /// The parent node of two subtrees, given their chaining values.
+    #[must_use]
+    pub fn parent(&self, left: &Digest<32>, right: &Digest<32>) -> Node {
+        let words = |cv: &Digest<32>| bytes_to_words_le(cv);
+        self.parent_words(words(left), words(right))
+    }
+
+    fn parent_words(&self, left: [u32; 8], right: [u32; 8]) -> Node {
+        Node::new(
+            self.key,
+            parent_block(left, right),
+            0,
+            64,
+            self.flags | PARENT,
+        )
+    }
+
+    /// The root node of the tree over a range of chunks, which starts at the given chunk index in the message.
+    ///
+    /// The left subtree of every parent holds the largest power of two number of chunks which leaves at least one byte for the right subtree.
+    /// If the chunks form a subtree of the whole message, the chaining value of the node is the same as in the tree of the whole message.
+    /// This allows verifying a part of the message, without hashing all of it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let message = [0x2a; 5000];
+    /// let hasher = lore::Blake3::new();
+    ///
+    /// // the first four chunks form the left subtree, and the last chunk the right subtree
+    /// let left = hasher.subtree(0, &message[..4096]).chaining_value();
+    /// let right = hasher.subtree(4, &message[4096..]).chaining_value();
+    ///
+    /// assert_eq!(hasher.parent(&left, &right).root_hash(), lore::blake3(message));
+    /// ```
+    #[must_use]
+    pub fn subtree(&self, first_chunk: u64, data: impl AsRef<[u8]>) -> Node {
+        let data = data.as_ref();
+        if data.len() <= CHUNK_LEN {
+            return self.chunk(first_chunk, data);
+        }
+
+        // the largest power of two which is less than the number of chunks
+        let chunks = data.len().div_ceil(CHUNK_LEN);
+        let left_chunks = 1 << (usize::BITS - 1 - (chunks - 1).leading_zeros());
+        let (left, right) = data.split_at(left_chunks * CHUNK_LEN);
+
+        self.parent_words(
+            self.subtree(first_chunk, left).chaining_words(),
+            self.subtree(first_chunk + left_chunks as u64, right)
+                .chaining_words(),
+        )
+    }
+}
+
+impl Default for Blake3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Blake3 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 32;
+
+    type Output = Digest<32>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<32> {
+        self.finalize()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+// the chunk which is currently being hashed, whose blocks are chained together
+#[derive(Debug, Clone)]
+struct Chunk {
+    cv: [u32; 8],
+    counter: u64,
+    flags: u32,
+    buffer: Buffer<64>,
+    blocks: u64,
+}
+
+impl Chunk {
+    fn new(key: [u32; 8], counter: u64, flags: u32) -> Self {
+        Self {
+            cv: key,
+            counter,
+            flags,
+            buffer: Buffer::new(),
+            blocks: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.length() as usize
+    }
+
+    // the first block of a chunk is flagged
+    fn start_flag(&self) -> u32 {
+        if self.blocks == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        let Self {
+            cv,
+            counter,
+            flags,
+            buffer,
+            blocks,
+        } = self;
+
+        // the last block is compressed as part of the node, so blocks are only compressed once more data arrives
+        buffer.update_lazy(data, |block| {
+            let start = if *blocks == 0 { CHUNK_START } else { 0 };
+            *cv = truncate(compress(*cv, block, *counter, 64, *flags | start));
+            *blocks += 1;
+        });
+    }
+
+    fn node(&self) -> Node {
+        let remaining = self.buffer.remaining();
+        let mut block = [0u8; 64];
+        block[..remaining.len()].copy_from_slice(remaining);
+
+        Node::new(
+            self.cv,
+            block,
+            self.counter,
+            remaining.len() as u32,
+            self.flags | self.start_flag() | CHUNK_END,
+        )
+    }
+}
+
+// builds the tree of an unkeyed hash from the whole message at once, reporting every compression in order
+#[cfg(feature = "alloc")]
+struct Tracer<'a, O: ?Sized> {
+    observer: &'a mut O,
+    blocks: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<O: Observer + ?Sized> Tracer<'_, O> {
+    fn compress_block(
+        &mut self,
+        cv: [u32; 8],
+        block: &[u8; 64],
+        counter: u64,
+        block_len: u32,
+        flags: u32,
+    ) -> [u32; 16] {
+        let index = self.blocks;
+        emit!(
+            self.observer,
+            Event::Block {
+                index,
+                words: bytes_to_words_le::<16>(block).into(),
+            }
+        );
+        self.blocks += 1;
+
+        compress_observed(cv, block, counter, block_len, flags, self.observer)
+    }
+
+    // the last compression of a node, with the given counter and additional flags
+    fn compress(&mut self, node: &Node, counter: u64, flags: u32) -> [u32; 16] {
+        self.compress_block(
+            node.cv,
+            &node.block,
+            counter,
+            node.block_len,
+            node.flags | flags,
+        )
+    }
+
+    fn chaining_words(&mut self, node: &Node) -> [u32; 8] {
+        truncate(self.compress(node, node.counter, 0))
+    }
+
+    fn chunk(&mut self, index: u64, data: &[u8]) -> Node {
+        // every block but the last is compressed right away
+        let full = data.len().saturating_sub(1) / 64;
+        let mut cv = IV;
+        for (i, block) in data.as_chunks::<64>().0.iter().take(full).enumerate() {
+            let start = if i == 0 { CHUNK_START } else { 0 };
+            cv = truncate(self.compress_block(cv, block, index, 64, start));
+        }
+
+        let remaining = &data[full * 64..];
+        let mut block = [0u8; 64];
+        block[..remaining.len()].copy_from_slice(remaining);
+        let start = if full == 0 { CHUNK_START } else { 0 };
+
+        Node::new(cv, block, index, remaining.len() as u32, start | CHUNK_END)
+    }
+
+    // the same split as in `Blake3::subtree`
+    fn subtree(&mut self, first_chunk: u64, data: &[u8]) -> Node {
+        if data.len() <= CHUNK_LEN {
+            return self.chunk(first_chunk, data);
+        }
+
+        let chunks = data.len().div_ceil(CHUNK_LEN);
+        let left_chunks = 1 << (usize::BITS - 1 - (chunks - 1).leading_zeros());
+        let (left, right) = data.split_at(left_chunks * CHUNK_LEN);
+
+        let left = self.subtree(first_chunk, left);
+        let left = self.chaining_words(&left);
+        let right = self.subtree(first_chunk + left_chunks as u64, right);
+        let right = self.chaining_words(&right);
+
+        Node::new(IV, parent_block(left, right), 0, 64, PARENT)
+    }
+}
+
+/// A node of the BLAKE3 tree, i.e. a chunk or the parent of two subtrees, right before its last compression.
+///
+/// The last compression depends on whether the node is the root of the tree.
+/// Other nodes pass their chaining value on to their parent, while the root produces the output of the whole tree.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let hasher = lore::Blake3::new();
+/// let left = hasher.chunk(0, [0x2a; 1024]).chaining_value();
+/// let right = hasher.chunk(1, [0x2a; 1]).chaining_value();
+///
+/// assert_eq!(hasher.parent(&left, &right).root_hash(), lore::blake3([0x2a; 1025]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    cv: [u32; 8],
+    block: [u8; 64],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Node {
+    fn new(cv: [u32; 8], block: [u8; 64], counter: u64, block_len: u32, flags: u32) -> Self {
+        Self {
+            cv,
+            block,
+            counter,
+            block_len,
+            flags,
+        }
+    }
+
+    fn chaining_words(&self) -> [u32; 8] {
+        truncate(compress(
+            self.cv,
+            &self.block,
+            self.counter,
+            self.block_len,
+            self.flags,
+        ))
+    }
+
+    // the root is compressed once for every 64 bytes of output, with the counter as the index of the output block
+    fn output(&self, counter: u64) -> [u32; 16] {
+        compress(
+            self.cv,
+            &self.block,
+            counter,
+            self.block_len,
+            self.flags | ROOT,
+        )
+    }
+
+    /// The chaining value of the node, which is passed on to its parent.
+    #[must_use]
+    pub fn chaining_value(&self) -> Digest<32> {
+        Digest(words_to_bytes_le(self.chaining_words()))
+    }
+
+    /// The digest of the whole tree, if this node is its root.
+    #[must_use]
+    pub fn root_hash(&self) -> Digest<32> {
+        Digest(words_to_bytes_le(self.output(0)))
+    }
+
+    /// A reader for the extendable output of the whole tree, if this node is its root.
+    #[must_use]
+    pub fn root_output(&self) -> Reader {
+        Reader {
+            node: self.clone(),
+            counter: 0,
+            block: [0; 64],
+            position: 64,
+        }
+    }
+}
+
+/// Reads an arbitrary amount of output from the root of a BLAKE3 tree.
+///
+/// This is returned by [`Blake3::finalize_xof`] and [`Node::root_output`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Blake3::new();
+/// hasher.update("abc");
+/// let mut reader = hasher.finalize_xof();
+///
+/// // the output can be read in pieces of any size
+/// let mut output = [0u8; 40];
+/// reader.read(&mut output[..3]);
+/// reader.read(&mut output[3..]);
+///
+/// assert_eq!(&output[..32], lore::blake3("abc").as_ref());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reader {
+    node: Node,
+    counter: u64,
+    block: [u8; 64],
+    position: usize,
+}
+
+impl Reader {
+    /// Fill the output with the next bytes of the output stream.
+    pub fn read(&mut self, output: &mut [u8]) {
+        for byte in output {
+            // compress the root again when the current output block has been read
+            if self.position == 64 {
+                self.block = words_to_bytes_le(self.node.output(self.counter));
+                self.counter += 1;
+                self.position = 0;
+            }
+
+            *byte = self.block[self.position];
+            self.position += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::trace::Words;
+
+    // the working vector for the single block of "abc", which is also the root
+    #[rustfmt::skip]
+    const ABC_V: [u32; 16] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x00000000, 0x00000000, 0x00000003, 0x0000000b,
+    ];
+
+    // the working vector after the first round
+    #[rustfmt::skip]
+    const ABC_ROUND0: [u32; 16] = [
+        0xdb143866, 0x950eaea4, 0xb01bb383, 0x8a76b87b, 0x0e26c52c, 0x0a0ccdef, 0x19283993, 0xa4e212be,
+        0x906c3ec2, 0x5879cdbb, 0x8b4a7ef9, 0x8186f2f7, 0xbef17dec, 0x3ca110c7, 0x252cbc73, 0xfb497983,
+    ];
+
+    // the working vector after the second round, with the permuted message words
+    #[rustfmt::skip]
+    const ABC_ROUND1: [u32; 16] = [
+        0xf7cd6139, 0xd1d50a38, 0xccfa64dd, 0xb71dc538, 0xde7670ad, 0x7963493e, 0x9749662c, 0xc4a2c4f0,
+        0x3b74ed33, 0x3614e198, 0x4fd9e822, 0xb2ffcf61, 0x13d2662d, 0x3a9fbd02, 0x61bc98ff, 0x4e66d7ff,
+    ];
+
+    // the full output of the compression function
+    #[rustfmt::skip]
+    const ABC_OUTPUT: [u32; 16] = [
+        0xacb33764, 0x33514638, 0x753bb6ff, 0xb58d3a27, 0x4658c548, 0x03db795d, 0x6c9c35fd, 0x859dbdd5,
+        0xae50b21f, 0xd0f59373, 0x5db61328, 0x490d1a52, 0x9ca09b2d, 0x4c7fcef7, 0xf200d9ff, 0x0bbf7433,
+    ];
+
+    // the key and context of the official test vectors
+    const KEY: [u8; 32] = *b"whats the Elvish word for friend";
+    const CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+    // the hash, keyed hash and derived key of inputs of different lengths
+    #[rustfmt::skip]
+    const VECTORS: [(usize, &str, &str, &str); 18] = [
+        (0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262", "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26", "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d"),
+        (1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213", "6d7878dfff2f485635d39013278ae14f1454b8c0a3a2d34bc1ab38228a80c95b", "b3e2e340a117a499c6cf2398a19ee0d29cca2bb7404c73063382693bf66cb06c"),
+        (63, "e9bc37a594daad83be9470df7f7b3798297c3d834ce80ba85d6e207627b7db7b", "bb1eb5d4afa793c1ebdd9fb08def6c36d10096986ae0cfe148cd101170ce37ae", "b6451e30b953c206e34644c6803724e9d2725e0893039cfc49584f991f451af3"),
+        (64, "4eed7141ea4a5cd4b788606bd23f46e212af9cacebacdc7d1f4c6dc7f2511b98", "ba8ced36f327700d213f120b1a207a3b8c04330528586f414d09f2f7d9ccb7e6", "a5c4a7053fa86b64746d4bb688d06ad1f02a18fce9afd3e818fefaa7126bf73e"),
+        (65, "de1e5fa0be70df6d2be8fffd0e99ceaa8eb6e8c93a63f2d8d1c30ecb6b263dee", "c0a4edefa2d2accb9277c371ac12fcdbb52988a86edc54f0716e1591b4326e72", "51fd05c3c1cfbc8ed67d139ad76f5cf8236cd2acd26627a30c104dfd9d3ff8a8"),
+        (1023, "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11", "c951ecdf03288d0fcc96ee3413563d8a6d3589547f2c2fb36d9786470f1b9d6e", "74a16c1c3d44368a86e1ca6df64be6a2f64cce8f09220787450722d85725dea5"),
+        (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7", "75c46f6f3d9eb4f55ecaaee480db732e6c2105546f1e675003687c31719c7ba4", "7356cd7720d5b66b6d0697eb3177d9f8d73a4a5c5e968896eb6a689684302706"),
+        (1025, "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444", "357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69", "effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb"),
+        (2048, "e776b6028c7cd22a4d0ba182a8bf62205d2ef576467e838ed6f2529b85fba24a", "879cf1fa2ea0e79126cb1063617a05b6ad9d0b696d0d757cf053439f60a99dd1", "7b2945cb4fef70885cc5d78a87bf6f6207dd901ff239201351ffac04e1088a23"),
+        (2049, "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030", "9f29700902f7c86e514ddc4df1e3049f258b2472b6dd5267f61bf13983b78dd5", "2ea477c5515cc3dd606512ee72bb3e0e758cfae7232826f35fb98ca1bcbdf273"),
+        (3072, "b98cb0ff3623be03326b373de6b9095218513e64f1ee2edd2525c7ad1e5cffd2", "044a0e7b172a312dc02a4c9a818c036ffa2776368d7f528268d2e6b5df191770", "050df97f8c2ead654d9bb3ab8c9178edcd902a32f8495949feadcc1e0480c46b"),
+        (3073, "7124b49501012f81cc7f11ca069ec9226cecb8a2c850cfe644e327d22d3e1cd3", "68dede9bef00ba89e43f31a6825f4cf433389fedae75c04ee9f0cf16a427c95a", "72613c9ec9ff7e40f8f5c173784c532ad852e827dba2bf85b2ab4b76f7079081"),
+        (4096, "015094013f57a5277b59d8475c0501042c0b642e531b0a1c8f58d2163229e969", "befc660aea2f1718884cd8deb9902811d332f4fc4a38cf7c7300d597a081bfc0", "1e0d7f3db8c414c97c6307cbda6cd27ac3b030949da8e23be1a1a924ad2f25b9"),
+        (4097, "9b4052b38f1c5fc8b1f9ff7ac7b27cd242487b3d890d15c96a1c25b8aa0fb995", "00df940cd36bb9fa7cbbc3556744e0dbc8191401afe70520ba292ee3ca80abbc", "aca51029626b55fda7117b42a7c211f8c6e9ba4fe5b7a8ca922f34299500ead8"),
+        (5120, "9cadc15fed8b5d854562b26a9536d9707cadeda9b143978f319ab34230535833", "2c493e48e9b9bf31e0553a22b23503c0a3388f035cece68eb438d22fa1943e20", "7a7acac8a02adcf3038d74cdd1d34527de8a0fcc0ee3399d1262397ce5817f60"),
+        (8193, "bab6c09cb8ce8cf459261398d2e7aef35700bf488116ceb94a36d0f5f1b7bc3b", "954a2a75420c8d6547e3ba5b98d963e6fa6491addc8c023189cc519821b4a1f5", "af1e0346e389b17c23200270a64aa4e1ead98c61695d917de7d5b00491c9b0f1"),
+        (16384, "f875d6646de28985646f34ee13be9a576fd515f76b5b0a26bb324735041ddde4", "9e9fc4eb7cf081ea7c47d1807790ed211bfec56aa25bb7037784c13c4b707b0d", "160e18b5878cd0df1c3af85eb25a0db5344d43a6fbd7a8ef4ed98d0714c3f7e1"),
+        (31744, "62b6960e1a44bcc1eb1a611a8d6235b6b4b78f32e7abc4fb4c6cdcce94895c47", "efa53b389ab67c593dba624d898d0f7353ab99e4ac9d42302ee64cbf9939a419", "39772aef80e0ebe60596361e45b061e8f417429d529171b6764468c22928e28e"),
+    ];
+
+    // extendable output of the keyed hash of 1025 bytes, spanning three output blocks
+    #[rustfmt::skip]
+    const XOF: [u8; 131] = [
+        0x35, 0x7d, 0xc5, 0x5d, 0xe0, 0xc7, 0xe3, 0x82, 0xc9, 0x00, 0xfd, 0x6e, 0x32, 0x0a, 0xcc, 0x04,
+        0x14, 0x6b, 0xe0, 0x1d, 0xb6, 0xa8, 0xce, 0x72, 0x10, 0xb7, 0x18, 0x9b, 0xd6, 0x64, 0xea, 0x69,
+        0x36, 0x23, 0x96, 0xb7, 0x7f, 0xdc, 0x0d, 0x26, 0x34, 0xa5, 0x52, 0x97, 0x08, 0x43, 0x72, 0x20,
+        0x66, 0xc3, 0xc1, 0x59, 0x02, 0xae, 0x50, 0x97, 0xe0, 0x0f, 0xf5, 0x3f, 0x1e, 0x11, 0x6f, 0x1c,
+        0xd5, 0x35, 0x27, 0x20, 0x11, 0x3a, 0x83, 0x7a, 0xb2, 0x45, 0x2c, 0xaf, 0xbd, 0xe4, 0xd5, 0x40,
+        0x85, 0xd9, 0xcf, 0x5d, 0x21, 0xca, 0x61, 0x30, 0x71, 0x55, 0x1b, 0x25, 0xd5, 0x2e, 0x69, 0xd6,
+        0xc8, 0x11, 0x23, 0x87, 0x2b, 0x6f, 0x19, 0xcd, 0x3b, 0xc1, 0x33, 0x3e, 0xdf, 0x0c, 0x52, 0xb9,
+        0x4d, 0xe2, 0x3b, 0xa7, 0x72, 0xcf, 0x82, 0x63, 0x6c, 0xff, 0x45, 0x42, 0x54, 0x0a, 0x77, 0x38,
+        0xd5, 0xb9, 0x30,
+    ];
+
+    // the chaining values of the chunks of 4097 bytes, and of the subtree of the first four chunks
+    const TREE_CHUNKS: [&str; 5] = [
+        "5c9e654411e393d1f4bec710ccd5bc5669ab177d610a0eb691fcfee92fb4e8b1",
+        "5b1f23b8390368a2c15095910389d1b716eb52a6dfd34d0aa66a22a21b761ca7",
+        "31af34be96abc0f78f175c2b7af34754a2011916b9d343582c74d06f07153ca8",
+        "2384956f6c5c4cd565731e113eda8c592474646eb299b5d90b7b6899689b726e",
+        "809187020fb739eff22a86412de600c2d171052ee5a3cf5ff792e8dad1fd0eb8",
+    ];
+    const TREE_SUBTREE: &str = "7de819387cc4a00a473ed9b0f7802fa8d3b38d18a5e49ff3f034dd77be59af62";
+
+    // an input of the given length, repeating the bytes 0 to 250
+    fn input(length: usize) -> Vec<u8> {
+        (0..length).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn blake3_rounds() {
+        let mut block = [0u8; 64];
+        block[..3].copy_from_slice(b"abc");
+        let m = bytes_to_words_le(block);
+
+        let flags = CHUNK_START | CHUNK_END | ROOT;
+        assert_eq!(ABC_V, initialize(IV, 0, 3, flags));
+        assert_eq!(ABC_ROUND0, round(ABC_V, &m));
+        assert_eq!(ABC_ROUND1, round(ABC_ROUND0, &permute(m)));
+        assert_eq!(ABC_OUTPUT, compress(IV, &block, 0, 3, flags));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn blake3_trace() {
+        let mut events = Vec::new();
+        trace("abc", &mut events);
+
+        // a single chunk with a single block is compressed once, as the root
+        let steps: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Step { state, .. } => Some(state.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(7, steps.len());
+        assert_eq!(Words::from(ABC_ROUND0), steps[0]);
+        assert_eq!(Words::from(ABC_ROUND1), steps[1]);
+        assert!(matches!(
+            events.iter().rev().nth(1),
+            Some(Event::ChainingValue { next, .. }) if *next == ABC_OUTPUT.into()
+        ));
+
+        // every compression of a larger tree is reported: 16 + 16 + 1 blocks of the chunks, 2 parents
+        let mut events = Vec::new();
+        let digest = trace([0x2a; 2049], &mut events);
+        let blocks = events
+            .iter()
+            .filter(|event| matches!(event, Event::Block { .. }))
+            .count();
+        assert_eq!(35, blocks);
+        assert_eq!(hash([0x2a; 2049]), digest);
+    }
+
+    #[test]
+    fn blake3_hash() {
+        for (length, digest, keyed, derived) in VECTORS {
+            let message = input(length);
+            assert_eq!(digest, hash(&message).to_string());
+            assert_eq!(keyed, keyed_hash(KEY, &message).to_string());
+            assert_eq!(derived, derive_key(CONTEXT, &message).to_string());
+        }
+    }
+
+    #[test]
+    fn blake3_xof() {
+        let mut hasher = Blake3::with_key(KEY);
+        hasher.update(input(1025));
+        let mut reader = hasher.finalize_xof();
+
+        let mut output = [0u8; 131];
+        reader.read(&mut output[..1]);
+        reader.read(&mut output[1..64]);
+        reader.read(&mut output[64..]);
+        assert_eq!(XOF, output);
+    }
+
+    #[test]
+    fn blake3_tree() {
+        let message = input(4 * CHUNK_LEN + 1);
+        let hasher = Blake3::new();
+
+        // the chunks are the leaves of the tree
+        let chunks = message
+            .chunks(CHUNK_LEN)
+            .enumerate()
+            .map(|(i, chunk)| hasher.chunk(i as u64, chunk).chaining_value())
+            .collect::<Vec<_>>();
+        for (cv, expected) in chunks.iter().zip(TREE_CHUNKS) {
+            assert_eq!(expected, cv.to_string());
+        }
+
+        // the first four chunks form a complete subtree, and the last chunk is its sibling
+        let left = hasher.parent(&chunks[0], &chunks[1]).chaining_value();
+        let right = hasher.parent(&chunks[2], &chunks[3]).chaining_value();
+        let subtree = hasher.parent(&left, &right).chaining_value();
+        assert_eq!(TREE_SUBTREE, subtree.to_string());
+        assert_eq!(
+            subtree,
+            hasher
+                .subtree(0, &message[..4 * CHUNK_LEN])
+                .chaining_value()
+        );
+
+        let root = hasher.parent(
+            &subtree,
+            &hasher.chunk(4, &message[4 * CHUNK_LEN..]).chaining_value(),
+        );
+        assert_eq!(hash(&message), root.root_hash());
+        assert_eq!(hash(&message), hasher.subtree(0, &message).root_hash());
+    }
+
+    #[test]
+    fn blake3_subtree() {
+        for (length, digest, keyed, _) in VECTORS {
+            let message = input(length);
+            assert_eq!(
+                digest,
+                Blake3::new().subtree(0, &message).root_hash().to_string()
+            );
+            assert_eq!(
+                keyed,
+                Blake3::with_key(KEY)
+                    .subtree(0, &message)
+                    .root_hash()
+                    .to_string()
+            );
+        }
+
+        // a range of chunks in the middle of the message, which is the right subtree of the left half
+        let message = input(16 * CHUNK_LEN);
+        let hasher = Blake3::new();
+        let range = hasher.subtree(4, &message[4 * CHUNK_LEN..8 * CHUNK_LEN]);
+        let left = hasher
+            .subtree(0, &message[..4 * CHUNK_LEN])
+            .chaining_value();
+        let half = hasher.parent(&left, &range.chaining_value());
+        assert_eq!(hasher.subtree(0, &message[..8 * CHUNK_LEN]), half);
+    }
+
+    #[test]
+    fn blake3_streaming() {
+        let message = input(5 * CHUNK_LEN + 7);
+        for split in [
+            0,
+            1,
+            63,
+            64,
+            65,
+            CHUNK_LEN,
+            CHUNK_LEN + 1,
+            2 * CHUNK_LEN,
+            4 * CHUNK_LEN + 3,
+            message.len(),
+        ] {
+            for mut hasher in [
+                Blake3::new(),
+                Blake3::with_key(KEY),
+                Blake3::with_context(CONTEXT),
+            ] {
+                let mut reference = hasher.clone();
+                reference.update(&message);
+                hasher.update(&message[..split]);
+                hasher.update(&message[split..]);
+                assert_eq!(reference.finalize(), hasher.finalize());
+            }
+        }
+
+        let mut hasher = Blake3::with_key(KEY);
+        hasher.update("lol");
+        hasher.reset();
+        assert_eq!(keyed_hash(KEY, ""), hasher.finalize());
+    }
+}
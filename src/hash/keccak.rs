@@ -0,0 +1,818 @@
+use crate::hash::{Buffer, Digest, Padding};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// based on FIPS 202, SHA-3 Standard: Permutation-Based Hash and Extendable-Output Functions
+
+// the state is a 5x5 array of 64-bit lanes, where the lane at (x, y) is stored at index x + 5y
+
+// round constants for the ι step mapping
+#[rustfmt::skip]
+const RC: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+// rotation offsets for the ρ step mapping
+const R: [u32; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+// xor each lane with the parities of two nearby columns
+pub fn theta(mut state: [u64; 25]) -> [u64; 25] {
+    let mut parities = [0u64; 5];
+    for (i, lane) in state.iter().enumerate() {
+        parities[i % 5] ^= lane;
+    }
+
+    for (i, lane) in state.iter_mut().enumerate() {
+        let x = i % 5;
+        *lane ^= parities[(x + 4) % 5] ^ parities[(x + 1) % 5].rotate_left(1);
+    }
+
+    state
+}
+
+// rotate each lane by a fixed offset
+pub fn rho(mut state: [u64; 25]) -> [u64; 25] {
+    for (lane, offset) in state.iter_mut().zip(R) {
+        *lane = lane.rotate_left(offset);
+    }
+
+    state
+}
+
+// rearrange the positions of the lanes
+pub fn pi(state: [u64; 25]) -> [u64; 25] {
+    let mut permuted = [0u64; 25];
+    for (i, lane) in permuted.iter_mut().enumerate() {
+        let (x, y) = (i % 5, i / 5);
+        *lane = state[(x + 3 * y) % 5 + 5 * x];
+    }
+
+    permuted
+}
+
+// xor each lane with a non-linear function of the two following lanes in its row
+pub fn chi(state: [u64; 25]) -> [u64; 25] {
+    let mut mixed = state;
+    for (i, lane) in mixed.iter_mut().enumerate() {
+        let (x, y) = (i % 5, i / 5);
+        *lane ^= !state[(x + 1) % 5 + 5 * y] & state[(x + 2) % 5 + 5 * y];
+    }
+
+    mixed
+}
+
+// xor the first lane with the round constant
+pub fn iota(mut state: [u64; 25], round: usize) -> [u64; 25] {
+    state[0] ^= RC[round];
+
+    state
+}
+
+// compute a single round of the permutation
+pub fn round(state: [u64; 25], i: usize) -> [u64; 25] {
+    iota(chi(pi(rho(theta(state)))), i)
+}
+
+// the Keccak-f[1600] permutation, which consists of 24 rounds
+pub fn permute(state: [u64; 25]) -> [u64; 25] {
+    permute_observed(state, &mut ())
+}
+
+// the Keccak-f[1600] permutation, reporting each round to the observer
+pub fn permute_observed<O: Observer + ?Sized>(state: [u64; 25], observer: &mut O) -> [u64; 25] {
+    (0..24).fold(state, |state, i| {
+        let state = round(state, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: state.into(),
+            }
+        );
+        state
+    })
+}
+
+// pad the message to a multiple of the rate, including the domain separation suffix
+#[cfg(feature = "alloc")]
+pub fn pad(message: impl AsRef<[u8]>, rate: usize, suffix: u8) -> Vec<u8> {
+    let mut message = message.as_ref().to_vec();
+    message.extend_from_slice(padding(message.len() as u64, rate, suffix).as_ref());
+
+    message
+}
+
+// compute the padding which follows a message of the given length (in bytes)
+//
+// the suffix holds the domain separation bits followed by the first 1 bit of the pad10*1 rule,
+// which is 01 + 1 (0x06) for the SHA-3 hash functions and 1111 + 1 (0x1f) for SHAKE.
+// the padding is never longer than the rate, and the largest rate is the one of SHAKE128.
+pub fn padding(length: u64, rate: usize, suffix: u8) -> Padding<168> {
+    let mut padding = Padding::new();
+    padding.push(suffix);
+
+    // pad with 0 bits until the length is a multiple of the rate
+    while (length as usize).wrapping_add(padding.len()) % rate != 0 {
+        padding.push(0);
+    }
+
+    // the final 1 bit of pad10*1
+    *padding.as_mut().last_mut().unwrap() |= 0x80;
+
+    padding
+}
+
+// xor a block of the padded message into the state and permute it
+pub fn absorb(state: [u64; 25], block: &[u8]) -> [u64; 25] {
+    absorb_observed(state, block, &mut ())
+}
+
+// xor a block of the padded message into the state and permute it, reporting each round to the observer
+pub fn absorb_observed<O: Observer + ?Sized>(
+    mut state: [u64; 25],
+    block: &[u8],
+    observer: &mut O,
+) -> [u64; 25] {
+    for (lane, word) in state.iter_mut().zip(lanes(block)) {
+        *lane ^= word;
+    }
+
+    permute_observed(state, observer)
+}
+
+// the little-endian lanes of a block, which is shorter than the state
+fn lanes(block: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    block
+        .as_chunks::<8>()
+        .0
+        .iter()
+        .map(|lane| u64::from_le_bytes(*lane))
+}
+
+// compute `S` bytes of output for the message, reporting every intermediate value to the observer,
+// including the permutations needed to squeeze more than `RATE` bytes
+#[cfg(feature = "alloc")]
+pub fn trace<const RATE: usize, const S: usize, O: Observer + ?Sized>(
+    message: impl AsRef<[u8]>,
+    suffix: u8,
+    observer: &mut O,
+) -> Digest<S> {
+    let padded = pad(message, RATE, suffix);
+    let mut state = compress_blocks(
+        &padded,
+        [0; 25],
+        observer,
+        |block: [u8; RATE]| lanes(&block).collect::<Vec<_>>(),
+        |state, block, observer| absorb_observed(state, block, observer),
+    );
+
+    let mut digest = [0; S];
+    for (i, output) in digest.chunks_mut(RATE).enumerate() {
+        if i > 0 {
+            state = permute_observed(state, observer);
+        }
+        Reader::<RATE>::new(state).read(output);
+    }
+
+    trace::digest(Digest(digest), observer)
+}
+
+// the sponge construction shared by the SHA-3 hash functions and the SHAKE extendable-output functions,
+// which absorbs `RATE` bytes per permutation
+#[derive(Debug, Clone)]
+pub struct Sponge<const RATE: usize> {
+    state: [u64; 25],
+    buffer: Buffer<RATE>,
+    suffix: u8,
+}
+
+impl<const RATE: usize> Sponge<RATE> {
+    pub fn new(suffix: u8) -> Self {
+        Self {
+            state: [0; 25],
+            buffer: Buffer::new(),
+            suffix,
+        }
+    }
+
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer, .. } = self;
+        buffer.update(data.as_ref(), |block| *state = absorb(*state, block));
+    }
+
+    pub fn finalize(mut self) -> Reader<RATE> {
+        self.update(padding(self.buffer.length(), RATE, self.suffix));
+
+        Reader::new(self.state)
+    }
+
+    // pad the message and read the first `S` bytes of output
+    pub fn squeeze<const S: usize>(self) -> Digest<S> {
+        let mut digest = [0; S];
+        self.finalize().read(&mut digest);
+
+        Digest(digest)
+    }
+}
+
+// absorb everything the reader produces and squeeze `S` bytes of output, which the SHAKE functions share
+#[cfg(feature = "std")]
+pub fn hash_reader<const RATE: usize, const S: usize>(
+    suffix: u8,
+    mut reader: impl std::io::Read,
+) -> std::io::Result<Digest<S>> {
+    let mut sponge = Sponge::<RATE>::new(suffix);
+    std::io::copy(&mut reader, &mut sponge)?;
+
+    Ok(sponge.squeeze())
+}
+
+// absorb a file, which is read in chunks, and squeeze `S` bytes of output
+#[cfg(feature = "std")]
+pub fn hash_file<const RATE: usize, const S: usize>(
+    suffix: u8,
+    path: &std::path::Path,
+) -> Result<Digest<S>, crate::io::HashFileError> {
+    std::fs::File::open(path)
+        .and_then(|file| hash_reader::<RATE, S>(suffix, file))
+        .map_err(|source| crate::io::HashFileError::new(path, source))
+}
+
+// absorb a file which is mapped into memory and squeeze `S` bytes of output
+#[cfg(feature = "mmap")]
+pub fn hash_file_mmap<const RATE: usize, const S: usize>(
+    suffix: u8,
+    path: &std::path::Path,
+) -> Result<Digest<S>, crate::io::HashFileError> {
+    let mut sponge = Sponge::<RATE>::new(suffix);
+    sponge.update(&crate::io::map(path)?[..]);
+
+    Ok(sponge.squeeze())
+}
+
+/// Squeezes an arbitrary amount of output from a sponge, after the whole message has been absorbed.
+///
+/// This is returned by the SHAKE extendable-output functions, e.g. [`crate::Shake128::finalize_xof`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Shake128::new();
+/// hasher.update("abc");
+/// let mut reader = hasher.finalize_xof();
+///
+/// // the output can be read in pieces of any size
+/// let mut output = [0u8; 8];
+/// reader.read(&mut output[..3]);
+/// reader.read(&mut output[3..]);
+///
+/// assert_eq!(output, [0x58, 0x81, 0x09, 0x2d, 0xd8, 0x18, 0xbf, 0x5c]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reader<const RATE: usize> {
+    state: [u64; 25],
+    position: usize,
+}
+
+impl<const RATE: usize> Reader<RATE> {
+    pub(crate) fn new(state: [u64; 25]) -> Self {
+        Self { state, position: 0 }
+    }
+
+    /// Fill the output with the next bytes of the output stream.
+    pub fn read(&mut self, output: &mut [u8]) {
+        for byte in output {
+            // squeeze another block when the current one has been read
+            if self.position == RATE {
+                self.state = permute(self.state);
+                self.position = 0;
+            }
+
+            *byte = (self.state[self.position / 8] >> (8 * (self.position % 8))) as u8;
+            self.position += 1;
+        }
+    }
+}
+
+// define the hash function, the trace function and the streaming hasher of a SHA-3 function, which squeezes a single
+// digest of `$size` bytes from the sponge, in the module of the function
+macro_rules! sha3 {
+    (
+        $name:literal,
+        $hash:ident,
+        $hasher:ident,
+        $size:literal,
+        $abc:literal,
+        rate: $rate:ident,
+        suffix: $suffix:ident $(,)?
+    ) => {
+        #[doc = concat!("Computes the ", $name, " hash value (digest) of the input bytes.")]
+        ///
+        #[doc = concat!(
+            "Returns a ",
+            stringify!($size),
+            "-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation."
+        )]
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rust
+        /// let input = "abc";
+        #[doc = concat!("let digest = lore::", stringify!($hash), "(input);")]
+        ///
+        /// assert_eq!(
+        ///     digest.to_string(),
+        #[doc = concat!("    \"", $abc, "\"")]
+        /// )
+        /// ```
+        pub fn hash(message: impl AsRef<[u8]>) -> $crate::hash::Digest<$size> {
+            let mut hasher = $hasher::new();
+            hasher.update(message);
+            hasher.finalize()
+        }
+
+        #[doc = concat!(
+            "Computes the ",
+            $name,
+            " hash value (digest) of the input bytes, reporting every intermediate value to the observer."
+        )]
+        ///
+        /// See the [`trace`](crate::trace) module for the events which are reported.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// let mut events = Vec::new();
+        #[doc = concat!("let digest = lore::trace::", stringify!($hash), "(\"abc\", &mut events);")]
+        ///
+        #[doc = concat!("assert_eq!(digest, lore::", stringify!($hash), "(\"abc\"));")]
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn trace<O: $crate::trace::Observer + ?Sized>(
+            message: impl AsRef<[u8]>,
+            observer: &mut O,
+        ) -> $crate::hash::Digest<$size> {
+            $crate::hash::keccak::trace::<$rate, $size, O>(message, $suffix, observer)
+        }
+
+        #[doc = concat!(
+            "A streaming ",
+            $name,
+            " hasher, for hashing messages which are not available all at once."
+        )]
+        ///
+        /// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        #[doc = concat!("let mut hasher = lore::", stringify!($hasher), "::new();")]
+        /// hasher.update("a");
+        /// hasher.update("bc");
+        ///
+        #[doc = concat!("assert_eq!(hasher.finalize(), lore::", stringify!($hash), "(\"abc\"));")]
+        /// ```
+        #[derive(Debug, Clone)]
+        pub struct $hasher {
+            sponge: $crate::hash::keccak::Sponge<$rate>,
+        }
+
+        impl $hasher {
+            /// Create a new hasher with an empty message.
+            #[must_use]
+            pub fn new() -> Self {
+                Self {
+                    sponge: $crate::hash::keccak::Sponge::new($suffix),
+                }
+            }
+
+            /// Append data to the message being hashed.
+            pub fn update(&mut self, data: impl AsRef<[u8]>) {
+                self.sponge.update(data);
+            }
+
+            #[doc = concat!(
+                "Pad the message and return the digest of everything passed to [`",
+                stringify!($hasher),
+                "::update`]."
+            )]
+            #[must_use]
+            pub fn finalize(self) -> $crate::hash::Digest<$size> {
+                self.sponge.squeeze()
+            }
+        }
+
+        impl Default for $hasher {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $crate::hash::Hasher for $hasher {
+            const BLOCK_SIZE: usize = $rate;
+            const OUTPUT_SIZE: usize = $size;
+
+            type Output = $crate::hash::Digest<$size>;
+
+            fn update(&mut self, data: impl AsRef<[u8]>) {
+                self.update(data);
+            }
+
+            fn finalize(self) -> $crate::hash::Digest<$size> {
+                self.finalize()
+            }
+        }
+    };
+}
+pub(crate) use sha3;
+
+// define the output function, the trace function and the streaming hasher of a SHAKE extendable-output function, which
+// squeezes any amount of output from the sponge, in the module of the function
+macro_rules! shake {
+    (
+        $name:literal,
+        $hash:ident,
+        $hasher:ident,
+        $abc:literal,
+        rate: $rate:ident,
+        suffix: $suffix:ident $(,)?
+    ) => {
+        #[doc = concat!("Computes `S` bytes of ", $name, " output for the input bytes.")]
+        ///
+        /// Returns an `S`-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+        #[doc = concat!(
+            "For output of a length which is not known at compile time, use [`",
+            stringify!($hasher),
+            "::finalize_xof`].",
+        )]
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rust
+        /// let input = "abc";
+        #[doc = concat!("let digest = lore::", stringify!($hash), "::<16>(input);")]
+        ///
+        #[doc = concat!("assert_eq!(digest.to_string(), ", "\"", $abc, "\")")]
+        /// ```
+        pub fn hash<const S: usize>(message: impl AsRef<[u8]>) -> $crate::hash::Digest<S> {
+            let mut hasher = $hasher::new();
+            hasher.update(message);
+            hasher.finalize()
+        }
+
+        #[doc = concat!(
+            "Computes `S` bytes of ",
+            $name,
+            " output for the input bytes, reporting every intermediate value to the observer.",
+        )]
+        ///
+        /// When more than one block of output is needed, the rounds of the additional permutations are reported as well.
+        /// See the [`trace`](crate::trace) module for the events which are reported.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// let mut events = Vec::new();
+        #[doc = concat!("let digest = lore::trace::", stringify!($hash), "::<16, _>(\"abc\", &mut events);")]
+        ///
+        #[doc = concat!("assert_eq!(digest, lore::", stringify!($hash), "::<16>(\"abc\"));")]
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn trace<const S: usize, O: $crate::trace::Observer + ?Sized>(
+            message: impl AsRef<[u8]>,
+            observer: &mut O,
+        ) -> $crate::hash::Digest<S> {
+            $crate::hash::keccak::trace::<$rate, S, O>(message, $suffix, observer)
+        }
+
+        #[doc = concat!("A streaming ", $name, " hasher, for hashing messages which are not available all at once.")]
+        ///
+        /// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        #[doc = concat!("let mut hasher = lore::", stringify!($hasher), "::new();")]
+        /// hasher.update("a");
+        /// hasher.update("bc");
+        ///
+        #[doc = concat!("assert_eq!(hasher.finalize::<32>(), lore::", stringify!($hash), "::<32>(\"abc\"));")]
+        /// ```
+        #[derive(Debug, Clone)]
+        pub struct $hasher {
+            sponge: $crate::hash::keccak::Sponge<$rate>,
+        }
+
+        impl $hasher {
+            /// Create a new hasher with an empty message.
+            #[must_use]
+            pub fn new() -> Self {
+                Self {
+                    sponge: $crate::hash::keccak::Sponge::new($suffix),
+                }
+            }
+
+            /// Append data to the message being hashed.
+            pub fn update(&mut self, data: impl AsRef<[u8]>) {
+                self.sponge.update(data);
+            }
+
+            #[doc = concat!(
+                "Pad the message and return the first `S` bytes of output for everything passed to [`",
+                stringify!($hasher),
+                "::update`].",
+            )]
+            #[must_use]
+            pub fn finalize<const S: usize>(self) -> $crate::hash::Digest<S> {
+                self.sponge.squeeze()
+            }
+
+            /// Pad the message and return a reader, from which any amount of output can be read.
+            #[must_use]
+            pub fn finalize_xof(self) -> $crate::hash::keccak::Reader<$rate> {
+                self.sponge.finalize()
+            }
+
+            /// Check whether the output for the message starts with the expected bytes, in constant time.
+            ///
+            /// As much output is computed as there are expected bytes.
+            ///
+            /// # Examples
+            ///
+            /// Basic usage:
+            ///
+            /// ```
+            #[doc = concat!("let expected = lore::", stringify!($hash), "::<16>(\"abc\");")]
+            ///
+            #[doc = concat!("assert!(lore::", stringify!($hasher), "::verify(\"abc\", &expected));")]
+            #[doc = concat!("assert!(!lore::", stringify!($hasher), "::verify(\"abd\", &expected));")]
+            /// ```
+            #[must_use]
+            pub fn verify(message: impl AsRef<[u8]>, expected: impl AsRef<[u8]>) -> bool {
+                let mut hasher = Self::new();
+                hasher.update(message);
+                let mut reader = hasher.finalize_xof();
+
+                // compare a block at a time, so that the output doesn't have to be kept in memory
+                expected
+                    .as_ref()
+                    .chunks($rate)
+                    .fold(true, |equal, expected| {
+                        let mut output = [0; $rate];
+                        reader.read(&mut output[..expected.len()]);
+                        equal & $crate::hash::constant_time_eq(&output[..expected.len()], expected)
+                    })
+            }
+
+            /// Compute the first `S` bytes of output for everything the reader produces, until it reaches the end.
+            ///
+            #[doc = concat!(
+                "This is the counterpart of [`Hasher::hash_reader`](crate::Hasher::hash_reader), since ",
+                $name,
+                " has no fixed",
+            )]
+            /// output length and so isn't a [`Hasher`](crate::Hasher).
+            ///
+            /// # Errors
+            ///
+            /// Returns the first error of the reader, other than [`std::io::ErrorKind::Interrupted`], which is retried.
+            ///
+            /// # Examples
+            ///
+            /// Basic usage:
+            ///
+            /// ```
+            /// let reader = "abc".as_bytes();
+            ///
+            #[doc = concat!(
+                "assert_eq!(lore::",
+                stringify!($hasher),
+                "::hash_reader::<16>(reader).unwrap(), lore::",
+                stringify!($hash),
+                "::<16>(\"abc\"));",
+            )]
+            /// ```
+            #[cfg(feature = "std")]
+            pub fn hash_reader<const S: usize>(
+                reader: impl std::io::Read,
+            ) -> std::io::Result<$crate::hash::Digest<S>> {
+                $crate::hash::keccak::hash_reader::<$rate, S>($suffix, reader)
+            }
+
+            /// Compute the first `S` bytes of output for a file, which is read in chunks, so it never has to fit in memory.
+            ///
+            /// This is the counterpart of [`Hasher::hash_file`](crate::Hasher::hash_file).
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the file can't be opened or read.
+            ///
+            /// # Examples
+            ///
+            /// Basic usage:
+            ///
+            /// ```
+            #[doc = concat!("let digest = lore::", stringify!($hasher), "::hash_file::<32>(\"Cargo.toml\").unwrap();")]
+            ///
+            #[doc = concat!(
+                "assert_eq!(digest, lore::",
+                stringify!($hash),
+                "::<32>(std::fs::read(\"Cargo.toml\").unwrap()));",
+            )]
+            /// ```
+            #[cfg(feature = "std")]
+            pub fn hash_file<const S: usize>(
+                path: impl AsRef<std::path::Path>,
+            ) -> Result<$crate::hash::Digest<S>, $crate::io::HashFileError> {
+                $crate::hash::keccak::hash_file::<$rate, S>($suffix, path.as_ref())
+            }
+
+            /// Compute the first `S` bytes of output for a file by mapping it into memory, which is usually faster for large
+            /// files.
+            ///
+            /// This is the counterpart of [`Hasher::hash_file_mmap`](crate::Hasher::hash_file_mmap), with the same caveat:
+            /// the file must not be truncated or modified by another process while it is hashed.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the file can't be opened or mapped.
+            ///
+            /// # Examples
+            ///
+            /// Basic usage:
+            ///
+            /// ```
+            #[doc = concat!(
+                "let digest = lore::",
+                stringify!($hasher),
+                "::hash_file_mmap::<32>(\"Cargo.toml\").unwrap();",
+            )]
+            ///
+            #[doc = concat!(
+                "assert_eq!(digest, lore::",
+                stringify!($hasher),
+                "::hash_file::<32>(\"Cargo.toml\").unwrap());",
+            )]
+            /// ```
+            #[cfg(feature = "mmap")]
+            pub fn hash_file_mmap<const S: usize>(
+                path: impl AsRef<std::path::Path>,
+            ) -> Result<$crate::hash::Digest<S>, $crate::io::HashFileError> {
+                $crate::hash::keccak::hash_file_mmap::<$rate, S>($suffix, path.as_ref())
+            }
+        }
+
+        impl Default for $hasher {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+pub(crate) use shake;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::hash::bytes_to_words64_le;
+
+    // the state after xoring the padded SHA3-256 block of "abc" into the zero state
+    #[rustfmt::skip]
+    const ABC_STATE: [u64; 25] = [
+        0x0000000006636261, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000,
+        0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000,
+        0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000,
+        0x0000000000000000, 0x8000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000,
+        0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000,
+    ];
+
+    // the state after applying θ to ABC_STATE
+    #[rustfmt::skip]
+    const ABC_THETA: [u64; 25] = [
+        0x0000000006636260, 0x0000000006636261, 0x8000000000000000, 0x0000000000000000, 0x000000000cc6c4c2,
+        0x0000000000000001, 0x0000000006636261, 0x8000000000000000, 0x0000000000000000, 0x000000000cc6c4c2,
+        0x0000000000000001, 0x0000000006636261, 0x8000000000000000, 0x0000000000000000, 0x000000000cc6c4c2,
+        0x0000000000000001, 0x8000000006636261, 0x8000000000000000, 0x0000000000000000, 0x000000000cc6c4c2,
+        0x0000000000000001, 0x0000000006636261, 0x8000000000000000, 0x0000000000000000, 0x000000000cc6c4c2,
+    ];
+
+    // the state after applying ρ to ABC_THETA
+    #[rustfmt::skip]
+    const ABC_RHO: [u64; 25] = [
+        0x0000000006636260, 0x000000000cc6c4c2, 0x2000000000000000, 0x0000000000000000, 0x0066362610000000,
+        0x0000001000000000, 0x3626100000000066, 0x0000000000000020, 0x0000000000000000, 0x0000cc6c4c200000,
+        0x0000000000000008, 0x000000198d898400, 0x0000040000000000, 0x0000000000000000, 0x6362610000000006,
+        0x0000020000000000, 0x6c4c3000000000cc, 0x0000000000004000, 0x0000000000000000, 0x0000000cc6c4c200,
+        0x0000000000040000, 0x00000000198d8984, 0x1000000000000000, 0x0000000000000000, 0x00000331b1308000,
+    ];
+
+    // the state after applying π to ABC_RHO
+    #[rustfmt::skip]
+    const ABC_PI: [u64; 25] = [
+        0x0000000006636260, 0x3626100000000066, 0x0000040000000000, 0x0000000000000000, 0x00000331b1308000,
+        0x0000000000000000, 0x0000cc6c4c200000, 0x0000000000000008, 0x6c4c3000000000cc, 0x1000000000000000,
+        0x000000000cc6c4c2, 0x0000000000000020, 0x0000000000000000, 0x0000000cc6c4c200, 0x0000000000040000,
+        0x0066362610000000, 0x0000001000000000, 0x000000198d898400, 0x0000000000004000, 0x0000000000000000,
+        0x2000000000000000, 0x0000000000000000, 0x6362610000000006, 0x0000020000000000, 0x00000000198d8984,
+    ];
+
+    // the state after applying χ to ABC_PI
+    #[rustfmt::skip]
+    const ABC_CHI: [u64; 25] = [
+        0x0000040006636260, 0x3626100000000066, 0x00000731b1308000, 0x0000000006436260, 0x36261331b1308006,
+        0x0000000000000008, 0x6c4cfc6c4c2000c4, 0x1000000000000008, 0x6c4c3000000000cc, 0x1000cc6c4c200000,
+        0x000000000cc6c4c2, 0x0000000cc6c4c220, 0x0000000000000000, 0x0000000cca0606c2, 0x0000000000040020,
+        0x0066362f9d898400, 0x0000001000004000, 0x000000198d898400, 0x0066362610004000, 0x0000001000000000,
+        0x4362610000000006, 0x0000020000000000, 0x63626100198d8982, 0x2000020000000000, 0x00000000198d8984,
+    ];
+
+    // the state after applying ι to ABC_CHI, which completes the first round
+    #[rustfmt::skip]
+    const ABC_IOTA: [u64; 25] = [
+        0x0000040006636261, 0x3626100000000066, 0x00000731b1308000, 0x0000000006436260, 0x36261331b1308006,
+        0x0000000000000008, 0x6c4cfc6c4c2000c4, 0x1000000000000008, 0x6c4c3000000000cc, 0x1000cc6c4c200000,
+        0x000000000cc6c4c2, 0x0000000cc6c4c220, 0x0000000000000000, 0x0000000cca0606c2, 0x0000000000040020,
+        0x0066362f9d898400, 0x0000001000004000, 0x000000198d898400, 0x0066362610004000, 0x0000001000000000,
+        0x4362610000000006, 0x0000020000000000, 0x63626100198d8982, 0x2000020000000000, 0x00000000198d8984,
+    ];
+
+    // the state after the full permutation of ABC_STATE
+    #[rustfmt::skip]
+    const ABC_PERMUTED: [u64; 25] = [
+        0xb225e24fa75d983a, 0xbd90d36b2d175c04, 0x5b529d3e6e085f85, 0x3215431145e2bf46, 0xf81092fb22f636d1,
+        0x2097c8aad6603e27, 0x31f2c561623fb1f7, 0x8fdf74f3f204df9c, 0x9f5e838db186c4ac, 0xa80a1c04bc11baa2,
+        0xbf130581a54ef742, 0x3092fccecda9f8bf, 0x1a8b8b2a4af00862, 0x60c4cc90d90ca705, 0x1f24793254851e4e,
+        0x631239dca84621ef, 0x4b3f7866395fe1bd, 0x30bef91bffc67d8a, 0xd272513dd6dd06c9, 0x25a3af600aecb585,
+        0x5fc53e5cb938bec8, 0x02fc22dcacbc3cc1, 0x668094928c4b6cc3, 0xd5c25129ae161a7d, 0xb5d89c9e96e91041,
+    ];
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn keccak_pad() {
+        assert_eq!(vec![0x61, 0x62, 0x63, 0x86], pad("abc", 4, 0x06));
+        assert_eq!(vec![0x1f, 0, 0, 0x80], pad("", 4, 0x1f));
+        assert_eq!(136, pad([0u8; 135], 136, 0x06).len());
+        assert_eq!(272, pad([0u8; 136], 136, 0x06).len());
+
+        let padded = pad("abc", 136, 0x06);
+        let mut state = [0; 25];
+        state[..17].copy_from_slice(&bytes_to_words64_le::<17>(padded));
+        assert_eq!(ABC_STATE, state);
+    }
+
+    #[test]
+    fn keccak_steps() {
+        assert_eq!(ABC_THETA, theta(ABC_STATE));
+        assert_eq!(ABC_RHO, rho(ABC_THETA));
+        assert_eq!(ABC_PI, pi(ABC_RHO));
+        assert_eq!(ABC_CHI, chi(ABC_PI));
+        assert_eq!(ABC_IOTA, iota(ABC_CHI, 0));
+        assert_eq!(ABC_IOTA, round(ABC_STATE, 0));
+    }
+
+    #[test]
+    fn keccak_permute() {
+        assert_eq!(ABC_PERMUTED, permute(ABC_STATE));
+        assert_eq!(0xf1258f7940e1dde7, permute([0; 25])[0]);
+    }
+
+    #[test]
+    fn keccak_reader() {
+        let mut reader = Reader::<16>::new(ABC_STATE);
+        let mut output = [0u8; 40];
+        reader.read(&mut output[..5]);
+        reader.read(&mut output[5..]);
+
+        // the first 16 bytes of each state are squeezed, with a permutation in between
+        let squeezed = [ABC_STATE, permute(ABC_STATE), permute(permute(ABC_STATE))]
+            .iter()
+            .flat_map(|state| state[..2].iter().flat_map(|lane| lane.to_le_bytes()))
+            .collect::<Vec<_>>();
+        assert_eq!(squeezed[..40], output);
+    }
+}
@@ -1,4 +1,10 @@
-use crate::hash::Digest;
+use crate::hash::{Buffer, Digest, Hasher, Padding};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryInto;
 
 /// see RFC1319
 const S: [u8; 256] = [
@@ -20,27 +26,114 @@ const S: [u8; 256] = [
     0x31, 0x44, 0x50, 0xB4, 0x8F, 0xED, 0x1F, 0x1A, 0xDB, 0x99, 0x8D, 0x33, 0x9F, 0x11, 0x83, 0x14,
 ];
 
+#[cfg(feature = "alloc")]
 fn pad(message: impl AsRef<[u8]>) -> Vec<u8> {
     let mut message = message.as_ref().to_vec();
-    let padding_length = 16 - (message.len() % 16);
-    message.append(&mut vec![padding_length as u8; padding_length]);
+    message.extend_from_slice(padding(message.len() as u64).as_ref());
     message
 }
 
+// compute the padding which follows a message of the given length (in bytes)
+fn padding(length: u64) -> Padding<16> {
+    let padding_length = 16 - (length % 16) as usize;
+    let mut padding = Padding::new();
+    padding.extend(core::iter::repeat_n(padding_length as u8, padding_length));
+    padding
+}
+
+#[cfg(feature = "alloc")]
 fn checksum(message: impl AsRef<[u8]>) -> Vec<u8> {
     let mut message = message.as_ref().to_vec();
-    let mut checksum = vec![0u8; 16];
+    let checksum = message
+        .as_chunks::<16>()
+        .0
+        .iter()
+        .fold([0u8; 16], checksum_block);
 
-    let mut last = 0;
-    for block in message.array_chunks::<16>() {
-        for i in 0..16 {
-            checksum[i] ^= S[(block[i] ^ last) as usize];
-            last = checksum[i];
-        }
+    message.extend(checksum);
+    message
+}
+
+// update the running checksum with a single block
+const fn checksum_block(mut checksum: [u8; 16], block: &[u8; 16]) -> [u8; 16] {
+    // the last byte of the checksum carries over between blocks
+    let mut last = checksum[15];
+    let mut i = 0;
+    while i < 16 {
+        checksum[i] ^= S[(block[i] ^ last) as usize];
+        last = checksum[i];
+        i += 1;
     }
 
-    message.append(&mut checksum);
-    message
+    checksum
+}
+
+// process a single 128-bit block of the padded message
+fn compress(buffer: [u8; 48], block: &[u8; 16]) -> [u8; 48] {
+    compress_observed(buffer, block, &mut ())
+}
+
+// process a single 128-bit block of the padded message in a const context, which can't report the rounds to an
+// observer
+const fn compress_const(buffer: [u8; 48], block: &[u8; 16]) -> [u8; 48] {
+    let mut buffer = load_block(buffer, block);
+
+    let mut t = 0;
+    let mut i = 0;
+    while i < 18 {
+        (buffer, t) = round(buffer, t, i);
+        i += 1;
+    }
+
+    buffer
+}
+
+// process a single 128-bit block of the padded message, reporting each round to the observer
+fn compress_observed<O: Observer + ?Sized>(
+    buffer: [u8; 48],
+    block: &[u8; 16],
+    observer: &mut O,
+) -> [u8; 48] {
+    let mut buffer = load_block(buffer, block);
+
+    // do 18 rounds
+    let mut t = 0;
+    for i in 0..18 {
+        (buffer, t) = round(buffer, t, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i.into(),
+                state: buffer.into(),
+            }
+        );
+    }
+
+    buffer
+}
+
+// copy the block into the buffer, followed by its xor with the state
+const fn load_block(mut buffer: [u8; 48], block: &[u8; 16]) -> [u8; 48] {
+    let mut i = 0;
+    while i < 16 {
+        buffer[16 + i] = block[i];
+        buffer[32 + i] = buffer[16 + i] ^ buffer[i];
+        i += 1;
+    }
+
+    buffer
+}
+
+// perform round i over the whole buffer, where t carries over from the previous round
+const fn round(mut buffer: [u8; 48], mut t: u8, i: u8) -> ([u8; 48], u8) {
+    let mut j = 0;
+    while j < 48 {
+        buffer[j] ^= S[t as usize];
+        t = buffer[j];
+        j += 1;
+    }
+
+    (buffer, t.wrapping_add(i))
 }
 
 /// Computes the MD2 hash value (digest) of the input bytes.
@@ -58,45 +151,176 @@ fn checksum(message: impl AsRef<[u8]>) -> Vec<u8> {
 /// assert_eq!(digest.to_string(), "da853b0d3f88d99b30283a69e6ded6bb");
 /// ```
 pub fn hash(msg: impl AsRef<[u8]>) -> Digest<16> {
+    let mut hasher = Md2::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// Computes the MD2 hash value (digest) of the input bytes in a const context, e.g. to embed digests in constants.
+///
+/// This gives the same digest as [`md2`](crate::md2), but is slower at runtime.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// const DIGEST: lore::Digest<16> = lore::md2_const(b"abc");
+///
+/// assert_eq!(DIGEST.to_string(), "da853b0d3f88d99b30283a69e6ded6bb");
+/// ```
+#[must_use]
+pub const fn hash_const(msg: &[u8]) -> Digest<16> {
+    let mut buffer = [0u8; 48];
+    let mut checksum = [0u8; 16];
+
+    // the message is padded with n bytes of value n, up to a whole number of blocks, so there is at least one byte
+    let padding = 16 - msg.len() % 16;
+    let mut index = 0;
+    while index <= msg.len() / 16 {
+        let mut block = [padding as u8; 16];
+        let mut i = 0;
+        while i < 16 && index * 16 + i < msg.len() {
+            block[i] = msg[index * 16 + i];
+            i += 1;
+        }
+
+        checksum = checksum_block(checksum, &block);
+        buffer = compress_const(buffer, &block);
+        index += 1;
+    }
+    buffer = compress_const(buffer, &checksum);
+
+    Digest(*buffer.first_chunk::<16>().unwrap())
+}
+
+/// Computes the MD2 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// The checksum is reported first, and the padded message includes the checksum as its last block.
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::md2("abc", &mut events);
+///
+/// assert_eq!(digest, lore::md2("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(msg: impl AsRef<[u8]>, observer: &mut O) -> Digest<16> {
     let padded = checksum(pad(msg));
-    let buffer = padded
-        .array_chunks::<16>()
-        .fold([0u8; 48], |mut buffer, chunk| {
-            // copy chunk into buffer
-            for i in 0..16 {
-                buffer[16 + i] = chunk[i];
-                buffer[32 + i] = buffer[16 + i] ^ buffer[i];
-            }
+    emit!(
+        observer,
+        Event::Checksum {
+            checksum: padded[padded.len() - 16..].to_vec(),
+        }
+    );
+    let buffer = compress_blocks(
+        &padded,
+        [0u8; 48],
+        observer,
+        |block| block,
+        compress_observed,
+    );
+    let digest = buffer[..16].try_into().unwrap();
 
-            // do 18 rounds
-            let mut t = 0;
-            for i in 0..18 {
-                for b in buffer.iter_mut() {
-                    *b ^= S[t as usize];
-                    t = *b;
-                }
-                t = t.wrapping_add(i);
-            }
+    trace::digest(Digest(digest), observer)
+}
+
+/// A streaming MD2 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, and the checksum is computed as the message is consumed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Md2::new();
+/// hasher.update("a");
+/// hasher.update("bc");
+///
+/// assert_eq!(hasher.finalize(), lore::md2("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Md2 {
+    state: [u8; 48],
+    checksum: [u8; 16],
+    buffer: Buffer<16>,
+}
+
+impl Md2 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: [0; 48],
+            checksum: [0; 16],
+            buffer: Buffer::new(),
+        }
+    }
 
-            buffer
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self {
+            state,
+            checksum,
+            buffer,
+        } = self;
+        buffer.update(data.as_ref(), |block| {
+            *state = compress(*state, block);
+            *checksum = checksum_block(*checksum, block);
         });
-    let digest = *buffer.array_chunks::<16>().next().unwrap();
+    }
+
+    /// Pad the message, append the checksum and return the digest of everything passed to [`Md2::update`].
+    #[must_use]
+    pub fn finalize(mut self) -> Digest<16> {
+        self.update(padding(self.buffer.length()));
+        let state = compress(self.state, &self.checksum);
+        let digest = state[..16].try_into().unwrap();
+
+        Digest(digest)
+    }
+}
+
+impl Default for Md2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Md2 {
+    const BLOCK_SIZE: usize = 16;
+    const OUTPUT_SIZE: usize = 16;
+
+    type Output = Digest<16>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
 
-    Digest(digest)
+    fn finalize(self) -> Digest<16> {
+        self.finalize()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn md2_pad() {
         // assert that padding length is always correct
-        assert!(pad([]).len() % 16 == 0);
-        assert!(pad([0u8]).len() % 16 == 0);
-        assert!(pad([0u8; 15]).len() % 16 == 0);
-        assert!(pad([0u8; 16]).len() % 16 == 0);
-        assert!(pad([0u8; 476]).len() % 16 == 0);
+        assert_eq!(pad([]).len() % 16, 0);
+        assert_eq!(pad([0u8]).len() % 16, 0);
+        assert_eq!(pad([0u8; 15]).len() % 16, 0);
+        assert_eq!(pad([0u8; 16]).len() % 16, 0);
+        assert_eq!(pad([0u8; 476]).len() % 16, 0);
 
         // check some simple cases
         assert_eq!(vec![16u8; 16], pad([]));
@@ -106,6 +330,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn md2_checksum() {
         assert_eq!(
@@ -143,4 +368,32 @@ mod tests {
             hash(b"The quick brown fox jumps over the lazy dog").to_string()
         );
     }
+
+    #[test]
+    fn md2_const() {
+        const DIGEST: Digest<16> = hash_const(b"abc");
+        assert_eq!("da853b0d3f88d99b30283a69e6ded6bb", DIGEST.to_string());
+
+        // every length around the block boundaries, where the padding spills into another block
+        let message: [u8; 200] = core::array::from_fn(|i| i as u8);
+        for length in 0..=message.len() {
+            assert_eq!(hash(&message[..length]), hash_const(&message[..length]));
+        }
+    }
+
+    #[test]
+    fn md2_streaming() {
+        let message = [0x42u8; 100];
+        for split in [0, 1, 15, 16, 17, 32, 99, 100] {
+            let mut hasher = Md2::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+
+        let mut hasher = Md2::new();
+        message.chunks(7).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hash(message), hasher.finalize());
+        assert_eq!(hash(""), Md2::new().finalize());
+    }
 }
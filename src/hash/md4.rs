@@ -1,62 +1,90 @@
-use crate::hash::{bytes_to_words_le, words_to_bytes_le, Digest};
+use crate::hash::{
+    bytes_to_words_le, bytes_to_words_le_const, padded_block, padded_blocks, words_to_bytes_le,
+    words_to_bytes_le_const, Buffer, Digest, Hasher, Padding,
+};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 // based on RFC1320
-const A: u32 = 0x67452301;
-const B: u32 = 0xefcdab89;
-const C: u32 = 0x98badcfe;
-const D: u32 = 0x10325476;
+pub const A: u32 = 0x67452301;
+pub const B: u32 = 0xefcdab89;
+pub const C: u32 = 0x98badcfe;
+pub const D: u32 = 0x10325476;
 
 // additional constants for round 1, 2 & 3
 const C1: u32 = 0;
-const C2: u32 = 0x5a827999;
-const C3: u32 = 0x6ed9eba1;
+pub const C2: u32 = 0x5a827999;
+pub const C3: u32 = 0x6ed9eba1;
 
 // shifts & indices for each step
-const S: [u32; 48] = [
+pub const S: [u32; 48] = [
     3, 7, 11, 19, 3, 7, 11, 19, 3, 7, 11, 19, 3, 7, 11, 19, 3, 5, 9, 13, 3, 5, 9, 13, 3, 5, 9, 13,
     3, 5, 9, 13, 3, 9, 11, 15, 3, 9, 11, 15, 3, 9, 11, 15, 3, 9, 11, 15,
 ];
-const W: [usize; 48] = [
+pub const W: [usize; 48] = [
     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14,
     3, 7, 11, 15, 0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15,
 ];
 
 // round functions
-const F: fn(u32, u32, u32) -> u32 = |x, y, z| (x & y) | (!x & z);
-const G: fn(u32, u32, u32) -> u32 = |x: u32, y: u32, z: u32| (x & y) | (x & z) | (y & z);
-const H: fn(u32, u32, u32) -> u32 = |x: u32, y: u32, z: u32| x ^ y ^ z;
+pub const fn f(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+pub const fn g(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (x & z) | (y & z)
+}
+pub const fn h(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
 
 // pad the message to next 512-bit interval
+#[cfg(feature = "alloc")]
 pub fn pad(message: impl AsRef<[u8]>) -> Vec<u8> {
     let mut message = message.as_ref().to_vec();
-    let message_length = message.len().wrapping_mul(8) as u64;
+    message.extend_from_slice(padding(message.len() as u64).as_ref());
+
+    message
+}
+
+// compute the padding which follows a message of the given length (in bytes)
+pub fn padding(length: u64) -> Padding<72> {
+    padding_bits(length.wrapping_mul(8), 0)
+}
 
-    // add 1 bit (le)
-    message.push(0x80);
+// compute the padding which follows a message of the given length (in bits), starting with the last byte of the
+// message if only some of its bits are used
+pub fn padding_bits(bits: u64, last: u8) -> Padding<72> {
+    // add 1 bit right after the used (most significant) bits of the last byte
+    let used = (bits % 8) as u32;
+    let mut padding = Padding::new();
+    padding.push((last & !(0xff >> used)) | (0x80 >> used));
 
     // add 0 bits until length in bits is congruent to 448 mod 512
-    while (message.len()) % 64 != 56 {
-        message.push(0u8);
+    while ((bits / 8) as usize).wrapping_add(padding.len()) % 64 != 56 {
+        padding.push(0);
     }
 
     // append message length (64 bits)
-    message.extend(message_length.to_le_bytes());
+    padding.extend(bits.to_le_bytes());
 
-    message
+    padding
 }
 
 // compute an invidiual step in the md4 algorithm
-fn step([mut a, b, c, d]: [u32; 4], words: &[u32], i: usize) -> [u32; 4] {
+const fn step([mut a, b, c, d]: [u32; 4], words: &[u32], i: usize) -> [u32; 4] {
     // choose function and constant based on which round is currently active
     let (f, k) = match i {
-        0..=15 => (F, C1),
-        16..=31 => (G, C2),
-        32..=47 => (H, C3),
+        0..=15 => (f(b, c, d), C1),
+        16..=31 => (g(b, c, d), C2),
+        32..=47 => (h(b, c, d), C3),
         _ => panic!("This function shouldn't be called using an index outside 0..48"),
     };
 
     // main operation
-    a = f(b, c, d)
+    a = f
         .wrapping_add(a)
         .wrapping_add(words[W[i]])
         .wrapping_add(k)
@@ -80,37 +108,291 @@ fn step([mut a, b, c, d]: [u32; 4], words: &[u32], i: usize) -> [u32; 4] {
 /// assert_eq!(digest.to_string(), "a448017aaf21d8525fc10ae87aa6729d");
 /// ```
 pub fn hash(message: impl AsRef<[u8]>) -> Digest<16> {
+    let mut hasher = Md4::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the MD4 hash value (digest) of a message whose length in bits is not necessarily a multiple of 8.
+///
+/// The message consists of the first `bits` bits of the input bytes, most significant bit first, and any bits after
+/// them are ignored.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// // the bits 10011000 1, which are not a whole number of bytes
+/// let digest = lore::md4_bits([0x98, 0x80], 9);
+///
+/// assert_ne!(digest, lore::md4([0x98, 0x80]));
+/// assert_eq!(lore::md4_bits("abc", 24), lore::md4("abc"));
+/// ```
+pub fn hash_bits(message: impl AsRef<[u8]>, bits: u64) -> Digest<16> {
+    let message = message.as_ref();
+    assert!(
+        bits <= (message.len() as u64).wrapping_mul(8),
+        "The message must have at least as many bits as its length"
+    );
+
+    let whole = (bits / 8) as usize;
+    let mut hasher = Md4::new();
+    hasher.update(&message[..whole]);
+    hasher.finalize_bits(message.get(whole).copied().unwrap_or(0), (bits % 8) as u32)
+}
+
+/// Computes the MD4 hash value (digest) of the input bytes in a const context, e.g. to embed digests in constants.
+///
+/// This gives the same digest as [`md4`](crate::md4), but is slower at runtime.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// const DIGEST: lore::Digest<16> = lore::md4_const(b"abc");
+///
+/// assert_eq!(DIGEST, lore::md4("abc"));
+/// ```
+#[must_use]
+pub const fn hash_const(message: &[u8]) -> Digest<16> {
+    let length = (message.len() as u64).wrapping_mul(8).to_le_bytes();
+    let mut buffer = [A, B, C, D];
+    let mut index = 0;
+    while index < padded_blocks(message.len()) {
+        buffer = compress_const(buffer, &padded_block(message, index, length));
+        index += 1;
+    }
+
+    Digest(words_to_bytes_le_const(&buffer))
+}
+
+/// Computes the MD4 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::md4("abc", &mut events);
+///
+/// assert_eq!(digest, lore::md4("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<16> {
     let padded = pad(message);
-    let buffer = padded.array_chunks::<64>().map(bytes_to_words_le).fold(
+    let buffer = compress_blocks(
+        &padded,
         [A, B, C, D],
-        |[a, b, c, d], words| {
-            // perform rounds on this chunk of data
-            let mut state = [a, b, c, d];
-            for i in 0..48 {
-                state = step(state, &words, i);
-                state.rotate_right(1);
+        observer,
+        bytes_to_words_le::<16>,
+        |buffer, block, observer| compress_observed(buffer, block, 48, observer),
+    );
+
+    trace::digest(Digest(words_to_bytes_le(buffer)), observer)
+}
+
+// process a single 512-bit block of the padded message
+pub fn compress(buffer: [u32; 4], block: &[u8; 64]) -> [u32; 4] {
+    compress_observed(buffer, block, 48, &mut ())
+}
+
+// process a single 512-bit block of the padded message in a const context, which can't report the steps to an
+// observer
+const fn compress_const([a, b, c, d]: [u32; 4], block: &[u8; 64]) -> [u32; 4] {
+    let words: [u32; 16] = bytes_to_words_le_const(block);
+
+    let mut state = [a, b, c, d];
+    let mut i = 0;
+    while i < 48 {
+        let [a, b, c, d] = step(state, &words, i);
+        state = [d, a, b, c];
+        i += 1;
+    }
+
+    [
+        a.wrapping_add(state[0]),
+        b.wrapping_add(state[1]),
+        c.wrapping_add(state[2]),
+        d.wrapping_add(state[3]),
+    ]
+}
+
+// process a single 512-bit block of the padded message with the given number of steps, reporting each step to the
+// observer
+pub fn compress_observed<O: Observer + ?Sized>(
+    [a, b, c, d]: [u32; 4],
+    block: &[u8; 64],
+    steps: usize,
+    observer: &mut O,
+) -> [u32; 4] {
+    let words: [u32; 16] = bytes_to_words_le(block);
+
+    // perform rounds on this chunk of data
+    let mut state = [a, b, c, d];
+    for i in 0..steps {
+        state = step(state, &words, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: state.into(),
             }
+        );
+        state.rotate_right(1);
+    }
 
-            [
-                a.wrapping_add(state[0]),
-                b.wrapping_add(state[1]),
-                c.wrapping_add(state[2]),
-                d.wrapping_add(state[3]),
-            ]
-        },
+    let buffer = [
+        a.wrapping_add(state[0]),
+        b.wrapping_add(state[1]),
+        c.wrapping_add(state[2]),
+        d.wrapping_add(state[3]),
+    ];
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: [a, b, c, d].into(),
+            working: state.into(),
+            next: buffer.into(),
+        }
     );
-    let digest = *words_to_bytes_le(buffer)
-        .array_chunks::<16>()
-        .next()
-        .unwrap();
 
-    Digest(digest)
+    buffer
+}
+
+/// A streaming MD4 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Md4::new();
+/// hasher.update("ab");
+/// hasher.update("c");
+///
+/// assert_eq!(hasher.finalize(), lore::md4("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Md4 {
+    state: [u32; 4],
+    buffer: Buffer<64>,
+}
+
+impl Md4 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: [A, B, C, D],
+            buffer: Buffer::new(),
+        }
+    }
+
+    /// Continue hashing after a message of which only the digest and the length (in bytes) are known.
+    ///
+    /// The hasher is left in the same state as after hashing the message and its padding, so the digest covers the
+    /// message, its padding and everything passed to [`Md4::update`] afterwards.
+    #[cfg_attr(
+        feature = "alloc",
+        doc = "This is the basis of length extension attacks, see the [`length_extension`](crate::length_extension) module."
+    )]
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let mut hasher = lore::Md4::resume(&lore::md4("abc"), 3);
+    /// hasher.update("def");
+    ///
+    /// let mut message = b"abc".to_vec();
+    /// message.push(0x80);
+    /// message.resize(56, 0);
+    /// message.extend(24u64.to_le_bytes());
+    /// message.extend(b"def");
+    ///
+    /// assert_eq!(hasher.finalize(), lore::md4(message));
+    /// ```
+    #[must_use]
+    pub fn resume(digest: &Digest<16>, length: u64) -> Self {
+        let padded = length.wrapping_add(padding(length).len() as u64);
+
+        Self {
+            state: bytes_to_words_le(digest),
+            buffer: Buffer::with_length(padded),
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer } = self;
+        buffer.update(data.as_ref(), |block| *state = compress(*state, block));
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Md4::update`].
+    #[must_use]
+    pub fn finalize(self) -> Digest<16> {
+        self.finalize_bits(0, 0)
+    }
+
+    /// Append the `bits` most significant bits of `last` to the message, then pad it and return the digest.
+    ///
+    /// This finishes messages whose length is not a multiple of 8 bits, whose last byte is only partially used.
+    /// Panics if `bits` is not less than 8.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let mut hasher = lore::Md4::new();
+    /// hasher.update("ab");
+    ///
+    /// assert_eq!(hasher.finalize_bits(0x98, 5), lore::md4_bits([b'a', b'b', 0x98], 21));
+    /// ```
+    #[must_use]
+    pub fn finalize_bits(mut self, last: u8, bits: u32) -> Digest<16> {
+        assert!(bits < 8, "The last byte must have fewer than 8 bits");
+        let length = self.buffer.length().wrapping_mul(8);
+        self.update(padding_bits(length.wrapping_add(u64::from(bits)), last));
+
+        Digest(words_to_bytes_le(self.state))
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Md4 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 16;
+
+    type Output = Digest<16>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<16> {
+        self.finalize()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn md4_pad() {
         assert_eq!(pad([1u8]).len() % 64, 0);
@@ -133,6 +415,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn md4_padding_bits() {
+        // the padding of the bits 11111111 111, after the first byte
+        let bits = padding_bits(11, 0xff);
+
+        assert_eq!(63, bits.len());
+        assert_eq!([0xf0, 0x00], bits.as_ref()[..2]);
+        assert_eq!(11u64.to_le_bytes(), bits.as_ref()[55..]);
+        assert_eq!(padding(3).as_ref(), padding_bits(24, b'd').as_ref());
+    }
+
     #[test]
     fn md4_hash() {
         assert_eq!(
@@ -174,6 +467,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn md4_const() {
+        const DIGEST: Digest<16> = hash_const(b"abc");
+        assert_eq!("a448017aaf21d8525fc10ae87aa6729d", DIGEST.to_string());
+
+        // every length around the block boundaries, where the padding spills into another block
+        let message: [u8; 200] = core::array::from_fn(|i| i as u8);
+        for length in 0..=message.len() {
+            assert_eq!(hash(&message[..length]), hash_const(&message[..length]));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn md4_padding() {
+        assert_eq!(&pad([1u8; 55])[55..], padding(55).as_ref());
+        assert_eq!(&pad([1u8; 56])[56..], padding(56).as_ref());
+        assert_eq!(&pad([1u8; 4472])[4472..], padding(4472).as_ref());
+    }
+
+    #[test]
+    fn md4_streaming() {
+        let message = [0x5au8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Md4::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+
+        let mut hasher = Md4::new();
+        message.chunks(7).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hash(message), hasher.finalize());
+        assert_eq!(hash(""), Md4::new().finalize());
+    }
+
     #[test]
     fn md4_steps() {
         let expected: [[u32; 4]; 48] = [
@@ -1,12 +1,22 @@
-use crate::hash::{bytes_to_words_le, md4::pad, words_to_bytes_le, Digest};
+#[cfg(feature = "alloc")]
+use crate::hash::md4::pad;
+use crate::hash::{
+    bytes_to_words_le, bytes_to_words_le_const,
+    md4::{padding, padding_bits},
+    padded_block, padded_blocks, words_to_bytes_le, words_to_bytes_le_const, Buffer, Digest,
+    Hasher,
+};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
 
 // based on RFC1321
-const A: u32 = 0x67452301;
-const B: u32 = 0xefcdab89;
-const C: u32 = 0x98badcfe;
-const D: u32 = 0x10325476;
+pub const A: u32 = 0x67452301;
+pub const B: u32 = 0xefcdab89;
+pub const C: u32 = 0x98badcfe;
+pub const D: u32 = 0x10325476;
 
-const K: [u32; 64] = [
+pub const K: [u32; 64] = [
     0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
     0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
     0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
@@ -18,33 +28,44 @@ const K: [u32; 64] = [
 ];
 
 // shifts & indices for each step
-const S: [u32; 64] = [
+pub const S: [u32; 64] = [
     7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
     14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
     21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
 ];
-const W: [usize; 64] = [
+pub const W: [usize; 64] = [
     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 1, 6, 11, 0, 5, 10, 15, 4, 9, 14, 3, 8,
     13, 2, 7, 12, 5, 8, 11, 14, 1, 4, 7, 10, 13, 0, 3, 6, 9, 12, 15, 2, 0, 7, 14, 5, 12, 3, 10, 1,
     8, 15, 6, 13, 4, 11, 2, 9,
 ];
 
 // round functions
-const F: fn(u32, u32, u32) -> u32 = |x: u32, y: u32, z: u32| (x & y) | (!x & z);
-const G: fn(u32, u32, u32) -> u32 = |x: u32, y: u32, z: u32| (x & z) | (y & !z);
-const H: fn(u32, u32, u32) -> u32 = |x: u32, y: u32, z: u32| x ^ y ^ z;
-const I: fn(u32, u32, u32) -> u32 = |x: u32, y: u32, z: u32| y ^ (x | !z);
-
-fn step([mut a, b, c, d]: [u32; 4], words: &[u32], index: usize) -> [u32; 4] {
-    let round_function = match index {
-        0..=15 => F,
-        16..=31 => G,
-        32..=47 => H,
-        48..=63 => I,
-        _ => panic!("This function shouldn't be called using an index outside 0..48"),
-    };
-
-    a = round_function(b, c, d)
+const fn f(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+const fn g(x: u32, y: u32, z: u32) -> u32 {
+    (x & z) | (y & !z)
+}
+const fn h(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+const fn i(x: u32, y: u32, z: u32) -> u32 {
+    y ^ (x | !z)
+}
+
+// the round function of the step with the given index
+pub const fn round_function(index: usize, x: u32, y: u32, z: u32) -> u32 {
+    match index {
+        0..=15 => f(x, y, z),
+        16..=31 => g(x, y, z),
+        32..=47 => h(x, y, z),
+        48..=63 => i(x, y, z),
+        _ => panic!("This function shouldn't be called using an index outside 0..64"),
+    }
+}
+
+const fn step([mut a, b, c, d]: [u32; 4], words: &[u32], index: usize) -> [u32; 4] {
+    a = round_function(index, b, c, d)
         .wrapping_add(a)
         .wrapping_add(words[W[index]])
         .wrapping_add(K[index])
@@ -70,40 +91,321 @@ fn step([mut a, b, c, d]: [u32; 4], words: &[u32], index: usize) -> [u32; 4] {
 ///
 /// ```
 pub fn hash(message: impl AsRef<[u8]>) -> Digest<16> {
+    let mut hasher = Md5::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the MD5 hash value (digest) of a message whose length in bits is not necessarily a multiple of 8.
+///
+/// The message consists of the first `bits` bits of the input bytes, most significant bit first, and any bits after
+/// them are ignored.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// // the five bits 10011
+/// let digest = lore::md5_bits([0x98], 5);
+///
+/// assert_eq!(digest.to_string(), "e0ce190aabc2e4aa602238ca5b81dd6d");
+/// assert_eq!(lore::md5_bits("abc", 24), lore::md5("abc"));
+/// ```
+pub fn hash_bits(message: impl AsRef<[u8]>, bits: u64) -> Digest<16> {
+    let message = message.as_ref();
+    assert!(
+        bits <= (message.len() as u64).wrapping_mul(8),
+        "The message must have at least as many bits as its length"
+    );
+
+    let whole = (bits / 8) as usize;
+    let mut hasher = Md5::new();
+    hasher.update(&message[..whole]);
+    hasher.finalize_bits(message.get(whole).copied().unwrap_or(0), (bits % 8) as u32)
+}
+
+/// Computes the MD5 hash value (digest) of the input bytes in a const context, e.g. to embed digests in constants.
+///
+/// This gives the same digest as [`md5`](crate::md5), but is slower at runtime.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// const DIGEST: lore::Digest<16> = lore::md5_const(b"lol");
+///
+/// assert_eq!(DIGEST.to_string(), "9cdfb439c7876e703e307864c9167a15");
+/// ```
+#[must_use]
+pub const fn hash_const(message: &[u8]) -> Digest<16> {
+    let length = (message.len() as u64).wrapping_mul(8).to_le_bytes();
+    let mut buffer = [A, B, C, D];
+    let mut index = 0;
+    while index < padded_blocks(message.len()) {
+        buffer = compress_const(buffer, &padded_block(message, index, length));
+        index += 1;
+    }
+
+    Digest(words_to_bytes_le_const(&buffer))
+}
+
+/// Computes the MD5 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use lore::trace::Event;
+///
+/// let mut events = Vec::new();
+/// let digest = lore::trace::md5("lol", &mut events);
+///
+/// assert_eq!(digest, lore::md5("lol"));
+/// assert_eq!(events.last(), Some(&Event::Digest { bytes: digest.as_ref().to_vec() }));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<16> {
     // the padding function for MD5 is exactly equivalent to the MD4 version, so we reuse it.
     let padded = pad(message);
-    let buffer = padded.array_chunks::<64>().map(bytes_to_words_le).fold(
+    let buffer = compress_blocks(
+        &padded,
         [A, B, C, D],
-        |[a, b, c, d], words| {
-            // initialize state
-            let mut state = [a, b, c, d];
-
-            for i in 0..64 {
-                state = step(state, &words, i);
-                println!("{state:08x?}");
-                state.rotate_right(1);
+        observer,
+        bytes_to_words_le::<16>,
+        |buffer, block, observer| compress_observed(buffer, block, 64, observer),
+    );
+    let digest = words_to_bytes_le(buffer);
+
+    trace::digest(Digest(digest), observer)
+}
+
+// process a single 512-bit block of the padded message
+pub fn compress(buffer: [u32; 4], block: &[u8; 64]) -> [u32; 4] {
+    compress_observed(buffer, block, 64, &mut ())
+}
+
+// process a single 512-bit block of the padded message in a const context, which can't report the steps to an
+// observer
+const fn compress_const([a, b, c, d]: [u32; 4], block: &[u8; 64]) -> [u32; 4] {
+    let words: [u32; 16] = bytes_to_words_le_const(block);
+
+    let mut state = [a, b, c, d];
+    let mut i = 0;
+    while i < 64 {
+        let [a, b, c, d] = step(state, &words, i);
+        state = [d, a, b, c];
+        i += 1;
+    }
+
+    [
+        a.wrapping_add(state[0]),
+        b.wrapping_add(state[1]),
+        c.wrapping_add(state[2]),
+        d.wrapping_add(state[3]),
+    ]
+}
+
+// process a single 512-bit block of the padded message with the given number of steps, reporting each step to the
+// observer
+pub fn compress_observed<O: Observer + ?Sized>(
+    [a, b, c, d]: [u32; 4],
+    block: &[u8; 64],
+    steps: usize,
+    observer: &mut O,
+) -> [u32; 4] {
+    let words: [u32; 16] = bytes_to_words_le(block);
+
+    // initialize state
+    let mut state = [a, b, c, d];
+
+    for i in 0..steps {
+        state = step(state, &words, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: state.into(),
             }
+        );
+        state.rotate_right(1);
+    }
 
-            // add the computed state to the buffer
-            [
-                a.wrapping_add(state[0]),
-                b.wrapping_add(state[1]),
-                c.wrapping_add(state[2]),
-                d.wrapping_add(state[3]),
-            ]
-        },
+    // add the computed state to the buffer
+    let buffer = [
+        a.wrapping_add(state[0]),
+        b.wrapping_add(state[1]),
+        c.wrapping_add(state[2]),
+        d.wrapping_add(state[3]),
+    ];
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: [a, b, c, d].into(),
+            working: state.into(),
+            next: buffer.into(),
+        }
     );
-    let digest = *words_to_bytes_le(buffer)
-        .array_chunks::<16>()
-        .next()
-        .unwrap();
 
-    Digest(digest)
+    buffer
+}
+
+/// A streaming MD5 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Md5::new();
+/// hasher.update("lol");
+/// hasher.update(" xd");
+///
+/// assert_eq!(hasher.finalize(), lore::md5("lol xd"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Md5 {
+    state: [u32; 4],
+    buffer: Buffer<64>,
+}
+
+impl Md5 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: [A, B, C, D],
+            buffer: Buffer::new(),
+        }
+    }
+
+    /// Continue hashing after a message of which only the digest and the length (in bytes) are known.
+    ///
+    /// The hasher is left in the same state as after hashing the message and its padding, so the digest covers the
+    /// message, its padding and everything passed to [`Md5::update`] afterwards.
+    #[cfg_attr(
+        feature = "alloc",
+        doc = "This is the basis of length extension attacks, see the [`length_extension`](crate::length_extension) module."
+    )]
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let mut hasher = lore::Md5::resume(&lore::md5("abc"), 3);
+    /// hasher.update("def");
+    ///
+    /// let mut message = b"abc".to_vec();
+    /// message.push(0x80);
+    /// message.resize(56, 0);
+    /// message.extend(24u64.to_le_bytes());
+    /// message.extend(b"def");
+    ///
+    /// assert_eq!(hasher.finalize(), lore::md5(message));
+    /// ```
+    #[must_use]
+    pub fn resume(digest: &Digest<16>, length: u64) -> Self {
+        let padded = length.wrapping_add(padding(length).len() as u64);
+
+        Self {
+            state: bytes_to_words_le(digest),
+            buffer: Buffer::with_length(padded),
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer } = self;
+        buffer.update(data.as_ref(), |block| *state = compress(*state, block));
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Md5::update`].
+    #[must_use]
+    pub fn finalize(self) -> Digest<16> {
+        self.finalize_bits(0, 0)
+    }
+
+    /// Append the `bits` most significant bits of `last` to the message, then pad it and return the digest.
+    ///
+    /// This finishes messages whose length is not a multiple of 8 bits, whose last byte is only partially used.
+    /// Panics if `bits` is not less than 8.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let mut hasher = lore::Md5::new();
+    /// hasher.update("ab");
+    ///
+    /// assert_eq!(hasher.finalize_bits(0x98, 5), lore::md5_bits([b'a', b'b', 0x98], 21));
+    /// ```
+    #[must_use]
+    pub fn finalize_bits(mut self, last: u8, bits: u32) -> Digest<16> {
+        assert!(bits < 8, "The last byte must have fewer than 8 bits");
+        let length = self.buffer.length().wrapping_mul(8);
+        self.update(padding_bits(length.wrapping_add(u64::from(bits)), last));
+        let digest = words_to_bytes_le(self.state);
+
+        Digest(digest)
+    }
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Md5 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 16;
+
+    type Output = Digest<16>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<16> {
+        self.finalize()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::trace::Words;
+
+    #[test]
+    fn md5_hash_bits() {
+        let message: Vec<u8> = (0x41..0x87).collect();
+
+        assert_eq!(
+            "e0ce190aabc2e4aa602238ca5b81dd6d",
+            hash_bits([0x98], 5).to_string()
+        );
+        assert_eq!(
+            "b834dddace97d125db66708cd389b891",
+            hash_bits(&message, 555).to_string()
+        );
+        assert_eq!(hash(&message), hash_bits(&message, 560));
+
+        let mut hasher = Md5::new();
+        hasher.update(&message[..69]);
+        assert_eq!(
+            hash_bits(&message, 555),
+            hasher.finalize_bits(message[69], 3)
+        );
+    }
 
     #[test]
     fn md5_hash() {
@@ -119,6 +421,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn md5_const() {
+        const DIGEST: Digest<16> = hash_const(b"abc");
+        assert_eq!("900150983cd24fb0d6963f7d28e17f72", DIGEST.to_string());
+
+        // every length around the block boundaries, where the padding spills into another block
+        let message: [u8; 200] = core::array::from_fn(|i| i as u8);
+        for length in 0..=message.len() {
+            assert_eq!(hash(&message[..length]), hash_const(&message[..length]));
+        }
+    }
+
+    #[test]
+    fn md5_streaming() {
+        let message = [0xa5u8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Md5::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+
+        let mut hasher = Md5::new();
+        message.chunks(7).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hash(message), hasher.finalize());
+        assert_eq!(hash(""), Md5::new().finalize());
+    }
+
+    #[cfg(feature = "alloc")]
     #[test]
     fn md5_steps() {
         let expected: [[u32; 4]; 64] = [
@@ -201,5 +532,17 @@ mod tests {
             assert_eq!(expected[i], state);
             state.rotate_right(1);
         }
+
+        // tracing reports the same states
+        let mut events = Vec::new();
+        trace("tihi xd", &mut events);
+        let steps: Vec<_> = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Step { state, .. } => Some(state),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(expected.map(Words::from).to_vec(), steps);
     }
 }
@@ -0,0 +1,408 @@
+#[cfg(feature = "alloc")]
+use crate::hash::md4::pad;
+use crate::hash::{
+    bytes_to_words_le,
+    md4::padding,
+    ripemd160::{F, R, R_PRIME, S, S_PRIME},
+    words_to_bytes_le, Buffer, Digest, Hasher,
+};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+
+// based on "RIPEMD-160: A strengthened version of RIPEMD" by Dobbertin, Bosselaers and Preneel
+const IV: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+// additional constants for each round of the left and the right line
+const K: [u32; 4] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc];
+const K_PRIME: [u32; 4] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x00000000];
+
+// compute an individual step of the left line, which uses the first four rounds of RIPEMD-160
+pub fn left_step([a, b, c, d]: [u32; 4], words: &[u32], i: usize) -> [u32; 4] {
+    let t = F[i / 16](b, c, d)
+        .wrapping_add(a)
+        .wrapping_add(words[R[i]])
+        .wrapping_add(K[i / 16])
+        .rotate_left(S[i]);
+
+    [d, t, b, c]
+}
+
+// compute an individual step of the right line
+pub fn right_step([a, b, c, d]: [u32; 4], words: &[u32], i: usize) -> [u32; 4] {
+    let t = F[3 - i / 16](b, c, d)
+        .wrapping_add(a)
+        .wrapping_add(words[R_PRIME[i]])
+        .wrapping_add(K_PRIME[i / 16])
+        .rotate_left(S_PRIME[i]);
+
+    [d, t, b, c]
+}
+
+/// Computes the RIPEMD-128 hash value (digest) of the input bytes.
+///
+/// Returns a 16-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let input = "abc";
+/// let digest = lore::ripemd128(input);
+///
+/// assert_eq!(
+///     digest.to_string(),
+///     "c14a12199c66e4ba84636b0f69144c77"
+/// );
+/// ```
+pub fn hash(message: impl AsRef<[u8]>) -> Digest<16> {
+    let mut hasher = Ripemd128::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the RIPEMD-128 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::ripemd128("abc", &mut events);
+///
+/// assert_eq!(digest, lore::ripemd128("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<16> {
+    let padded = pad(message);
+    let state = compress_blocks(
+        &padded,
+        IV,
+        observer,
+        bytes_to_words_le::<16>,
+        compress_observed,
+    );
+    let digest = words_to_bytes_le(state);
+
+    trace::digest(Digest(digest), observer)
+}
+
+// process a single 512-bit block of the padded message, with both lines in parallel
+fn compress(h: [u32; 4], block: &[u8; 64]) -> [u32; 4] {
+    compress_observed(h, block, &mut ())
+}
+
+// process a single 512-bit block of the padded message, with both lines in parallel, reporting each step to the observer
+fn compress_observed<O: Observer + ?Sized>(
+    h: [u32; 4],
+    block: &[u8; 64],
+    observer: &mut O,
+) -> [u32; 4] {
+    let words: [u32; 16] = bytes_to_words_le(block);
+    let (l, r) = (0..64).fold((h, h), |(l, r), i| {
+        let (l, r) = (left_step(l, &words, i), right_step(r, &words, i));
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: [l, r].concat().into(),
+            }
+        );
+        (l, r)
+    });
+
+    // combine both lines with the previous state
+    let state = [
+        h[1].wrapping_add(l[2]).wrapping_add(r[3]),
+        h[2].wrapping_add(l[3]).wrapping_add(r[0]),
+        h[3].wrapping_add(l[0]).wrapping_add(r[1]),
+        h[0].wrapping_add(l[1]).wrapping_add(r[2]),
+    ];
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: h.into(),
+            working: [l, r].concat().into(),
+            next: state.into(),
+        }
+    );
+
+    state
+}
+
+/// A streaming RIPEMD-128 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Ripemd128::new();
+/// hasher.update("ab");
+/// hasher.update("c");
+///
+/// assert_eq!(hasher.finalize(), lore::ripemd128("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ripemd128 {
+    state: [u32; 4],
+    buffer: Buffer<64>,
+}
+
+impl Ripemd128 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: IV,
+            buffer: Buffer::new(),
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer } = self;
+        buffer.update(data.as_ref(), |block| *state = compress(*state, block));
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Ripemd128::update`].
+    #[must_use]
+    pub fn finalize(mut self) -> Digest<16> {
+        self.update(padding(self.buffer.length()));
+        let digest = words_to_bytes_le(self.state);
+
+        Digest(digest)
+    }
+}
+
+impl Default for Ripemd128 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Ripemd128 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 16;
+
+    type Output = Digest<16>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<16> {
+        self.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the states of the left line after each step, for the single block of "abc"
+    #[cfg(feature = "alloc")]
+    #[rustfmt::skip]
+    const ABC_LEFT_STEPS: [[u32; 4]; 64] = [
+        [0x10325476, 0x6d431a77, 0xefcdab89, 0x98badcfe],
+        [0x98badcfe, 0xb05d8a99, 0x6d431a77, 0xefcdab89],
+        [0xefcdab89, 0x0c32e5c7, 0xb05d8a99, 0x6d431a77],
+        [0x6d431a77, 0xa20b2c0f, 0x0c32e5c7, 0xb05d8a99],
+        [0xb05d8a99, 0x74ebb911, 0xa20b2c0f, 0x0c32e5c7],
+        [0x0c32e5c7, 0x2ffb728b, 0x74ebb911, 0xa20b2c0f],
+        [0xa20b2c0f, 0xa766ae02, 0x2ffb728b, 0x74ebb911],
+        [0x74ebb911, 0x03234f3d, 0xa766ae02, 0x2ffb728b],
+        [0x2ffb728b, 0x52662805, 0x03234f3d, 0xa766ae02],
+        [0xa766ae02, 0xe778a4c3, 0x52662805, 0x03234f3d],
+        [0x03234f3d, 0x1c7f5769, 0xe778a4c3, 0x52662805],
+        [0x52662805, 0x95765642, 0x1c7f5769, 0xe778a4c3],
+        [0xe778a4c3, 0x35f37b70, 0x95765642, 0x1c7f5769],
+        [0x1c7f5769, 0x398f8f52, 0x35f37b70, 0x95765642],
+        [0x95765642, 0x13f3c36b, 0x398f8f52, 0x35f37b70],
+        [0x35f37b70, 0x058d8bb5, 0x13f3c36b, 0x398f8f52],
+        [0x398f8f52, 0xfcbe3664, 0x058d8bb5, 0x13f3c36b],
+        [0x13f3c36b, 0xf7f306a6, 0xfcbe3664, 0x058d8bb5],
+        [0x058d8bb5, 0x34cc3963, 0xf7f306a6, 0xfcbe3664],
+        [0xfcbe3664, 0x416e8ba0, 0x34cc3963, 0xf7f306a6],
+        [0xf7f306a6, 0xede91870, 0x416e8ba0, 0x34cc3963],
+        [0x34cc3963, 0xc352c547, 0xede91870, 0x416e8ba0],
+        [0x416e8ba0, 0x5d5eee28, 0xc352c547, 0xede91870],
+        [0xede91870, 0x6cc4bef2, 0x5d5eee28, 0xc352c547],
+        [0xc352c547, 0xe140970b, 0x6cc4bef2, 0x5d5eee28],
+        [0x5d5eee28, 0x79f631a9, 0xe140970b, 0x6cc4bef2],
+        [0x6cc4bef2, 0x038e0e91, 0x79f631a9, 0xe140970b],
+        [0xe140970b, 0x1b942d52, 0x038e0e91, 0x79f631a9],
+        [0x79f631a9, 0x496aecfd, 0x1b942d52, 0x038e0e91],
+        [0x038e0e91, 0xfe6cd56f, 0x496aecfd, 0x1b942d52],
+        [0x1b942d52, 0x2e94f501, 0xfe6cd56f, 0x496aecfd],
+        [0x496aecfd, 0x584e8e58, 0x2e94f501, 0xfe6cd56f],
+        [0xfe6cd56f, 0x41a17efa, 0x584e8e58, 0x2e94f501],
+        [0x2e94f501, 0x8981c6cd, 0x41a17efa, 0x584e8e58],
+        [0x584e8e58, 0x400a93e1, 0x8981c6cd, 0x41a17efa],
+        [0x41a17efa, 0x841f817f, 0x400a93e1, 0x8981c6cd],
+        [0x8981c6cd, 0x659379be, 0x841f817f, 0x400a93e1],
+        [0x400a93e1, 0xab3d9a70, 0x659379be, 0x841f817f],
+        [0x841f817f, 0xd3d21dc8, 0xab3d9a70, 0x659379be],
+        [0x659379be, 0x38c8d29d, 0xd3d21dc8, 0xab3d9a70],
+        [0xab3d9a70, 0x738b9b0f, 0x38c8d29d, 0xd3d21dc8],
+        [0xd3d21dc8, 0x8528b83e, 0x738b9b0f, 0x38c8d29d],
+        [0x38c8d29d, 0x7345af18, 0x8528b83e, 0x738b9b0f],
+        [0x738b9b0f, 0xffccc52b, 0x7345af18, 0x8528b83e],
+        [0x8528b83e, 0xa77e902b, 0xffccc52b, 0x7345af18],
+        [0x7345af18, 0xcb9c6c83, 0xa77e902b, 0xffccc52b],
+        [0xffccc52b, 0x38a2da83, 0xcb9c6c83, 0xa77e902b],
+        [0xa77e902b, 0x487f9401, 0x38a2da83, 0xcb9c6c83],
+        [0xcb9c6c83, 0xc7184576, 0x487f9401, 0x38a2da83],
+        [0x38a2da83, 0x56d619b1, 0xc7184576, 0x487f9401],
+        [0x487f9401, 0x3a35a3c5, 0x56d619b1, 0xc7184576],
+        [0xc7184576, 0xb5517538, 0x3a35a3c5, 0x56d619b1],
+        [0x56d619b1, 0x4609c4c2, 0xb5517538, 0x3a35a3c5],
+        [0x3a35a3c5, 0xd5c2b699, 0x4609c4c2, 0xb5517538],
+        [0xb5517538, 0x342af741, 0xd5c2b699, 0x4609c4c2],
+        [0x4609c4c2, 0x38286dda, 0x342af741, 0xd5c2b699],
+        [0xd5c2b699, 0x9bceec0a, 0x38286dda, 0x342af741],
+        [0x342af741, 0x5803df3a, 0x9bceec0a, 0x38286dda],
+        [0x38286dda, 0xe1b026eb, 0x5803df3a, 0x9bceec0a],
+        [0x9bceec0a, 0x31587c22, 0xe1b026eb, 0x5803df3a],
+        [0x5803df3a, 0x9b25e1dc, 0x31587c22, 0xe1b026eb],
+        [0xe1b026eb, 0x2205379e, 0x9b25e1dc, 0x31587c22],
+        [0x31587c22, 0x5e3334a3, 0x2205379e, 0x9b25e1dc],
+        [0x9b25e1dc, 0x56f80fa9, 0x5e3334a3, 0x2205379e],
+    ];
+
+    // the states of the right line after each step, for the single block of "abc"
+    #[cfg(feature = "alloc")]
+    #[rustfmt::skip]
+    const ABC_RIGHT_STEPS: [[u32; 4]; 64] = [
+        [0x10325476, 0x70376f40, 0xefcdab89, 0x98badcfe],
+        [0x98badcfe, 0x989f6bb0, 0x70376f40, 0xefcdab89],
+        [0xefcdab89, 0x39b14904, 0x989f6bb0, 0x70376f40],
+        [0x70376f40, 0x671c03cc, 0x39b14904, 0x989f6bb0],
+        [0x989f6bb0, 0xbfd55c42, 0x671c03cc, 0x39b14904],
+        [0x39b14904, 0xa12f346f, 0xbfd55c42, 0x671c03cc],
+        [0x671c03cc, 0x989c2210, 0xa12f346f, 0xbfd55c42],
+        [0xbfd55c42, 0x0f95fbea, 0x989c2210, 0xa12f346f],
+        [0xa12f346f, 0x068d5115, 0x0f95fbea, 0x989c2210],
+        [0x989c2210, 0xafcd27fc, 0x068d5115, 0x0f95fbea],
+        [0x0f95fbea, 0xcbd1f3f8, 0xafcd27fc, 0x068d5115],
+        [0x068d5115, 0xcffe405f, 0xcbd1f3f8, 0xafcd27fc],
+        [0xafcd27fc, 0x2b55c9c3, 0xcffe405f, 0xcbd1f3f8],
+        [0xcbd1f3f8, 0xdd6a43fb, 0x2b55c9c3, 0xcffe405f],
+        [0xcffe405f, 0x049b909e, 0xdd6a43fb, 0x2b55c9c3],
+        [0x2b55c9c3, 0x3713bffd, 0x049b909e, 0xdd6a43fb],
+        [0xdd6a43fb, 0x82addb53, 0x3713bffd, 0x049b909e],
+        [0x049b909e, 0xcc1d8105, 0x82addb53, 0x3713bffd],
+        [0x3713bffd, 0xbe09159a, 0xcc1d8105, 0x82addb53],
+        [0x82addb53, 0x541ae568, 0xbe09159a, 0xcc1d8105],
+        [0xcc1d8105, 0x27d40f94, 0x541ae568, 0xbe09159a],
+        [0xbe09159a, 0x675c363a, 0x27d40f94, 0x541ae568],
+        [0x541ae568, 0x77f3a38b, 0x675c363a, 0x27d40f94],
+        [0x27d40f94, 0x84d73c44, 0x77f3a38b, 0x675c363a],
+        [0x675c363a, 0xd2958f37, 0x84d73c44, 0x77f3a38b],
+        [0x77f3a38b, 0xfc39c927, 0xd2958f37, 0x84d73c44],
+        [0x84d73c44, 0xe3a5a4de, 0xfc39c927, 0xd2958f37],
+        [0xd2958f37, 0x4ba3a889, 0xe3a5a4de, 0xfc39c927],
+        [0xfc39c927, 0xa964ba74, 0x4ba3a889, 0xe3a5a4de],
+        [0xe3a5a4de, 0x7af9dbb0, 0xa964ba74, 0x4ba3a889],
+        [0x4ba3a889, 0x7da68ea9, 0x7af9dbb0, 0xa964ba74],
+        [0xa964ba74, 0x9c7247e5, 0x7da68ea9, 0x7af9dbb0],
+        [0x7af9dbb0, 0x0130312b, 0x9c7247e5, 0x7da68ea9],
+        [0x7da68ea9, 0x90552232, 0x0130312b, 0x9c7247e5],
+        [0x9c7247e5, 0x99c1fba4, 0x90552232, 0x0130312b],
+        [0x0130312b, 0x9d481cd2, 0x99c1fba4, 0x90552232],
+        [0x90552232, 0xf5aabe07, 0x9d481cd2, 0x99c1fba4],
+        [0x99c1fba4, 0xc3afb7e6, 0xf5aabe07, 0x9d481cd2],
+        [0x9d481cd2, 0x473e2b79, 0xc3afb7e6, 0xf5aabe07],
+        [0xf5aabe07, 0xc4caff99, 0x473e2b79, 0xc3afb7e6],
+        [0xc3afb7e6, 0xa2879aa4, 0xc4caff99, 0x473e2b79],
+        [0x473e2b79, 0x56565edb, 0xa2879aa4, 0xc4caff99],
+        [0xc4caff99, 0xe7a4bd86, 0x56565edb, 0xa2879aa4],
+        [0xa2879aa4, 0x974b9e10, 0xe7a4bd86, 0x56565edb],
+        [0x56565edb, 0x96cc5ae1, 0x974b9e10, 0xe7a4bd86],
+        [0xe7a4bd86, 0x57e6a772, 0x96cc5ae1, 0x974b9e10],
+        [0x974b9e10, 0xf10b6cf5, 0x57e6a772, 0x96cc5ae1],
+        [0x96cc5ae1, 0x90426e6b, 0xf10b6cf5, 0x57e6a772],
+        [0x57e6a772, 0x0066e6be, 0x90426e6b, 0xf10b6cf5],
+        [0xf10b6cf5, 0x22d17257, 0x0066e6be, 0x90426e6b],
+        [0x90426e6b, 0x016777a4, 0x22d17257, 0x0066e6be],
+        [0x0066e6be, 0x9a8dc5a0, 0x016777a4, 0x22d17257],
+        [0x22d17257, 0xa9c46e68, 0x9a8dc5a0, 0x016777a4],
+        [0x016777a4, 0x13b0d540, 0xa9c46e68, 0x9a8dc5a0],
+        [0x9a8dc5a0, 0x983d8b08, 0x13b0d540, 0xa9c46e68],
+        [0xa9c46e68, 0x96084f4e, 0x983d8b08, 0x13b0d540],
+        [0x13b0d540, 0xd25fdbb1, 0x96084f4e, 0x983d8b08],
+        [0x983d8b08, 0x35ea6fe0, 0xd25fdbb1, 0x96084f4e],
+        [0x96084f4e, 0xb862709f, 0x35ea6fe0, 0xd25fdbb1],
+        [0xd25fdbb1, 0xc02839eb, 0xb862709f, 0x35ea6fe0],
+        [0x35ea6fe0, 0x00245200, 0xc02839eb, 0xb862709f],
+        [0xb862709f, 0xcb116a95, 0x00245200, 0xc02839eb],
+        [0xc02839eb, 0xb90ee1bf, 0xcb116a95, 0x00245200],
+        [0x00245200, 0x64132d32, 0xb90ee1bf, 0xcb116a95],
+    ];
+
+    #[test]
+    fn ripemd128_hash() {
+        // test suite from the RIPEMD homepage
+        let vectors = [
+            ("", "cdf26213a150dc3ecb610f18f6b38b46"),
+            ("a", "86be7afa339d0fc7cfc785e72f578d33"),
+            ("abc", "c14a12199c66e4ba84636b0f69144c77"),
+            ("message digest", "9e327b3d6e523062afc1132d7df9d1b8"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "fd2aa607f71dc8f510714922b371834e",
+            ),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "a1aa0689d0fafa2ddc22e88b49133a06",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "d1e959eb179c911faea4624c60c5c702",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "3f45ef194732c2dbb2c4a2c769795fa3",
+            ),
+        ];
+        for (message, digest) in vectors {
+            assert_eq!(digest, hash(message).to_string());
+        }
+    }
+
+    #[test]
+    fn ripemd128_streaming() {
+        let message = [0x5au8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Ripemd128::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+
+        let mut hasher = Ripemd128::new();
+        message.chunks(7).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hash(message), hasher.finalize());
+        assert_eq!(hash(""), Ripemd128::new().finalize());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn ripemd128_steps() {
+        let words: [u32; 16] = bytes_to_words_le(pad("abc"));
+        let (mut left, mut right) = (IV, IV);
+
+        for i in 0..64 {
+            left = left_step(left, &words, i);
+            right = right_step(right, &words, i);
+            assert_eq!(ABC_LEFT_STEPS[i], left);
+            assert_eq!(ABC_RIGHT_STEPS[i], right);
+        }
+    }
+}
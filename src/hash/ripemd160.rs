@@ -0,0 +1,508 @@
+#[cfg(feature = "alloc")]
+use crate::hash::md4::pad;
+use crate::hash::{bytes_to_words_le, md4::padding, words_to_bytes_le, Buffer, Digest, Hasher};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+
+// based on "RIPEMD-160: A strengthened version of RIPEMD" by Dobbertin, Bosselaers and Preneel
+const IV: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+// additional constants for each round of the left and the right line
+const K: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+const K_PRIME: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+// indices of the message words for each step of the left and the right line
+#[rustfmt::skip]
+pub const R: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+#[rustfmt::skip]
+pub const R_PRIME: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+// shifts for each step of the left and the right line
+#[rustfmt::skip]
+pub const S: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+#[rustfmt::skip]
+pub const S_PRIME: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+// round functions, which are used in opposite order by the left and the right line
+pub const F: [fn(u32, u32, u32) -> u32; 5] = [
+    |x, y, z| x ^ y ^ z,
+    |x, y, z| (x & y) | (!x & z),
+    |x, y, z| (x | !y) ^ z,
+    |x, y, z| (x & z) | (y & !z),
+    |x, y, z| x ^ (y | !z),
+];
+
+// compute an individual step of the left line
+pub fn left_step([a, b, c, d, e]: [u32; 5], words: &[u32], i: usize) -> [u32; 5] {
+    let t = F[i / 16](b, c, d)
+        .wrapping_add(a)
+        .wrapping_add(words[R[i]])
+        .wrapping_add(K[i / 16])
+        .rotate_left(S[i])
+        .wrapping_add(e);
+
+    [e, t, b, c.rotate_left(10), d]
+}
+
+// compute an individual step of the right line
+pub fn right_step([a, b, c, d, e]: [u32; 5], words: &[u32], i: usize) -> [u32; 5] {
+    let t = F[4 - i / 16](b, c, d)
+        .wrapping_add(a)
+        .wrapping_add(words[R_PRIME[i]])
+        .wrapping_add(K_PRIME[i / 16])
+        .rotate_left(S_PRIME[i])
+        .wrapping_add(e);
+
+    [e, t, b, c.rotate_left(10), d]
+}
+
+/// Computes the RIPEMD-160 hash value (digest) of the input bytes.
+///
+/// Returns a 20-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let input = "abc";
+/// let digest = lore::ripemd160(input);
+///
+/// assert_eq!(
+///     digest.to_string(),
+///     "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+/// );
+/// ```
+pub fn hash(message: impl AsRef<[u8]>) -> Digest<20> {
+    let mut hasher = Ripemd160::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the RIPEMD-160 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::ripemd160("abc", &mut events);
+///
+/// assert_eq!(digest, lore::ripemd160("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<20> {
+    let padded = pad(message);
+    let state = compress_blocks(
+        &padded,
+        IV,
+        observer,
+        bytes_to_words_le::<16>,
+        compress_observed,
+    );
+    let digest = words_to_bytes_le(state);
+
+    trace::digest(Digest(digest), observer)
+}
+
+// process a single 512-bit block of the padded message, with both lines in parallel
+fn compress(h: [u32; 5], block: &[u8; 64]) -> [u32; 5] {
+    compress_observed(h, block, &mut ())
+}
+
+// process a single 512-bit block of the padded message, with both lines in parallel, reporting each step to the observer
+fn compress_observed<O: Observer + ?Sized>(
+    h: [u32; 5],
+    block: &[u8; 64],
+    observer: &mut O,
+) -> [u32; 5] {
+    let words: [u32; 16] = bytes_to_words_le(block);
+    let (l, r) = (0..80).fold((h, h), |(l, r), i| {
+        let (l, r) = (left_step(l, &words, i), right_step(r, &words, i));
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: [l, r].concat().into(),
+            }
+        );
+        (l, r)
+    });
+
+    // combine both lines with the previous state
+    let state = [
+        h[1].wrapping_add(l[2]).wrapping_add(r[3]),
+        h[2].wrapping_add(l[3]).wrapping_add(r[4]),
+        h[3].wrapping_add(l[4]).wrapping_add(r[0]),
+        h[4].wrapping_add(l[0]).wrapping_add(r[1]),
+        h[0].wrapping_add(l[1]).wrapping_add(r[2]),
+    ];
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: h.into(),
+            working: [l, r].concat().into(),
+            next: state.into(),
+        }
+    );
+
+    state
+}
+
+/// A streaming RIPEMD-160 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Ripemd160::new();
+/// hasher.update("ab");
+/// hasher.update("c");
+///
+/// assert_eq!(hasher.finalize(), lore::ripemd160("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ripemd160 {
+    state: [u32; 5],
+    buffer: Buffer<64>,
+}
+
+impl Ripemd160 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: IV,
+            buffer: Buffer::new(),
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer } = self;
+        buffer.update(data.as_ref(), |block| *state = compress(*state, block));
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Ripemd160::update`].
+    #[must_use]
+    pub fn finalize(mut self) -> Digest<20> {
+        self.update(padding(self.buffer.length()));
+        let digest = words_to_bytes_le(self.state);
+
+        Digest(digest)
+    }
+}
+
+impl Default for Ripemd160 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Ripemd160 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 20;
+
+    type Output = Digest<20>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<20> {
+        self.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::trace::Words;
+
+    // the states of the left line after each step, for the single block of "abc"
+    #[cfg(feature = "alloc")]
+    #[rustfmt::skip]
+    const ABC_LEFT_STEPS: [[u32; 5]; 80] = [
+        [0xc3d2e1f0, 0x3115fc67, 0xefcdab89, 0xeb73fa62, 0x10325476],
+        [0x10325476, 0xb41192d5, 0x3115fc67, 0x36ae27bf, 0xeb73fa62],
+        [0xeb73fa62, 0x3a35dc50, 0xb41192d5, 0x57f19cc4, 0x36ae27bf],
+        [0x36ae27bf, 0xd3786413, 0x3a35dc50, 0x464b56d0, 0x57f19cc4],
+        [0x57f19cc4, 0x0e946720, 0xd3786413, 0xd77140e8, 0x464b56d0],
+        [0x464b56d0, 0xd52bf632, 0x0e946720, 0xe1904f4d, 0xd77140e8],
+        [0xd77140e8, 0x150bd8a8, 0xd52bf632, 0x519c803a, 0xe1904f4d],
+        [0xe1904f4d, 0x3d6f601f, 0x150bd8a8, 0xafd8cb54, 0x519c803a],
+        [0x519c803a, 0xb7b60384, 0x3d6f601f, 0x2f62a054, 0xafd8cb54],
+        [0xafd8cb54, 0xb85a0a3f, 0xb7b60384, 0xbd807cf5, 0x2f62a054],
+        [0x2f62a054, 0x7f8b38e5, 0xb85a0a3f, 0xd80e12de, 0xbd807cf5],
+        [0xbd807cf5, 0x9daca495, 0x7f8b38e5, 0x6828fee1, 0xd80e12de],
+        [0xd80e12de, 0xbc05f46f, 0x9daca495, 0x2ce395fe, 0x6828fee1],
+        [0x6828fee1, 0x1494f053, 0xbc05f46f, 0xb2925676, 0x2ce395fe],
+        [0x2ce395fe, 0x85861d02, 0x1494f053, 0x17d1bef0, 0xb2925676],
+        [0xb2925676, 0x597bf629, 0x85861d02, 0x53c14c52, 0x17d1bef0],
+        [0x17d1bef0, 0x6347ef78, 0x597bf629, 0x18740a16, 0x53c14c52],
+        [0x53c14c52, 0x45c8fa44, 0x6347ef78, 0xefd8a565, 0x18740a16],
+        [0x18740a16, 0xad2956af, 0x45c8fa44, 0x1fbde18d, 0xefd8a565],
+        [0xefd8a565, 0x5eaf16b7, 0xad2956af, 0x23e91117, 0x1fbde18d],
+        [0x1fbde18d, 0x41730d4b, 0x5eaf16b7, 0xa55abeb4, 0x23e91117],
+        [0x23e91117, 0xfc0ccbd3, 0x41730d4b, 0xbc5add7a, 0xa55abeb4],
+        [0xa55abeb4, 0x042ecc93, 0xfc0ccbd3, 0xcc352d05, 0xbc5add7a],
+        [0xbc5add7a, 0x4d4d4377, 0x042ecc93, 0x332f4ff0, 0xcc352d05],
+        [0xcc352d05, 0x5207002b, 0x4d4d4377, 0xbb324c10, 0x332f4ff0],
+        [0x332f4ff0, 0x388278f5, 0x5207002b, 0x350ddd35, 0xbb324c10],
+        [0xbb324c10, 0x62879d70, 0x388278f5, 0x1c00ad48, 0x350ddd35],
+        [0x350ddd35, 0xa30a1fd9, 0x62879d70, 0x09e3d4e2, 0x1c00ad48],
+        [0x1c00ad48, 0xbda2b31b, 0xa30a1fd9, 0x1e75c18a, 0x09e3d4e2],
+        [0x09e3d4e2, 0xf7211dee, 0xbda2b31b, 0x287f668c, 0x1e75c18a],
+        [0x1e75c18a, 0xb6a665c6, 0xf7211dee, 0x8acc6ef6, 0x287f668c],
+        [0x287f668c, 0x2d30fa02, 0xb6a665c6, 0x8477bbdc, 0x8acc6ef6],
+        [0x8acc6ef6, 0xc76d12f9, 0x2d30fa02, 0x99971ada, 0x8477bbdc],
+        [0x8477bbdc, 0x516f84df, 0xc76d12f9, 0xc3e808b4, 0x99971ada],
+        [0x99971ada, 0xf3fa5b05, 0x516f84df, 0xb44be71d, 0xc3e808b4],
+        [0xc3e808b4, 0xd539625e, 0xf3fa5b05, 0xbe137d45, 0xb44be71d],
+        [0xb44be71d, 0xd8500c99, 0xd539625e, 0xe96c17cf, 0xbe137d45],
+        [0xbe137d45, 0x7ecde5b2, 0xd8500c99, 0xe5897b54, 0xe96c17cf],
+        [0xe96c17cf, 0x681d30b9, 0x7ecde5b2, 0x40326761, 0xe5897b54],
+        [0xe5897b54, 0x960f7bfd, 0x681d30b9, 0x3796c9fb, 0x40326761],
+        [0x40326761, 0x6770e498, 0x960f7bfd, 0x74c2e5a0, 0x3796c9fb],
+        [0x3796c9fb, 0x75eb06c5, 0x6770e498, 0x3deff658, 0x74c2e5a0],
+        [0x74c2e5a0, 0x14fa827a, 0x75eb06c5, 0xc392619d, 0x3deff658],
+        [0x3deff658, 0x804b0068, 0x14fa827a, 0xac1b15d7, 0xc392619d],
+        [0xc392619d, 0x475ba81b, 0x804b0068, 0xea09e853, 0xac1b15d7],
+        [0xac1b15d7, 0xd26bc25d, 0x475ba81b, 0x2c01a201, 0xea09e853],
+        [0xea09e853, 0xdbc5a2cb, 0xd26bc25d, 0x6ea06d1d, 0x2c01a201],
+        [0x2c01a201, 0x77367f5e, 0xdbc5a2cb, 0xaf097749, 0x6ea06d1d],
+        [0x6ea06d1d, 0x8155a6b4, 0x77367f5e, 0x168b2f6f, 0xaf097749],
+        [0xaf097749, 0xc90c4d38, 0x8155a6b4, 0xd9fd79dc, 0x168b2f6f],
+        [0x168b2f6f, 0x9762713b, 0xc90c4d38, 0x569ad205, 0xd9fd79dc],
+        [0xd9fd79dc, 0x7ebf9c32, 0x9762713b, 0x3134e324, 0x569ad205],
+        [0x569ad205, 0x20effa01, 0x7ebf9c32, 0x89c4ee5d, 0x3134e324],
+        [0x3134e324, 0x75b7117f, 0x20effa01, 0xfe70c9fa, 0x89c4ee5d],
+        [0x89c4ee5d, 0xa96be4c7, 0x75b7117f, 0xbfe80483, 0xfe70c9fa],
+        [0xfe70c9fa, 0x5e3201fc, 0xa96be4c7, 0xdc45fdd6, 0xbfe80483],
+        [0xbfe80483, 0x2cf95a98, 0x5e3201fc, 0xaf931ea5, 0xdc45fdd6],
+        [0xdc45fdd6, 0x1393f0c3, 0x2cf95a98, 0xc807f178, 0xaf931ea5],
+        [0xaf931ea5, 0xbb49ccf7, 0x1393f0c3, 0xe56a60b3, 0xc807f178],
+        [0xc807f178, 0x6a330eb4, 0xbb49ccf7, 0x4fc30c4e, 0xe56a60b3],
+        [0xe56a60b3, 0x14e58204, 0x6a330eb4, 0x2733deed, 0x4fc30c4e],
+        [0x4fc30c4e, 0x79aaf53e, 0x14e58204, 0xcc3ad1a8, 0x2733deed],
+        [0x2733deed, 0x210769b3, 0x79aaf53e, 0x96081053, 0xcc3ad1a8],
+        [0xcc3ad1a8, 0xf44b53a7, 0x210769b3, 0xabd4f9e6, 0x96081053],
+        [0x96081053, 0x7c1e3640, 0xf44b53a7, 0x1da6cc84, 0xabd4f9e6],
+        [0xabd4f9e6, 0x06b59ee8, 0x7c1e3640, 0x2d4e9fd1, 0x1da6cc84],
+        [0x1da6cc84, 0xc422c3cd, 0x06b59ee8, 0x78d901f0, 0x2d4e9fd1],
+        [0x2d4e9fd1, 0xad864025, 0xc422c3cd, 0xd67ba01a, 0x78d901f0],
+        [0x78d901f0, 0x29a83bb5, 0xad864025, 0x8b0f3710, 0xd67ba01a],
+        [0xd67ba01a, 0x626e3910, 0x29a83bb5, 0x190096b6, 0x8b0f3710],
+        [0x8b0f3710, 0xa719d8bc, 0x626e3910, 0xa0eed4a6, 0x190096b6],
+        [0x190096b6, 0xba84c782, 0xa719d8bc, 0xb8e44189, 0xa0eed4a6],
+        [0xa0eed4a6, 0x9f6887a9, 0xba84c782, 0x6762f29c, 0xb8e44189],
+        [0xb8e44189, 0x3a88288c, 0x9f6887a9, 0x131e0aea, 0x6762f29c],
+        [0x6762f29c, 0xab23f78f, 0x3a88288c, 0xa21ea67d, 0x131e0aea],
+        [0x131e0aea, 0x7299044a, 0xab23f78f, 0x20a230ea, 0xa21ea67d],
+        [0xa21ea67d, 0x6a3f10cf, 0x7299044a, 0x8fde3eac, 0x20a230ea],
+        [0x20a230ea, 0x1a1b904d, 0x6a3f10cf, 0x641129ca, 0x8fde3eac],
+        [0x8fde3eac, 0x0b2cdc01, 0x1a1b904d, 0xfc433da8, 0x641129ca],
+        [0x641129ca, 0xd563bfdc, 0x0b2cdc01, 0x6e413468, 0xfc433da8],
+    ];
+
+    // the states of the right line after each step, for the single block of "abc"
+    #[cfg(feature = "alloc")]
+    #[rustfmt::skip]
+    const ABC_RIGHT_STEPS: [[u32; 5]; 80] = [
+        [0xc3d2e1f0, 0xddd63fb8, 0xefcdab89, 0xeb73fa62, 0x10325476],
+        [0x10325476, 0x322e7ae3, 0xddd63fb8, 0x36ae27bf, 0xeb73fa62],
+        [0xeb73fa62, 0x883ee903, 0x322e7ae3, 0x58fee377, 0x36ae27bf],
+        [0x36ae27bf, 0x92b2b79b, 0x883ee903, 0xb9eb8cc8, 0x58fee377],
+        [0x58fee377, 0xf9091ff2, 0x92b2b79b, 0xfba40e20, 0xb9eb8cc8],
+        [0xb9eb8cc8, 0xe5b09992, 0xf9091ff2, 0xcade6e4a, 0xfba40e20],
+        [0xfba40e20, 0x8b2d9fb3, 0xe5b09992, 0x247fcbe4, 0xcade6e4a],
+        [0xcade6e4a, 0xe755f422, 0x8b2d9fb3, 0xc2664b96, 0x247fcbe4],
+        [0x247fcbe4, 0x5922d09e, 0xe755f422, 0xb67ece2c, 0xc2664b96],
+        [0xc2664b96, 0xcf24e72c, 0x5922d09e, 0x57d08b9d, 0xb67ece2c],
+        [0xb67ece2c, 0xca6a1c75, 0xcf24e72c, 0x8b427964, 0x57d08b9d],
+        [0x57d08b9d, 0x227f6d84, 0xca6a1c75, 0x939cb33c, 0x8b427964],
+        [0x8b427964, 0x5d801685, 0x227f6d84, 0xa871d729, 0x939cb33c],
+        [0x939cb33c, 0xb3c3f4d5, 0x5d801685, 0xfdb61089, 0xa871d729],
+        [0xa871d729, 0x3d16242d, 0xb3c3f4d5, 0x005a1576, 0xfdb61089],
+        [0xfdb61089, 0xff459078, 0x3d16242d, 0x0fd356cf, 0x005a1576],
+        [0x005a1576, 0x927e40a8, 0xff459078, 0x5890b4f4, 0x0fd356cf],
+        [0x0fd356cf, 0xacbb994e, 0x927e40a8, 0x1641e3fd, 0x5890b4f4],
+        [0x5890b4f4, 0xad30ad24, 0xacbb994e, 0xf902a249, 0x1641e3fd],
+        [0x1641e3fd, 0x6261732e, 0xad30ad24, 0xee653ab2, 0xf902a249],
+        [0xf902a249, 0x45ed27af, 0x6261732e, 0xc2b492b4, 0xee653ab2],
+        [0xee653ab2, 0x243c5668, 0x45ed27af, 0x85ccb989, 0xc2b492b4],
+        [0xc2b492b4, 0x82f89bd1, 0x243c5668, 0xb49ebd17, 0x85ccb989],
+        [0x85ccb989, 0x5fc74686, 0x82f89bd1, 0xf159a090, 0xb49ebd17],
+        [0xb49ebd17, 0xb2720031, 0x5fc74686, 0xe26f460b, 0xf159a090],
+        [0xf159a090, 0x58a100f8, 0xb2720031, 0x1d1a197f, 0xe26f460b],
+        [0xe26f460b, 0x5992068b, 0x58a100f8, 0xc800c6c9, 0x1d1a197f],
+        [0x1d1a197f, 0xcc290dca, 0x5992068b, 0x8403e162, 0xc800c6c9],
+        [0xc800c6c9, 0x863d625e, 0xcc290dca, 0x481a2d66, 0x8403e162],
+        [0x8403e162, 0x6061b5a5, 0x863d625e, 0xa4372b30, 0x481a2d66],
+        [0x481a2d66, 0xaa98adb5, 0x6061b5a5, 0xf5897a18, 0xa4372b30],
+        [0xa4372b30, 0x2999255a, 0xaa98adb5, 0x86d69581, 0xf5897a18],
+        [0xf5897a18, 0x98237631, 0x2999255a, 0x62b6d6aa, 0x86d69581],
+        [0x86d69581, 0x6c472a90, 0x98237631, 0x649568a6, 0x62b6d6aa],
+        [0x62b6d6aa, 0x2ead5672, 0x6c472a90, 0x8dd8c660, 0x649568a6],
+        [0x649568a6, 0xc5cb48ba, 0x2ead5672, 0x1caa41b1, 0x8dd8c660],
+        [0x8dd8c660, 0x05286dfb, 0xc5cb48ba, 0xb559c8ba, 0x1caa41b1],
+        [0x1caa41b1, 0x88396dd2, 0x05286dfb, 0x2d22eb17, 0xb559c8ba],
+        [0xb559c8ba, 0x333f2212, 0x88396dd2, 0xa1b7ec14, 0x2d22eb17],
+        [0x2d22eb17, 0xc699295b, 0x333f2212, 0xe5b74a20, 0xa1b7ec14],
+        [0xa1b7ec14, 0xbfd68874, 0xc699295b, 0xfc8848cc, 0xe5b74a20],
+        [0xe5b74a20, 0xbddf3474, 0xbfd68874, 0x64a56f1a, 0xfc8848cc],
+        [0xfc8848cc, 0x8cbc87e9, 0xbddf3474, 0x5a21d2ff, 0x64a56f1a],
+        [0x64a56f1a, 0xcdda6ebf, 0x8cbc87e9, 0x7cd1d2f7, 0x5a21d2ff],
+        [0x5a21d2ff, 0x656c7da3, 0xcdda6ebf, 0xf21fa632, 0x7cd1d2f7],
+        [0x7cd1d2f7, 0x76d66ca3, 0x656c7da3, 0x69baff37, 0xf21fa632],
+        [0xf21fa632, 0xc9b17f72, 0x76d66ca3, 0xb1f68d95, 0x69baff37],
+        [0x69baff37, 0x65a60151, 0xc9b17f72, 0x59b28ddb, 0xb1f68d95],
+        [0xb1f68d95, 0x33f3ac81, 0x65a60151, 0xc5fdcb26, 0x59b28ddb],
+        [0x59b28ddb, 0x9bfb827d, 0x33f3ac81, 0x98054596, 0xc5fdcb26],
+        [0xc5fdcb26, 0xddc8130e, 0x9bfb827d, 0xceb204cf, 0x98054596],
+        [0x98054596, 0xc24c2c79, 0xddc8130e, 0xee09f66f, 0xceb204cf],
+        [0xceb204cf, 0xf255847e, 0xc24c2c79, 0x204c3b77, 0xee09f66f],
+        [0xee09f66f, 0xdcd63949, 0xf255847e, 0x30b1e709, 0x204c3b77],
+        [0x204c3b77, 0x5b99238d, 0xdcd63949, 0x5611fbc9, 0x30b1e709],
+        [0x30b1e709, 0xb43484f4, 0x5b99238d, 0x58e52773, 0x5611fbc9],
+        [0x5611fbc9, 0x52325a09, 0xb43484f4, 0x648e356e, 0x58e52773],
+        [0x58e52773, 0xd015577d, 0x52325a09, 0xd213d2d0, 0x648e356e],
+        [0x648e356e, 0xbb9c87c4, 0xd015577d, 0xc9682548, 0xd213d2d0],
+        [0xd213d2d0, 0xb1bb1a2e, 0xbb9c87c4, 0x555df740, 0xc9682548],
+        [0xc9682548, 0xac77f96d, 0xb1bb1a2e, 0x721f12ee, 0x555df740],
+        [0x555df740, 0x1774d326, 0xac77f96d, 0xec68bac6, 0x721f12ee],
+        [0x721f12ee, 0xa625f112, 0x1774d326, 0xdfe5b6b1, 0xec68bac6],
+        [0xec68bac6, 0x5dca4d12, 0xa625f112, 0xd34c985d, 0xdfe5b6b1],
+        [0xdfe5b6b1, 0xebc4d9c6, 0x5dca4d12, 0x97c44a98, 0xd34c985d],
+        [0xd34c985d, 0x095f37fd, 0xebc4d9c6, 0x29344977, 0x97c44a98],
+        [0x97c44a98, 0x5bbee487, 0x095f37fd, 0x13671baf, 0x29344977],
+        [0x29344977, 0xbf5b2529, 0x5bbee487, 0x7cdff425, 0x13671baf],
+        [0x13671baf, 0xfb5747c5, 0xbf5b2529, 0xfb921d6e, 0x7cdff425],
+        [0x7cdff425, 0xdd935a5f, 0xfb5747c5, 0x6c94a6fd, 0xfb921d6e],
+        [0xfb921d6e, 0x27754f3a, 0xdd935a5f, 0x5d1f17ed, 0x6c94a6fd],
+        [0x6c94a6fd, 0x4f5ca4a5, 0x27754f3a, 0x4d697f76, 0x5d1f17ed],
+        [0x5d1f17ed, 0x325afe7e, 0x4f5ca4a5, 0xd53ce89d, 0x4d697f76],
+        [0x4d697f76, 0x86afe021, 0x325afe7e, 0x7292953d, 0xd53ce89d],
+        [0xd53ce89d, 0xc97f9ea1, 0x86afe021, 0x6bf9f8c9, 0x7292953d],
+        [0x7292953d, 0x9f60751c, 0xc97f9ea1, 0xbf80861a, 0x6bf9f8c9],
+        [0x6bf9f8c9, 0x1e9ce713, 0x9f60751c, 0xfe7a8725, 0xbf80861a],
+        [0xbf80861a, 0xc13f038a, 0x1e9ce713, 0x81d4727d, 0xfe7a8725],
+        [0xfe7a8725, 0xbf627814, 0xc13f038a, 0x739c4c7a, 0x81d4727d],
+        [0x81d4727d, 0x5fccbade, 0xbf627814, 0xfc0e2b04, 0x739c4c7a],
+    ];
+
+    #[test]
+    fn ripemd160_hash() {
+        // test suite from the RIPEMD homepage
+        let vectors = [
+            ("", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+            ("a", "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe"),
+            ("abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            ("message digest", "5d0689ef49d2fae572b881b123a85ffa21595f36"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "f71c27109c692c1b56bbdceb5b9d2865b3708dbc",
+            ),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "b0e20b6e3116640286ed3a87a5713079b21f5189",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+            ),
+        ];
+        for (message, digest) in vectors {
+            assert_eq!(digest, hash(message).to_string());
+        }
+    }
+
+    #[test]
+    fn ripemd160_streaming() {
+        let message = [0x5au8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Ripemd160::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+
+        let mut hasher = Ripemd160::new();
+        message.chunks(7).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hash(message), hasher.finalize());
+        assert_eq!(hash(""), Ripemd160::new().finalize());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn ripemd160_steps() {
+        let words: [u32; 16] = bytes_to_words_le(pad("abc"));
+        let (mut left, mut right) = (IV, IV);
+
+        for i in 0..80 {
+            left = left_step(left, &words, i);
+            right = right_step(right, &words, i);
+            assert_eq!(ABC_LEFT_STEPS[i], left);
+            assert_eq!(ABC_RIGHT_STEPS[i], right);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn ripemd160_trace() {
+        let mut events = Vec::new();
+        trace("abc", &mut events);
+        let steps: Vec<_> = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Step { state, .. } => Some(state),
+                _ => None,
+            })
+            .collect();
+
+        // both lines are reported together, the left line first
+        assert_eq!(80, steps.len());
+        for (i, state) in steps.into_iter().enumerate() {
+            assert_eq!(
+                Words::from([ABC_LEFT_STEPS[i], ABC_RIGHT_STEPS[i]].concat()),
+                state
+            );
+        }
+    }
+}
@@ -0,0 +1,254 @@
+#[cfg(feature = "alloc")]
+use crate::hash::md4::pad;
+use crate::hash::{
+    bytes_to_words_le,
+    md4::padding,
+    ripemd128::{left_step, right_step},
+    words_to_bytes_le, Buffer, Digest, Hasher,
+};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+use core::convert::TryInto;
+
+// based on "RIPEMD-160: A strengthened version of RIPEMD" by Dobbertin, Bosselaers and Preneel
+// the state holds both lines, which are initialized differently
+const IV: [u32; 8] = [
+    0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0x76543210, 0xfedcba98, 0x89abcdef, 0x01234567,
+];
+
+// the words which are exchanged between the lines after each round
+const SWAPS: [usize; 4] = [0, 1, 2, 3];
+
+/// Computes the RIPEMD-256 hash value (digest) of the input bytes.
+///
+/// Returns a 32-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let input = "abc";
+/// let digest = lore::ripemd256(input);
+///
+/// assert_eq!(
+///     digest.to_string(),
+///     "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65"
+/// );
+/// ```
+pub fn hash(message: impl AsRef<[u8]>) -> Digest<32> {
+    let mut hasher = Ripemd256::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the RIPEMD-256 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::ripemd256("abc", &mut events);
+///
+/// assert_eq!(digest, lore::ripemd256("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<32> {
+    let padded = pad(message);
+    let state = compress_blocks(
+        &padded,
+        IV,
+        observer,
+        bytes_to_words_le::<16>,
+        compress_observed,
+    );
+    let digest = words_to_bytes_le(state);
+
+    trace::digest(Digest(digest), observer)
+}
+
+// process a single 512-bit block of the padded message, with both lines kept apart
+fn compress(h: [u32; 8], block: &[u8; 64]) -> [u32; 8] {
+    compress_observed(h, block, &mut ())
+}
+
+// process a single 512-bit block of the padded message, with both lines kept apart, reporting each step to the observer
+fn compress_observed<O: Observer + ?Sized>(
+    h: [u32; 8],
+    block: &[u8; 64],
+    observer: &mut O,
+) -> [u32; 8] {
+    let words: [u32; 16] = bytes_to_words_le(block);
+    let mut l: [u32; 4] = h[..4].try_into().unwrap();
+    let mut r: [u32; 4] = h[4..].try_into().unwrap();
+
+    for i in 0..64 {
+        l = left_step(l, &words, i);
+        r = right_step(r, &words, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: [l, r].concat().into(),
+            }
+        );
+
+        // after each round, a word of the left line is exchanged with the same word of the right line
+        if i % 16 == 15 {
+            let j = SWAPS[i / 16];
+            core::mem::swap(&mut l[j], &mut r[j]);
+        }
+    }
+
+    let mut state = h;
+    for (word, line) in state.iter_mut().zip(l.iter().chain(&r)) {
+        *word = word.wrapping_add(*line);
+    }
+
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: h.into(),
+            working: [l, r].concat().into(),
+            next: state.into(),
+        }
+    );
+
+    state
+}
+
+/// A streaming RIPEMD-256 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Ripemd256::new();
+/// hasher.update("ab");
+/// hasher.update("c");
+///
+/// assert_eq!(hasher.finalize(), lore::ripemd256("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ripemd256 {
+    state: [u32; 8],
+    buffer: Buffer<64>,
+}
+
+impl Ripemd256 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: IV,
+            buffer: Buffer::new(),
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer } = self;
+        buffer.update(data.as_ref(), |block| *state = compress(*state, block));
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Ripemd256::update`].
+    #[must_use]
+    pub fn finalize(mut self) -> Digest<32> {
+        self.update(padding(self.buffer.length()));
+        let digest = words_to_bytes_le(self.state);
+
+        Digest(digest)
+    }
+}
+
+impl Default for Ripemd256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Ripemd256 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 32;
+
+    type Output = Digest<32>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<32> {
+        self.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ripemd256_hash() {
+        // test suite from the RIPEMD homepage
+        let vectors = [
+            (
+                "",
+                "02ba4c4e5f8ecd1877fc52d64d30e37a2d9774fb1e5d026380ae0168e3c5522d",
+            ),
+            (
+                "a",
+                "f9333e45d857f5d90a91bab70a1eba0cfb1be4b0783c9acfcd883a9134692925",
+            ),
+            (
+                "abc",
+                "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65",
+            ),
+            (
+                "message digest",
+                "87e971759a1ce47a514d5c914c392c9018c7c46bc14465554afcdf54a5070c0e",
+            ),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "649d3034751ea216776bf9a18acc81bc7896118a5197968782dd1fd97d8d5133",
+            ),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "3843045583aac6c8c8d9128573e7a9809afb2a0f34ccc36ea9e72f16f6368e3f",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "5740a408ac16b720b84424ae931cbb1fe363d1d0bf4017f1a89f7ea6de77a0b8",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "06fdcc7a409548aaf91368c06a6275b553e3f099bf0ea4edfd6778df89a890dd",
+            ),
+        ];
+        for (message, digest) in vectors {
+            assert_eq!(digest, hash(message).to_string());
+        }
+    }
+
+    #[test]
+    fn ripemd256_streaming() {
+        let message = [0x5au8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Ripemd256::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+
+        let mut hasher = Ripemd256::new();
+        message.chunks(7).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hash(message), hasher.finalize());
+        assert_eq!(hash(""), Ripemd256::new().finalize());
+    }
+}
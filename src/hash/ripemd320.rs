@@ -0,0 +1,255 @@
+#[cfg(feature = "alloc")]
+use crate::hash::md4::pad;
+use crate::hash::{
+    bytes_to_words_le,
+    md4::padding,
+    ripemd160::{left_step, right_step},
+    words_to_bytes_le, Buffer, Digest, Hasher,
+};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+use core::convert::TryInto;
+
+// based on "RIPEMD-160: A strengthened version of RIPEMD" by Dobbertin, Bosselaers and Preneel
+// the state holds both lines, which are initialized differently
+const IV: [u32; 10] = [
+    0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0, 0x76543210, 0xfedcba98, 0x89abcdef,
+    0x01234567, 0x3c2d1e0f,
+];
+
+// the words which are exchanged between the lines after each round
+const SWAPS: [usize; 5] = [1, 3, 0, 2, 4];
+
+/// Computes the RIPEMD-320 hash value (digest) of the input bytes.
+///
+/// Returns a 40-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let input = "abc";
+/// let digest = lore::ripemd320(input);
+///
+/// assert_eq!(
+///     digest.to_string(),
+///     "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d"
+/// );
+/// ```
+pub fn hash(message: impl AsRef<[u8]>) -> Digest<40> {
+    let mut hasher = Ripemd320::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the RIPEMD-320 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::ripemd320("abc", &mut events);
+///
+/// assert_eq!(digest, lore::ripemd320("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<40> {
+    let padded = pad(message);
+    let state = compress_blocks(
+        &padded,
+        IV,
+        observer,
+        bytes_to_words_le::<16>,
+        compress_observed,
+    );
+    let digest = words_to_bytes_le(state);
+
+    trace::digest(Digest(digest), observer)
+}
+
+// process a single 512-bit block of the padded message, with both lines kept apart
+fn compress(h: [u32; 10], block: &[u8; 64]) -> [u32; 10] {
+    compress_observed(h, block, &mut ())
+}
+
+// process a single 512-bit block of the padded message, with both lines kept apart, reporting each step to the observer
+fn compress_observed<O: Observer + ?Sized>(
+    h: [u32; 10],
+    block: &[u8; 64],
+    observer: &mut O,
+) -> [u32; 10] {
+    let words: [u32; 16] = bytes_to_words_le(block);
+    let mut l: [u32; 5] = h[..5].try_into().unwrap();
+    let mut r: [u32; 5] = h[5..].try_into().unwrap();
+
+    for i in 0..80 {
+        l = left_step(l, &words, i);
+        r = right_step(r, &words, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: [l, r].concat().into(),
+            }
+        );
+
+        // after each round, a word of the left line is exchanged with the same word of the right line
+        if i % 16 == 15 {
+            let j = SWAPS[i / 16];
+            core::mem::swap(&mut l[j], &mut r[j]);
+        }
+    }
+
+    let mut state = h;
+    for (word, line) in state.iter_mut().zip(l.iter().chain(&r)) {
+        *word = word.wrapping_add(*line);
+    }
+
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: h.into(),
+            working: [l, r].concat().into(),
+            next: state.into(),
+        }
+    );
+
+    state
+}
+
+/// A streaming RIPEMD-320 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Ripemd320::new();
+/// hasher.update("ab");
+/// hasher.update("c");
+///
+/// assert_eq!(hasher.finalize(), lore::ripemd320("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ripemd320 {
+    state: [u32; 10],
+    buffer: Buffer<64>,
+}
+
+impl Ripemd320 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: IV,
+            buffer: Buffer::new(),
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer } = self;
+        buffer.update(data.as_ref(), |block| *state = compress(*state, block));
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Ripemd320::update`].
+    #[must_use]
+    pub fn finalize(mut self) -> Digest<40> {
+        self.update(padding(self.buffer.length()));
+        let digest = words_to_bytes_le(self.state);
+
+        Digest(digest)
+    }
+}
+
+impl Default for Ripemd320 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Ripemd320 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 40;
+
+    type Output = Digest<40>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<40> {
+        self.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ripemd320_hash() {
+        // test suite from the RIPEMD homepage
+        let vectors = [
+            (
+                "",
+                "22d65d5661536cdc75c1fdf5c6de7b41b9f27325ebc61e8557177d705a0ec880151c3a32a00899b8",
+            ),
+            (
+                "a",
+                "ce78850638f92658a5a585097579926dda667a5716562cfcf6fbe77f63542f99b04705d6970dff5d",
+            ),
+            (
+                "abc",
+                "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d",
+            ),
+            (
+                "message digest",
+                "3a8e28502ed45d422f68844f9dd316e7b98533fa3f2a91d29f84d425c88d6b4eff727df66a7c0197",
+            ),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "cabdb1810b92470a2093aa6bce05952c28348cf43ff60841975166bb40ed234004b8824463e6b009",
+            ),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "d034a7950cf722021ba4b84df769a5de2060e259df4c9bb4a4268c0e935bbc7470a969c9d072a1ac",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "ed544940c86d67f250d232c30b7b3e5770e0c60c8cb9a4cafe3b11388af9920e1b99230b843c86a4",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "557888af5f6d8ed62ab66945c6d2a0a47ecd5341e915eb8fea1d0524955f825dc717e4a008ab2d42",
+            ),
+        ];
+        for (message, digest) in vectors {
+            assert_eq!(digest, hash(message).to_string());
+        }
+    }
+
+    #[test]
+    fn ripemd320_streaming() {
+        let message = [0x5au8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Ripemd320::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+
+        let mut hasher = Ripemd320::new();
+        message.chunks(7).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hash(message), hasher.finalize());
+        assert_eq!(hash(""), Ripemd320::new().finalize());
+    }
+}
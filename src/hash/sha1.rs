@@ -1,12 +1,28 @@
-use crate::hash::{bytes_to_words_be, words_to_bytes_be, Digest};
+use crate::hash::{
+    bytes_to_words_be, bytes_to_words_be_const, padded_block, padded_blocks, words_to_bytes_be,
+    words_to_bytes_be_const, Buffer, Digest, Hasher, Padding,
+};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 // based on RFC3174, Secure Hash Algorithm 1
 
 // round functions
-const F1: fn(u32, u32, u32) -> u32 = |x, y, z| (x & y) | ((!x) & z);
-const F2: fn(u32, u32, u32) -> u32 = |x, y, z| x ^ y ^ z;
-const F3: fn(u32, u32, u32) -> u32 = |x, y, z| (x & y) | (x & z) | (y & z);
-const F4: fn(u32, u32, u32) -> u32 = |x, y, z| x ^ y ^ z;
+const fn f1(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | ((!x) & z)
+}
+const fn f2(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+const fn f3(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (x & z) | (y & z)
+}
+const fn f4(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
 
 // round constants
 const K1: u32 = 0x5a827999;
@@ -15,42 +31,61 @@ const K3: u32 = 0x8f1bbcdc;
 const K4: u32 = 0xca62c1d6;
 
 // buffer 2 initial constants
-const H0: u32 = 0x67452301;
-const H1: u32 = 0xefcdab89;
-const H2: u32 = 0x98badcfe;
-const H3: u32 = 0x10325476;
-const H4: u32 = 0xc3d2e1f0;
+pub const H0: u32 = 0x67452301;
+pub const H1: u32 = 0xefcdab89;
+pub const H2: u32 = 0x98badcfe;
+pub const H3: u32 = 0x10325476;
+pub const H4: u32 = 0xc3d2e1f0;
 
-fn pad(message: impl AsRef<[u8]>) -> Vec<u8> {
+#[cfg(feature = "alloc")]
+pub fn pad(message: impl AsRef<[u8]>) -> Vec<u8> {
     let mut message = message.as_ref().to_vec();
-    let message_length = message.len().wrapping_mul(8) as u64;
+    message.extend_from_slice(padding(message.len() as u64).as_ref());
+
+    message
+}
 
-    // push 1 bit (little endian)
-    message.push(0x80);
+// compute the padding which follows a message of the given length (in bytes)
+pub fn padding(length: u64) -> Padding<72> {
+    padding_bits(length.wrapping_mul(8), 0)
+}
+
+// compute the padding which follows a message of the given length (in bits), starting with the last byte of the
+// message if only some of its bits are used
+pub fn padding_bits(bits: u64, last: u8) -> Padding<72> {
+    // push 1 bit right after the used (most significant) bits of the last byte
+    let used = (bits % 8) as u32;
+    let mut padding = Padding::new();
+    padding.push((last & !(0xff >> used)) | (0x80 >> used));
 
     // pad with 0 bits until length is congruent with 64 mod 56 bytes
-    while (message.len() % 64) < 56 {
-        message.push(0);
+    while ((bits / 8) as usize).wrapping_add(padding.len()) % 64 != 56 {
+        padding.push(0);
     }
 
     // append the length of the original message (big endian)
-    message.extend(message_length.to_be_bytes());
+    padding.extend(bits.to_be_bytes());
 
-    message
+    padding
 }
 
-fn step([a, b, c, d, e]: [u32; 5], words: &[u32], i: usize) -> [u32; 5] {
-    let (k, f) = match i {
-        0..=19 => (K1, F1),
-        20..=39 => (K2, F2),
-        40..=59 => (K3, F3),
-        60..=79 => (K4, F4),
+// select the round constant and function of a step
+const fn round(i: usize, x: u32, y: u32, z: u32) -> (u32, u32) {
+    match i {
+        0..=19 => (K1, f1(x, y, z)),
+        20..=39 => (K2, f2(x, y, z)),
+        40..=59 => (K3, f3(x, y, z)),
+        60..=79 => (K4, f4(x, y, z)),
         _ => panic!("step function should not be called with index outside of range 0..80"),
-    };
+    }
+}
+
+pub const fn step([a, b, c, d, e]: [u32; 5], words: &[u32], i: usize) -> [u32; 5] {
+    let (k, f) = round(i, b, c, d);
 
     [
         a.rotate_left(5)
-            .wrapping_add(f(b, c, d))
+            .wrapping_add(f)
             .wrapping_add(e)
             .wrapping_add(k)
             .wrapping_add(words[i]),
@@ -61,6 +96,20 @@ fn step([a, b, c, d, e]: [u32; 5], words: &[u32], i: usize) -> [u32; 5] {
     ]
 }
 
+// undo a single step, recovering the state before it from the state after it
+pub const fn unstep([t, a, b, c, d]: [u32; 5], words: &[u32], i: usize) -> [u32; 5] {
+    let b = b.rotate_right(30);
+    let (k, f) = round(i, b, c, d);
+    let e = t.wrapping_sub(
+        a.rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(k)
+            .wrapping_add(words[i]),
+    );
+
+    [a, b, c, d, e]
+}
+
 /// Computes the SHA1 hash value (digest) of the input bytes.
 ///
 /// Returns a 20-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
@@ -76,49 +125,334 @@ fn step([a, b, c, d, e]: [u32; 5], words: &[u32], i: usize) -> [u32; 5] {
 /// assert_eq!(digest.to_string(), "a9993e364706816aba3e25717850c26c9cd0d89d")
 /// ```
 pub fn hash(message: impl AsRef<[u8]>) -> Digest<20> {
+    let mut hasher = Sha1::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the SHA1 hash value (digest) of a message whose length in bits is not necessarily a multiple of 8.
+///
+/// The message consists of the first `bits` bits of the input bytes, most significant bit first, and any bits after
+/// them are ignored.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// // the five bits 10011
+/// let digest = lore::sha1_bits([0x98], 5);
+///
+/// assert_eq!(digest.to_string(), "29826b003b906e660eff4027ce98af3531ac75ba");
+/// assert_eq!(lore::sha1_bits("abc", 24), lore::sha1("abc"));
+/// ```
+pub fn hash_bits(message: impl AsRef<[u8]>, bits: u64) -> Digest<20> {
+    let message = message.as_ref();
+    assert!(
+        bits <= (message.len() as u64).wrapping_mul(8),
+        "The message must have at least as many bits as its length"
+    );
+
+    let whole = (bits / 8) as usize;
+    let mut hasher = Sha1::new();
+    hasher.update(&message[..whole]);
+    hasher.finalize_bits(message.get(whole).copied().unwrap_or(0), (bits % 8) as u32)
+}
+
+/// Computes the SHA1 hash value (digest) of the input bytes in a const context, e.g. to embed digests in constants.
+///
+/// This gives the same digest as [`sha1`](crate::sha1), but is slower at runtime.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// const DIGEST: lore::Digest<20> = lore::sha1_const(b"abc");
+///
+/// assert_eq!(DIGEST.to_string(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+/// ```
+#[must_use]
+pub const fn hash_const(message: &[u8]) -> Digest<20> {
+    let length = (message.len() as u64).wrapping_mul(8).to_be_bytes();
+    let mut buffer = [H0, H1, H2, H3, H4];
+    let mut index = 0;
+    while index < padded_blocks(message.len()) {
+        buffer = compress_const(buffer, &padded_block(message, index, length));
+        index += 1;
+    }
+
+    Digest(words_to_bytes_be_const(&buffer))
+}
+
+/// Computes the SHA1 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::sha1("abc", &mut events);
+///
+/// assert_eq!(digest, lore::sha1("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<20> {
     let padded = pad(message);
+    let buffer = compress_blocks(
+        &padded,
+        [H0, H1, H2, H3, H4],
+        observer,
+        bytes_to_words_be::<16>,
+        |buffer, block, observer| compress_observed(buffer, &schedule(block), 80, observer),
+    );
 
-    let buffer = padded
-        .array_chunks::<64>()
-        .map(|chunk| bytes_to_words_be(*chunk))
-        .fold([H0, H1, H2, H3, H4], |[a, b, c, d, e], mut words| {
-            // extend 16 words to 80 words
-            for i in 16..80 {
-                words.push(
-                    (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1),
-                );
-            }
+    let digest = words_to_bytes_be(buffer);
+
+    trace::digest(Digest(digest), observer)
+}
+
+// expand the 16 words of a block into the 80 word message schedule
+pub fn schedule(block: &[u8; 64]) -> [u32; 80] {
+    expand(bytes_to_words_be(block))
+}
+
+// expand the 16 words of a block into the 80 word message schedule of SHA-0, which differs from SHA-1 only in the
+// missing rotation
+#[cfg(feature = "alloc")]
+pub fn schedule_sha0(block: &[u8; 64]) -> [u32; 80] {
+    recurrence(bytes_to_words_be(block), 0)
+}
+
+// extend 16 words to 80 words using the linear recurrence of the message schedule
+pub const fn expand(block: [u32; 16]) -> [u32; 80] {
+    recurrence(block, 1)
+}
 
-            // initialize state
-            let mut state = [a, b, c, d, e];
+const fn recurrence(block: [u32; 16], rotation: u32) -> [u32; 80] {
+    let mut words = [0; 80];
+    let mut i = 0;
+    while i < 80 {
+        words[i] = if i < 16 {
+            block[i]
+        } else {
+            (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(rotation)
+        };
+        i += 1;
+    }
+
+    words
+}
+
+// process a single 512-bit block of the padded message
+pub fn compress(buffer: [u32; 5], block: &[u8; 64]) -> [u32; 5] {
+    compress_observed(buffer, &schedule(block), 80, &mut ())
+}
 
-            // perform 80 steps
-            for i in 0..80 {
-                state = step(state, &words, i);
+// process a single 512-bit block of the padded message in a const context, which can't report the steps to an
+// observer
+const fn compress_const([a, b, c, d, e]: [u32; 5], block: &[u8; 64]) -> [u32; 5] {
+    let words = expand(bytes_to_words_be_const(block));
+
+    let mut state = [a, b, c, d, e];
+    let mut i = 0;
+    while i < 80 {
+        state = step(state, &words, i);
+        i += 1;
+    }
+
+    [
+        a.wrapping_add(state[0]),
+        b.wrapping_add(state[1]),
+        c.wrapping_add(state[2]),
+        d.wrapping_add(state[3]),
+        e.wrapping_add(state[4]),
+    ]
+}
+
+// process a single block, given as its message schedule, with the given number of steps, reporting each step to the
+// observer
+pub fn compress_observed<O: Observer + ?Sized>(
+    [a, b, c, d, e]: [u32; 5],
+    words: &[u32],
+    steps: usize,
+    observer: &mut O,
+) -> [u32; 5] {
+    emit!(
+        observer,
+        Event::Schedule {
+            words: words.to_vec().into(),
+        }
+    );
+
+    // initialize state
+    let mut state = [a, b, c, d, e];
+
+    // perform the steps, 80 in full SHA-1
+    for i in 0..steps {
+        state = step(state, words, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: state.into(),
             }
+        );
+    }
 
-            // add computed round state to buffer
-            [
-                a.wrapping_add(state[0]),
-                b.wrapping_add(state[1]),
-                c.wrapping_add(state[2]),
-                d.wrapping_add(state[3]),
-                e.wrapping_add(state[4]),
-            ]
-        });
+    // add computed round state to buffer
+    let buffer = [
+        a.wrapping_add(state[0]),
+        b.wrapping_add(state[1]),
+        c.wrapping_add(state[2]),
+        d.wrapping_add(state[3]),
+        e.wrapping_add(state[4]),
+    ];
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: [a, b, c, d, e].into(),
+            working: state.into(),
+            next: buffer.into(),
+        }
+    );
+
+    buffer
+}
+
+/// A streaming SHA1 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Sha1::new();
+/// hasher.update("a");
+/// hasher.update("bc");
+///
+/// assert_eq!(hasher.finalize(), lore::sha1("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: Buffer<64>,
+}
+
+impl Sha1 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: [H0, H1, H2, H3, H4],
+            buffer: Buffer::new(),
+        }
+    }
+
+    /// Continue hashing after a message of which only the digest and the length (in bytes) are known.
+    ///
+    /// The hasher is left in the same state as after hashing the message and its padding, so the digest covers the
+    /// message, its padding and everything passed to [`Sha1::update`] afterwards.
+    #[cfg_attr(
+        feature = "alloc",
+        doc = "This is the basis of length extension attacks, see the [`length_extension`](crate::length_extension) module."
+    )]
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let mut hasher = lore::Sha1::resume(&lore::sha1("abc"), 3);
+    /// hasher.update("def");
+    ///
+    /// let mut message = b"abc".to_vec();
+    /// message.push(0x80);
+    /// message.resize(56, 0);
+    /// message.extend(24u64.to_be_bytes());
+    /// message.extend(b"def");
+    ///
+    /// assert_eq!(hasher.finalize(), lore::sha1(message));
+    /// ```
+    #[must_use]
+    pub fn resume(digest: &Digest<20>, length: u64) -> Self {
+        let padded = length.wrapping_add(padding(length).len() as u64);
+
+        Self {
+            state: bytes_to_words_be(digest),
+            buffer: Buffer::with_length(padded),
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer } = self;
+        buffer.update(data.as_ref(), |block| *state = compress(*state, block));
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Sha1::update`].
+    #[must_use]
+    pub fn finalize(self) -> Digest<20> {
+        self.finalize_bits(0, 0)
+    }
+
+    /// Append the `bits` most significant bits of `last` to the message, then pad it and return the digest.
+    ///
+    /// This finishes messages whose length is not a multiple of 8 bits, whose last byte is only partially used.
+    /// Panics if `bits` is not less than 8.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let mut hasher = lore::Sha1::new();
+    /// hasher.update("ab");
+    ///
+    /// assert_eq!(hasher.finalize_bits(0x98, 5), lore::sha1_bits([b'a', b'b', 0x98], 21));
+    /// ```
+    #[must_use]
+    pub fn finalize_bits(mut self, last: u8, bits: u32) -> Digest<20> {
+        assert!(bits < 8, "The last byte must have fewer than 8 bits");
+        let length = self.buffer.length().wrapping_mul(8);
+        self.update(padding_bits(length.wrapping_add(u64::from(bits)), last));
+        let digest = words_to_bytes_be(self.state);
+
+        Digest(digest)
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Sha1 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 20;
 
-    let digest = *words_to_bytes_be(buffer)
-        .array_chunks::<20>()
-        .next()
-        .unwrap();
+    type Output = Digest<20>;
 
-    Digest(digest)
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<20> {
+        self.finalize()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn sha1_pad() {
         let expected: [u8; 64] = [
@@ -133,6 +467,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sha1_hash_bits() {
+        let message: Vec<u8> = (0x41..0x87).collect();
+
+        assert_eq!(
+            "29826b003b906e660eff4027ce98af3531ac75ba",
+            hash_bits([0x98], 5).to_string()
+        );
+        assert_eq!(
+            "7fb3294a88d8fbad616a62321d952d2c5b7cd426",
+            hash_bits(&message, 555).to_string()
+        );
+        assert_eq!(hash(&message), hash_bits(&message, 560));
+
+        let mut hasher = Sha1::new();
+        hasher.update(&message[..69]);
+        assert_eq!(
+            hash_bits(&message, 555),
+            hasher.finalize_bits(message[69], 3)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sha1_trace() {
+        let mut events = Vec::new();
+        let digest = trace("abc", &mut events);
+
+        let schedule = events.iter().find_map(|event| match event {
+            Event::Schedule { words } => Some(words.to_u64s()),
+            _ => None,
+        });
+        let schedule = schedule.unwrap();
+        assert_eq!(80, schedule.len());
+        assert_eq!(
+            [0xc2c4c700, 0, 0x30, 0x85898e01, 0, 0x60, 0x0b131c03, 0x30],
+            schedule[16..24]
+        );
+        assert_eq!(0x822e0879, schedule[79]);
+
+        let steps = events
+            .iter()
+            .filter(|event| matches!(event, Event::Step { .. }))
+            .count();
+        assert_eq!(80, steps);
+
+        // the chaining value after the only block is the digest
+        let next = events.iter().find_map(|event| match event {
+            Event::ChainingValue { next, .. } => Some(next.clone()),
+            _ => None,
+        });
+        assert_eq!(Some(bytes_to_words_be::<5>(digest).into()), next);
+    }
+
     #[test]
     fn sha1_hash() {
         assert_eq!(
@@ -145,4 +533,35 @@ mod tests {
             "a9993e364706816aba3e25717850c26c9cd0d89d"
         );
     }
+
+    #[test]
+    fn sha1_const() {
+        const DIGEST: Digest<20> = hash_const(b"abc");
+        assert_eq!(
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+            DIGEST.to_string()
+        );
+
+        // every length around the block boundaries, where the padding spills into another block
+        let message: [u8; 200] = core::array::from_fn(|i| i as u8);
+        for length in 0..=message.len() {
+            assert_eq!(hash(&message[..length]), hash_const(&message[..length]));
+        }
+    }
+
+    #[test]
+    fn sha1_streaming() {
+        let message = [0x3cu8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Sha1::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+
+        let mut hasher = Sha1::new();
+        message.chunks(7).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hash(message), hasher.finalize());
+        assert_eq!(hash(""), Sha1::new().finalize());
+    }
 }
@@ -0,0 +1,639 @@
+use crate::hash::sha1::{compress, expand, padding, schedule, step, unstep, H0, H1, H2, H3, H4};
+use crate::hash::{words_to_bytes_be, Buffer, Digest, Hasher};
+
+// based on "Counter-cryptanalysis" by Marc Stevens (CRYPTO 2013), and the disturbance vectors of the sha1collisiondetection
+// library by Marc Stevens and Dan Shumow
+
+// the disturbance vectors used by known near-collision attacks: the step from which the state is recompressed, and the
+// first 16 words of the xor-difference between the two message blocks (the other 64 follow from the message schedule)
+const DISTURBANCE_VECTORS: [(usize, [u32; 16]); 32] = [
+    // I(43,0)
+    (
+        58,
+        [
+            0x08000000, 0x9800000c, 0xd8000010, 0x08000010, 0xb8000010, 0x98000000, 0x60000000,
+            0x00000008, 0xc0000000, 0x90000014, 0x10000010, 0xb8000014, 0x28000000, 0x20000010,
+            0x48000000, 0x08000018,
+        ],
+    ),
+    // I(44,0)
+    (
+        58,
+        [
+            0xb4000008, 0x08000000, 0x9800000c, 0xd8000010, 0x08000010, 0xb8000010, 0x98000000,
+            0x60000000, 0x00000008, 0xc0000000, 0x90000014, 0x10000010, 0xb8000014, 0x28000000,
+            0x20000010, 0x48000000,
+        ],
+    ),
+    // I(45,0)
+    (
+        58,
+        [
+            0xf4000014, 0xb4000008, 0x08000000, 0x9800000c, 0xd8000010, 0x08000010, 0xb8000010,
+            0x98000000, 0x60000000, 0x00000008, 0xc0000000, 0x90000014, 0x10000010, 0xb8000014,
+            0x28000000, 0x20000010,
+        ],
+    ),
+    // I(46,0)
+    (
+        58,
+        [
+            0x2c000010, 0xf4000014, 0xb4000008, 0x08000000, 0x9800000c, 0xd8000010, 0x08000010,
+            0xb8000010, 0x98000000, 0x60000000, 0x00000008, 0xc0000000, 0x90000014, 0x10000010,
+            0xb8000014, 0x28000000,
+        ],
+    ),
+    // I(46,2)
+    (
+        58,
+        [
+            0xb0000040, 0xd0000053, 0xd0000022, 0x20000000, 0x60000032, 0x60000043, 0x20000040,
+            0xe0000042, 0x60000002, 0x80000001, 0x00000020, 0x00000003, 0x40000052, 0x40000040,
+            0xe0000052, 0xa0000000,
+        ],
+    ),
+    // I(47,0)
+    (
+        58,
+        [
+            0xc8000010, 0x2c000010, 0xf4000014, 0xb4000008, 0x08000000, 0x9800000c, 0xd8000010,
+            0x08000010, 0xb8000010, 0x98000000, 0x60000000, 0x00000008, 0xc0000000, 0x90000014,
+            0x10000010, 0xb8000014,
+        ],
+    ),
+    // I(47,2)
+    (
+        58,
+        [
+            0x20000043, 0xb0000040, 0xd0000053, 0xd0000022, 0x20000000, 0x60000032, 0x60000043,
+            0x20000040, 0xe0000042, 0x60000002, 0x80000001, 0x00000020, 0x00000003, 0x40000052,
+            0x40000040, 0xe0000052,
+        ],
+    ),
+    // I(48,0)
+    (
+        58,
+        [
+            0xb800000a, 0xc8000010, 0x2c000010, 0xf4000014, 0xb4000008, 0x08000000, 0x9800000c,
+            0xd8000010, 0x08000010, 0xb8000010, 0x98000000, 0x60000000, 0x00000008, 0xc0000000,
+            0x90000014, 0x10000010,
+        ],
+    ),
+    // I(48,2)
+    (
+        58,
+        [
+            0xe000002a, 0x20000043, 0xb0000040, 0xd0000053, 0xd0000022, 0x20000000, 0x60000032,
+            0x60000043, 0x20000040, 0xe0000042, 0x60000002, 0x80000001, 0x00000020, 0x00000003,
+            0x40000052, 0x40000040,
+        ],
+    ),
+    // I(49,0)
+    (
+        58,
+        [
+            0x18000000, 0xb800000a, 0xc8000010, 0x2c000010, 0xf4000014, 0xb4000008, 0x08000000,
+            0x9800000c, 0xd8000010, 0x08000010, 0xb8000010, 0x98000000, 0x60000000, 0x00000008,
+            0xc0000000, 0x90000014,
+        ],
+    ),
+    // I(49,2)
+    (
+        58,
+        [
+            0x60000000, 0xe000002a, 0x20000043, 0xb0000040, 0xd0000053, 0xd0000022, 0x20000000,
+            0x60000032, 0x60000043, 0x20000040, 0xe0000042, 0x60000002, 0x80000001, 0x00000020,
+            0x00000003, 0x40000052,
+        ],
+    ),
+    // I(50,0)
+    (
+        65,
+        [
+            0x0800000c, 0x18000000, 0xb800000a, 0xc8000010, 0x2c000010, 0xf4000014, 0xb4000008,
+            0x08000000, 0x9800000c, 0xd8000010, 0x08000010, 0xb8000010, 0x98000000, 0x60000000,
+            0x00000008, 0xc0000000,
+        ],
+    ),
+    // I(50,2)
+    (
+        65,
+        [
+            0x20000030, 0x60000000, 0xe000002a, 0x20000043, 0xb0000040, 0xd0000053, 0xd0000022,
+            0x20000000, 0x60000032, 0x60000043, 0x20000040, 0xe0000042, 0x60000002, 0x80000001,
+            0x00000020, 0x00000003,
+        ],
+    ),
+    // I(51,0)
+    (
+        65,
+        [
+            0xe8000000, 0x0800000c, 0x18000000, 0xb800000a, 0xc8000010, 0x2c000010, 0xf4000014,
+            0xb4000008, 0x08000000, 0x9800000c, 0xd8000010, 0x08000010, 0xb8000010, 0x98000000,
+            0x60000000, 0x00000008,
+        ],
+    ),
+    // I(51,2)
+    (
+        65,
+        [
+            0xa0000003, 0x20000030, 0x60000000, 0xe000002a, 0x20000043, 0xb0000040, 0xd0000053,
+            0xd0000022, 0x20000000, 0x60000032, 0x60000043, 0x20000040, 0xe0000042, 0x60000002,
+            0x80000001, 0x00000020,
+        ],
+    ),
+    // I(52,0)
+    (
+        65,
+        [
+            0x04000010, 0xe8000000, 0x0800000c, 0x18000000, 0xb800000a, 0xc8000010, 0x2c000010,
+            0xf4000014, 0xb4000008, 0x08000000, 0x9800000c, 0xd8000010, 0x08000010, 0xb8000010,
+            0x98000000, 0x60000000,
+        ],
+    ),
+    // II(45,0)
+    (
+        58,
+        [
+            0xec000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x2c000004, 0xbc000018, 0xb0000010,
+            0x0000000c, 0xb8000010, 0x08000018, 0x78000010, 0x08000014, 0x70000010, 0xb800001c,
+            0xe8000000, 0xb0000004,
+        ],
+    ),
+    // II(46,0)
+    (
+        58,
+        [
+            0x2400001c, 0xec000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x2c000004, 0xbc000018,
+            0xb0000010, 0x0000000c, 0xb8000010, 0x08000018, 0x78000010, 0x08000014, 0x70000010,
+            0xb800001c, 0xe8000000,
+        ],
+    ),
+    // II(46,2)
+    (
+        58,
+        [
+            0x90000070, 0xb0000053, 0x30000008, 0x00000043, 0xd0000072, 0xb0000010, 0xf0000062,
+            0xc0000042, 0x00000030, 0xe0000042, 0x20000060, 0xe0000041, 0x20000050, 0xc0000041,
+            0xe0000072, 0xa0000003,
+        ],
+    ),
+    // II(47,0)
+    (
+        58,
+        [
+            0x20000010, 0x2400001c, 0xec000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x2c000004,
+            0xbc000018, 0xb0000010, 0x0000000c, 0xb8000010, 0x08000018, 0x78000010, 0x08000014,
+            0x70000010, 0xb800001c,
+        ],
+    ),
+    // II(48,0)
+    (
+        58,
+        [
+            0xbc00001a, 0x20000010, 0x2400001c, 0xec000014, 0x0c000002, 0xc0000010, 0xb400001c,
+            0x2c000004, 0xbc000018, 0xb0000010, 0x0000000c, 0xb8000010, 0x08000018, 0x78000010,
+            0x08000014, 0x70000010,
+        ],
+    ),
+    // II(49,0)
+    (
+        58,
+        [
+            0x3c000004, 0xbc00001a, 0x20000010, 0x2400001c, 0xec000014, 0x0c000002, 0xc0000010,
+            0xb400001c, 0x2c000004, 0xbc000018, 0xb0000010, 0x0000000c, 0xb8000010, 0x08000018,
+            0x78000010, 0x08000014,
+        ],
+    ),
+    // II(49,2)
+    (
+        58,
+        [
+            0xf0000010, 0xf000006a, 0x80000040, 0x90000070, 0xb0000053, 0x30000008, 0x00000043,
+            0xd0000072, 0xb0000010, 0xf0000062, 0xc0000042, 0x00000030, 0xe0000042, 0x20000060,
+            0xe0000041, 0x20000050,
+        ],
+    ),
+    // II(50,0)
+    (
+        65,
+        [
+            0xb400001c, 0x3c000004, 0xbc00001a, 0x20000010, 0x2400001c, 0xec000014, 0x0c000002,
+            0xc0000010, 0xb400001c, 0x2c000004, 0xbc000018, 0xb0000010, 0x0000000c, 0xb8000010,
+            0x08000018, 0x78000010,
+        ],
+    ),
+    // II(50,2)
+    (
+        65,
+        [
+            0xd0000072, 0xf0000010, 0xf000006a, 0x80000040, 0x90000070, 0xb0000053, 0x30000008,
+            0x00000043, 0xd0000072, 0xb0000010, 0xf0000062, 0xc0000042, 0x00000030, 0xe0000042,
+            0x20000060, 0xe0000041,
+        ],
+    ),
+    // II(51,0)
+    (
+        65,
+        [
+            0xc0000010, 0xb400001c, 0x3c000004, 0xbc00001a, 0x20000010, 0x2400001c, 0xec000014,
+            0x0c000002, 0xc0000010, 0xb400001c, 0x2c000004, 0xbc000018, 0xb0000010, 0x0000000c,
+            0xb8000010, 0x08000018,
+        ],
+    ),
+    // II(51,2)
+    (
+        65,
+        [
+            0x00000043, 0xd0000072, 0xf0000010, 0xf000006a, 0x80000040, 0x90000070, 0xb0000053,
+            0x30000008, 0x00000043, 0xd0000072, 0xb0000010, 0xf0000062, 0xc0000042, 0x00000030,
+            0xe0000042, 0x20000060,
+        ],
+    ),
+    // II(52,0)
+    (
+        65,
+        [
+            0x0c000002, 0xc0000010, 0xb400001c, 0x3c000004, 0xbc00001a, 0x20000010, 0x2400001c,
+            0xec000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x2c000004, 0xbc000018, 0xb0000010,
+            0x0000000c, 0xb8000010,
+        ],
+    ),
+    // II(53,0)
+    (
+        65,
+        [
+            0xcc000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x3c000004, 0xbc00001a, 0x20000010,
+            0x2400001c, 0xec000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x2c000004, 0xbc000018,
+            0xb0000010, 0x0000000c,
+        ],
+    ),
+    // II(54,0)
+    (
+        65,
+        [
+            0x0400001c, 0xcc000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x3c000004, 0xbc00001a,
+            0x20000010, 0x2400001c, 0xec000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x2c000004,
+            0xbc000018, 0xb0000010,
+        ],
+    ),
+    // II(55,0)
+    (
+        65,
+        [
+            0x00000010, 0x0400001c, 0xcc000014, 0x0c000002, 0xc0000010, 0xb400001c, 0x3c000004,
+            0xbc00001a, 0x20000010, 0x2400001c, 0xec000014, 0x0c000002, 0xc0000010, 0xb400001c,
+            0x2c000004, 0xbc000018,
+        ],
+    ),
+    // II(56,0)
+    (
+        65,
+        [
+            0x2600001a, 0x00000010, 0x0400001c, 0xcc000014, 0x0c000002, 0xc0000010, 0xb400001c,
+            0x3c000004, 0xbc00001a, 0x20000010, 0x2400001c, 0xec000014, 0x0c000002, 0xc0000010,
+            0xb400001c, 0x2c000004,
+        ],
+    ),
+];
+
+/// The result of hashing a message with SHA1 collision detection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    /// The digest, which is the SHA1 digest unless a collision was detected while computing the safe hash.
+    pub digest: Digest<20>,
+    /// Whether any block of the message is one half of a near-collision built from a known disturbance vector.
+    pub collision: bool,
+}
+
+/// Computes the SHA1 hash value (digest) of the input bytes, and detects whether the message contains a block crafted
+/// by a known collision attack, such as SHAttered.
+///
+/// The digest is always the regular SHA1 digest, see [`safe_hash`] for a digest which differs for colliding messages.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let detection = lore::sha1dc("abc");
+///
+/// assert_eq!(detection.digest, lore::sha1("abc"));
+/// assert!(!detection.collision);
+/// ```
+pub fn hash(message: impl AsRef<[u8]>) -> Detection {
+    let mut hasher = Sha1Dc::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the "safe" SHA1 hash value (digest) of the input bytes, detecting known collision attacks.
+///
+/// Each block for which a collision is detected is compressed three times instead of once, which gives the two
+/// colliding messages different digests, while every other message keeps its regular SHA1 digest.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let detection = lore::sha1dc_safe("abc");
+///
+/// assert_eq!(detection.digest.to_string(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+/// assert!(!detection.collision);
+/// ```
+pub fn safe_hash(message: impl AsRef<[u8]>) -> Detection {
+    let mut hasher = Sha1Dc::with_safe_hash();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+// add the working state to the buffer
+fn add(buffer: [u32; 5], state: [u32; 5]) -> [u32; 5] {
+    let mut buffer = buffer;
+    buffer
+        .iter_mut()
+        .zip(state)
+        .for_each(|(b, s)| *b = b.wrapping_add(s));
+
+    buffer
+}
+
+// process a single 512-bit block of the padded message, checking whether it is one half of a near-collision, where
+// every disturbance vector is tried without filtering them by unavoidable bit conditions first
+fn compress_detect(buffer: [u32; 5], block: &[u8; 64]) -> ([u32; 5], bool) {
+    let words = schedule(block);
+
+    // keep the state before every step, since recompression starts in the middle of the block
+    let mut states = [buffer; 81];
+    for i in 0..80 {
+        states[i + 1] = step(states[i], &words, i);
+    }
+    let next = add(buffer, states[80]);
+
+    // for each disturbance vector, compute the block which an attacker would have paired with this one, and check whether
+    // both blocks produce the same chaining value from the same intermediate state
+    let collision = DISTURBANCE_VECTORS.iter().any(|&(from, difference)| {
+        let difference = expand(difference);
+        let words: [u32; 80] = core::array::from_fn(|i| words[i] ^ difference[i]);
+
+        let previous = (0..from)
+            .rev()
+            .fold(states[from], |state, i| unstep(state, &words, i));
+        let working = (from..80).fold(states[from], |state, i| step(state, &words, i));
+
+        add(previous, working) == next
+    });
+
+    (next, collision)
+}
+
+/// A streaming SHA1 hasher with collision detection, for hashing messages which are not available all at once.
+///
+/// Every block is checked against all 32 known disturbance vectors by recompressing it once per vector, which makes
+/// this about 30 times slower than [`Sha1`](crate::Sha1), e.g. 750 ms instead of 24 ms for 4 MiB with an optimized
+/// build.
+/// The reference implementation of Stevens and Shumow first filters the vectors with unavoidable bit conditions (UBCs)
+/// on the message words, and only recompresses for the few which remain, which this doesn't do.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Sha1Dc::new();
+/// hasher.update("a");
+/// hasher.update("bc");
+///
+/// assert_eq!(hasher.finalize(), lore::sha1dc("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sha1Dc {
+    state: [u32; 5],
+    buffer: Buffer<64>,
+    safe: bool,
+    collision: bool,
+}
+
+impl Sha1Dc {
+    /// Create a new hasher with an empty message, which computes the regular SHA1 digest.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: [H0, H1, H2, H3, H4],
+            buffer: Buffer::new(),
+            safe: false,
+            collision: false,
+        }
+    }
+
+    /// Create a new hasher with an empty message, which computes the safe hash, see [`safe_hash`].
+    #[must_use]
+    pub fn with_safe_hash() -> Self {
+        Self {
+            safe: true,
+            ..Self::new()
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self {
+            state,
+            buffer,
+            safe,
+            collision,
+        } = self;
+        buffer.update(data.as_ref(), |block| {
+            let (next, detected) = compress_detect(*state, block);
+            *state = next;
+
+            // compress a colliding block twice more, so that its partner no longer collides
+            if detected && *safe {
+                *state = compress(compress(*state, block), block);
+            }
+            *collision |= detected;
+        });
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Sha1Dc::update`], and whether a collision was
+    /// detected.
+    #[must_use]
+    pub fn finalize(mut self) -> Detection {
+        self.update(padding(self.buffer.length()));
+        Detection {
+            digest: Digest(words_to_bytes_be(self.state)),
+            collision: self.collision,
+        }
+    }
+}
+
+impl Default for Sha1Dc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// the digest alone, for generic code, which can't tell whether a collision was detected
+impl Hasher for Sha1Dc {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 20;
+
+    type Output = Digest<20>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<20> {
+        self.finalize().digest
+    }
+
+    fn reset(&mut self) {
+        *self = Self {
+            safe: self.safe,
+            ..Self::new()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the first 192 bytes which both SHAttered PDFs share
+    const PREFIX: [u8; 192] = [
+        0x25, 0x50, 0x44, 0x46, 0x2d, 0x31, 0x2e, 0x33, 0x0a, 0x25, 0xe2, 0xe3, 0xcf, 0xd3, 0x0a,
+        0x0a, 0x0a, 0x31, 0x20, 0x30, 0x20, 0x6f, 0x62, 0x6a, 0x0a, 0x3c, 0x3c, 0x2f, 0x57, 0x69,
+        0x64, 0x74, 0x68, 0x20, 0x32, 0x20, 0x30, 0x20, 0x52, 0x2f, 0x48, 0x65, 0x69, 0x67, 0x68,
+        0x74, 0x20, 0x33, 0x20, 0x30, 0x20, 0x52, 0x2f, 0x54, 0x79, 0x70, 0x65, 0x20, 0x34, 0x20,
+        0x30, 0x20, 0x52, 0x2f, 0x53, 0x75, 0x62, 0x74, 0x79, 0x70, 0x65, 0x20, 0x35, 0x20, 0x30,
+        0x20, 0x52, 0x2f, 0x46, 0x69, 0x6c, 0x74, 0x65, 0x72, 0x20, 0x36, 0x20, 0x30, 0x20, 0x52,
+        0x2f, 0x43, 0x6f, 0x6c, 0x6f, 0x72, 0x53, 0x70, 0x61, 0x63, 0x65, 0x20, 0x37, 0x20, 0x30,
+        0x20, 0x52, 0x2f, 0x4c, 0x65, 0x6e, 0x67, 0x74, 0x68, 0x20, 0x38, 0x20, 0x30, 0x20, 0x52,
+        0x2f, 0x42, 0x69, 0x74, 0x73, 0x50, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70, 0x6f, 0x6e, 0x65,
+        0x6e, 0x74, 0x20, 0x38, 0x3e, 0x3e, 0x0a, 0x73, 0x74, 0x72, 0x65, 0x61, 0x6d, 0x0a, 0xff,
+        0xd8, 0xff, 0xfe, 0x00, 0x24, 0x53, 0x48, 0x41, 0x2d, 0x31, 0x20, 0x69, 0x73, 0x20, 0x64,
+        0x65, 0x61, 0x64, 0x21, 0x21, 0x21, 0x21, 0x21, 0x85, 0x2f, 0xec, 0x09, 0x23, 0x39, 0x75,
+        0x9c, 0x39, 0xb1, 0xa1, 0xc6, 0x3c, 0x4c, 0x97, 0xe1, 0xff, 0xfe, 0x01,
+    ];
+
+    // the two near-collision blocks of the first PDF
+    const BLOCKS_1: [u8; 128] = [
+        0x73, 0x46, 0xdc, 0x91, 0x66, 0xb6, 0x7e, 0x11, 0x8f, 0x02, 0x9a, 0xb6, 0x21, 0xb2, 0x56,
+        0x0f, 0xf9, 0xca, 0x67, 0xcc, 0xa8, 0xc7, 0xf8, 0x5b, 0xa8, 0x4c, 0x79, 0x03, 0x0c, 0x2b,
+        0x3d, 0xe2, 0x18, 0xf8, 0x6d, 0xb3, 0xa9, 0x09, 0x01, 0xd5, 0xdf, 0x45, 0xc1, 0x4f, 0x26,
+        0xfe, 0xdf, 0xb3, 0xdc, 0x38, 0xe9, 0x6a, 0xc2, 0x2f, 0xe7, 0xbd, 0x72, 0x8f, 0x0e, 0x45,
+        0xbc, 0xe0, 0x46, 0xd2, 0x3c, 0x57, 0x0f, 0xeb, 0x14, 0x13, 0x98, 0xbb, 0x55, 0x2e, 0xf5,
+        0xa0, 0xa8, 0x2b, 0xe3, 0x31, 0xfe, 0xa4, 0x80, 0x37, 0xb8, 0xb5, 0xd7, 0x1f, 0x0e, 0x33,
+        0x2e, 0xdf, 0x93, 0xac, 0x35, 0x00, 0xeb, 0x4d, 0xdc, 0x0d, 0xec, 0xc1, 0xa8, 0x64, 0x79,
+        0x0c, 0x78, 0x2c, 0x76, 0x21, 0x56, 0x60, 0xdd, 0x30, 0x97, 0x91, 0xd0, 0x6b, 0xd0, 0xaf,
+        0x3f, 0x98, 0xcd, 0xa4, 0xbc, 0x46, 0x29, 0xb1,
+    ];
+
+    // the two near-collision blocks of the second PDF
+    const BLOCKS_2: [u8; 128] = [
+        0x7f, 0x46, 0xdc, 0x93, 0xa6, 0xb6, 0x7e, 0x01, 0x3b, 0x02, 0x9a, 0xaa, 0x1d, 0xb2, 0x56,
+        0x0b, 0x45, 0xca, 0x67, 0xd6, 0x88, 0xc7, 0xf8, 0x4b, 0x8c, 0x4c, 0x79, 0x1f, 0xe0, 0x2b,
+        0x3d, 0xf6, 0x14, 0xf8, 0x6d, 0xb1, 0x69, 0x09, 0x01, 0xc5, 0x6b, 0x45, 0xc1, 0x53, 0x0a,
+        0xfe, 0xdf, 0xb7, 0x60, 0x38, 0xe9, 0x72, 0x72, 0x2f, 0xe7, 0xad, 0x72, 0x8f, 0x0e, 0x49,
+        0x04, 0xe0, 0x46, 0xc2, 0x30, 0x57, 0x0f, 0xe9, 0xd4, 0x13, 0x98, 0xab, 0xe1, 0x2e, 0xf5,
+        0xbc, 0x94, 0x2b, 0xe3, 0x35, 0x42, 0xa4, 0x80, 0x2d, 0x98, 0xb5, 0xd7, 0x0f, 0x2a, 0x33,
+        0x2e, 0xc3, 0x7f, 0xac, 0x35, 0x14, 0xe7, 0x4d, 0xdc, 0x0f, 0x2c, 0xc1, 0xa8, 0x74, 0xcd,
+        0x0c, 0x78, 0x30, 0x5a, 0x21, 0x56, 0x64, 0x61, 0x30, 0x97, 0x89, 0x60, 0x6b, 0xd0, 0xbf,
+        0x3f, 0x98, 0xcd, 0xa8, 0x04, 0x46, 0x29, 0xa1,
+    ];
+
+    fn shattered(blocks: &[u8; 128]) -> Vec<u8> {
+        [&PREFIX[..], blocks].concat()
+    }
+
+    #[test]
+    fn sha1dc_unstep() {
+        let words = schedule(&[0x5a; 64]);
+        let mut state = [H0, H1, H2, H3, H4];
+        for i in 0..80 {
+            let next = step(state, &words, i);
+            assert_eq!(state, unstep(next, &words, i));
+            state = next;
+        }
+    }
+
+    #[test]
+    fn sha1dc_hash() {
+        for message in [&b""[..], b"abc", &[0x42; 1000], &PREFIX] {
+            let detection = hash(message);
+            assert_eq!(crate::sha1(message), detection.digest);
+            assert!(!detection.collision);
+            assert_eq!(detection, safe_hash(message));
+        }
+    }
+
+    #[test]
+    fn sha1dc_shattered() {
+        let first = shattered(&BLOCKS_1);
+        let second = shattered(&BLOCKS_2);
+
+        // the prefixes are different, but collide
+        assert_ne!(first, second);
+        assert_eq!(crate::sha1(&first), crate::sha1(&second));
+        assert_eq!(
+            crate::sha1(&first).to_string(),
+            "f92d74e3874587aaf443d1db961d4e26dde13e9c"
+        );
+
+        for message in [&first, &second] {
+            let detection = hash(message);
+            assert!(detection.collision);
+            assert_eq!(crate::sha1(message), detection.digest);
+
+            // the first block only leads to a near-collision, the second block completes it
+            assert!(!hash(&message[..256]).collision);
+        }
+
+        // the safe hashes no longer collide, the expected ones are the output of sha1collisiondetection 0.3.4 (the Rust
+        // port of the reference implementation) for the same 320 bytes, i.e. the start of shattered-1.pdf and
+        // shattered-2.pdf, with its default safe-hash mode
+        let first = safe_hash(&first);
+        let second = safe_hash(&second);
+        assert!(first.collision && second.collision);
+        assert_eq!(
+            first.digest.to_string(),
+            "7117b3cb9225aaf0d8ef1a40e493957b0bf8693d"
+        );
+        assert_eq!(
+            second.digest.to_string(),
+            "29f38ae9fd98e2931120fa0bf213e024250d3f6a"
+        );
+    }
+
+    #[test]
+    fn sha1dc_streaming() {
+        let message = shattered(&BLOCKS_1);
+        for split in [0, 1, 63, 64, 65, 200, 319, 320] {
+            let mut hasher = Sha1Dc::with_safe_hash();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(safe_hash(&message), hasher.finalize());
+        }
+
+        // the detection sticks once a colliding block has been seen
+        let mut hasher = Sha1Dc::new();
+        hasher.update(&message);
+        hasher.update([0x20; 1000]);
+        assert!(hasher.finalize().collision);
+    }
+
+    #[test]
+    fn sha1dc_hasher() {
+        let message = shattered(&BLOCKS_1);
+
+        // the generic interface returns the digest, and resetting keeps computing the safe hash
+        let mut hasher = Sha1Dc::with_safe_hash();
+        hasher.update("garbage");
+        Hasher::reset(&mut hasher);
+        hasher.update(&message);
+        assert_eq!(safe_hash(&message).digest, Hasher::finalize(hasher));
+        assert_eq!(crate::sha1(&message), Sha1Dc::digest(&message));
+    }
+}
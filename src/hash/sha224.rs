@@ -0,0 +1,53 @@
+// SHA-224 is SHA-256 with different initial values and a truncated digest, see FIPS 180-4
+
+// initial hash value, the second 32 bits of the fractional parts of the square roots of the 9th through 16th primes
+const H: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+
+crate::hash::truncated_sha2!(
+    "SHA-224",
+    sha224,
+    Sha224,
+    28,
+    "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+    base: sha256,
+    initial: H,
+    state: [u32; 8],
+    block: 64,
+    words: bytes_to_words_be,
+    bytes: words_to_bytes_be,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha224_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"
+        );
+        assert_eq!(
+            hash("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"
+        );
+    }
+
+    #[test]
+    fn sha224_streaming() {
+        let message = [0x3cu8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Sha224::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha224::new().finalize());
+    }
+}
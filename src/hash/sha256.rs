@@ -0,0 +1,445 @@
+use crate::hash::{bytes_to_words_be, words_to_bytes_be, Buffer, Digest, Hasher, Padding};
+#[cfg(feature = "alloc")]
+use crate::trace::{self, compress_blocks, Event};
+use crate::trace::{emit, Observer};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// based on FIPS 180-4, Secure Hash Standard
+
+// round constants, the first 32 bits of the fractional parts of the cube roots of the first 64 primes
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// initial hash value, the first 32 bits of the fractional parts of the square roots of the first 8 primes
+const H: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// logical functions
+const CH: fn(u32, u32, u32) -> u32 = |x, y, z| (x & y) ^ (!x & z);
+const MAJ: fn(u32, u32, u32) -> u32 = |x, y, z| (x & y) ^ (x & z) ^ (y & z);
+const BIG_SIGMA0: fn(u32) -> u32 = |x| x.rotate_right(2) ^ x.rotate_right(13) ^ x.rotate_right(22);
+const BIG_SIGMA1: fn(u32) -> u32 = |x| x.rotate_right(6) ^ x.rotate_right(11) ^ x.rotate_right(25);
+const SMALL_SIGMA0: fn(u32) -> u32 = |x| x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3);
+const SMALL_SIGMA1: fn(u32) -> u32 = |x| x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10);
+
+// the padding is the same as SHA-1, with a 64-bit big endian length
+#[cfg(feature = "alloc")]
+pub fn pad(message: impl AsRef<[u8]>) -> Vec<u8> {
+    let mut message = message.as_ref().to_vec();
+    message.extend_from_slice(padding(message.len() as u64).as_ref());
+
+    message
+}
+
+// compute the padding which follows a message of the given length (in bytes)
+pub fn padding(length: u64) -> Padding<72> {
+    // push 1 bit
+    let mut padding = Padding::new();
+    padding.push(0x80);
+
+    // pad with 0 bits until length is congruent with 56 mod 64 bytes
+    while (length as usize).wrapping_add(padding.len()) % 64 != 56 {
+        padding.push(0);
+    }
+
+    // append the length of the original message (big endian)
+    padding.extend(length.wrapping_mul(8).to_be_bytes());
+
+    padding
+}
+
+// expand the 16 words of a block into the 64 word message schedule
+pub fn schedule(block: &[u8; 64]) -> [u32; 64] {
+    let mut words = [0; 64];
+    words[..16].copy_from_slice(&bytes_to_words_be::<16>(block));
+    for i in 16..64 {
+        words[i] = SMALL_SIGMA1(words[i - 2])
+            .wrapping_add(words[i - 7])
+            .wrapping_add(SMALL_SIGMA0(words[i - 15]))
+            .wrapping_add(words[i - 16]);
+    }
+
+    words
+}
+
+// compute an individual step, the working variables are shifted by one each step
+pub fn step([a, b, c, d, e, f, g, h]: [u32; 8], words: &[u32], i: usize) -> [u32; 8] {
+    let t1 = h
+        .wrapping_add(BIG_SIGMA1(e))
+        .wrapping_add(CH(e, f, g))
+        .wrapping_add(K[i])
+        .wrapping_add(words[i]);
+    let t2 = BIG_SIGMA0(a).wrapping_add(MAJ(a, b, c));
+
+    [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g]
+}
+
+// process a single 512-bit block of the padded message
+pub fn compress(buffer: [u32; 8], block: &[u8; 64]) -> [u32; 8] {
+    compress_observed(buffer, block, &mut ())
+}
+
+// process a single 512-bit block of the padded message, reporting each step to the observer
+pub fn compress_observed<O: Observer + ?Sized>(
+    previous: [u32; 8],
+    block: &[u8; 64],
+    observer: &mut O,
+) -> [u32; 8] {
+    let words = schedule(block);
+    emit!(
+        observer,
+        Event::Schedule {
+            words: words.into(),
+        }
+    );
+
+    // perform 64 steps
+    let mut state = previous;
+    for i in 0..64 {
+        state = step(state, &words, i);
+        emit!(
+            observer,
+            Event::Step {
+                index: i,
+                state: state.into(),
+            }
+        );
+    }
+
+    // add computed state to buffer
+    let mut buffer = previous;
+    for (word, computed) in buffer.iter_mut().zip(state) {
+        *word = word.wrapping_add(computed);
+    }
+    emit!(
+        observer,
+        Event::ChainingValue {
+            previous: previous.into(),
+            working: state.into(),
+            next: buffer.into(),
+        }
+    );
+
+    buffer
+}
+
+/// Computes the SHA-256 hash value (digest) of the input bytes.
+///
+/// Returns a 32-byte `Digest` which implements `Display` in order to get at hexadecimal-string representation.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let input = "abc";
+/// let digest = lore::sha256(input);
+///
+/// assert_eq!(digest.to_string(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+/// ```
+pub fn hash(message: impl AsRef<[u8]>) -> Digest<32> {
+    let mut hasher = Sha256::new();
+    hasher.update(message);
+    hasher.finalize()
+}
+
+/// Computes the SHA-256 hash value (digest) of the input bytes, reporting every intermediate value to the observer.
+///
+/// See the [`trace`](crate::trace) module for the events which are reported.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut events = Vec::new();
+/// let digest = lore::trace::sha256("abc", &mut events);
+///
+/// assert_eq!(digest, lore::sha256("abc"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trace<O: Observer + ?Sized>(message: impl AsRef<[u8]>, observer: &mut O) -> Digest<32> {
+    let padded = pad(message);
+    let buffer = compress_blocks(
+        &padded,
+        H,
+        observer,
+        bytes_to_words_be::<16>,
+        compress_observed,
+    );
+    let digest = words_to_bytes_be(buffer);
+
+    trace::digest(Digest(digest), observer)
+}
+
+/// A streaming SHA-256 hasher, for hashing messages which are not available all at once.
+///
+/// Only a single partial block is buffered, so arbitrarily long messages can be hashed in constant memory.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hasher = lore::Sha256::new();
+/// hasher.update("a");
+/// hasher.update("bc");
+///
+/// assert_eq!(hasher.finalize(), lore::sha256("abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    buffer: Buffer<64>,
+}
+
+impl Sha256 {
+    /// Create a new hasher with an empty message.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: H,
+            buffer: Buffer::new(),
+        }
+    }
+
+    /// Append data to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let Self { state, buffer } = self;
+        buffer.update(data.as_ref(), |block| *state = compress(*state, block));
+    }
+
+    /// Pad the message and return the digest of everything passed to [`Sha256::update`].
+    #[must_use]
+    pub fn finalize(mut self) -> Digest<32> {
+        self.update(padding(self.buffer.length()));
+        let digest = words_to_bytes_be(self.state);
+
+        Digest(digest)
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Sha256 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 32;
+
+    type Output = Digest<32>;
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.update(data);
+    }
+
+    fn finalize(self) -> Digest<32> {
+        self.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::trace::Words;
+    #[cfg(feature = "alloc")]
+    use core::convert::TryInto;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sha256_pad() {
+        assert_eq!(pad([1u8; 55]).len(), 64);
+        assert_eq!(pad([1u8; 56]).len(), 128);
+        assert_eq!(pad([1u8; 64]).len(), 128);
+        assert_eq!(pad([1u8; 4472]).len() % 64, 0);
+
+        assert_eq!(
+            vec![
+                0x61, 0x62, 0x63, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18
+            ],
+            pad("abc")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sha256_schedule() {
+        let block = pad("abc")[..64].try_into().unwrap();
+        let words = schedule(&block);
+
+        assert_eq!(
+            [0x61626380, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x18],
+            words[..16]
+        );
+        assert_eq!(
+            [
+                0x61626380, 0x000f0000, 0x7da86405, 0x600003c6, 0x3e9d7b78, 0x0183fc00, 0x12dcbfdb,
+                0xe2e2c38e, 0xc8215c1a, 0xb73679a2, 0xe5bc3909, 0x32663c5b, 0x9d209d67, 0xec8726cb,
+                0x702138a4, 0xd3b7973b, 0x93f5997f, 0x3b68ba73, 0xaff4ffc1, 0xf10a5c62, 0x0a8b3996,
+                0x72af830a, 0x9409e33e, 0x24641522, 0x9f47bf94, 0xf0a64f5a, 0x3e246a79, 0x27333ba3,
+                0x0c4763f2, 0x840abf27, 0x7a290d5d, 0x065c43da, 0xfb3e89cb, 0xcc7617db, 0xb9e66c34,
+                0xa9993667, 0x84badedd, 0xc21462bc, 0x1487472c, 0xb20f7a99, 0xef57b9cd, 0xebe6b238,
+                0x9fe3095e, 0x78bc8d4b, 0xa43fcf15, 0x668b2ff8, 0xeeaba2cc, 0x12b1edeb
+            ],
+            words[16..]
+        );
+    }
+
+    // intermediate states for the first block of "abc", see the FIPS 180-2 examples
+    #[cfg(feature = "alloc")]
+    #[rustfmt::skip]
+    const ABC_STEPS: [[u32; 8]; 64] = [
+        [0x5d6aebcd, 0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xfa2a4622, 0x510e527f, 0x9b05688c, 0x1f83d9ab],
+        [0x5a6ad9ad, 0x5d6aebcd, 0x6a09e667, 0xbb67ae85, 0x78ce7989, 0xfa2a4622, 0x510e527f, 0x9b05688c],
+        [0xc8c347a7, 0x5a6ad9ad, 0x5d6aebcd, 0x6a09e667, 0xf92939eb, 0x78ce7989, 0xfa2a4622, 0x510e527f],
+        [0xd550f666, 0xc8c347a7, 0x5a6ad9ad, 0x5d6aebcd, 0x24e00850, 0xf92939eb, 0x78ce7989, 0xfa2a4622],
+        [0x04409a6a, 0xd550f666, 0xc8c347a7, 0x5a6ad9ad, 0x43ada245, 0x24e00850, 0xf92939eb, 0x78ce7989],
+        [0x2b4209f5, 0x04409a6a, 0xd550f666, 0xc8c347a7, 0x714260ad, 0x43ada245, 0x24e00850, 0xf92939eb],
+        [0xe5030380, 0x2b4209f5, 0x04409a6a, 0xd550f666, 0x9b27a401, 0x714260ad, 0x43ada245, 0x24e00850],
+        [0x85a07b5f, 0xe5030380, 0x2b4209f5, 0x04409a6a, 0x0c657a79, 0x9b27a401, 0x714260ad, 0x43ada245],
+        [0x8e04ecb9, 0x85a07b5f, 0xe5030380, 0x2b4209f5, 0x32ca2d8c, 0x0c657a79, 0x9b27a401, 0x714260ad],
+        [0x8c87346b, 0x8e04ecb9, 0x85a07b5f, 0xe5030380, 0x1cc92596, 0x32ca2d8c, 0x0c657a79, 0x9b27a401],
+        [0x4798a3f4, 0x8c87346b, 0x8e04ecb9, 0x85a07b5f, 0x436b23e8, 0x1cc92596, 0x32ca2d8c, 0x0c657a79],
+        [0xf71fc5a9, 0x4798a3f4, 0x8c87346b, 0x8e04ecb9, 0x816fd6e9, 0x436b23e8, 0x1cc92596, 0x32ca2d8c],
+        [0x87912990, 0xf71fc5a9, 0x4798a3f4, 0x8c87346b, 0x1e578218, 0x816fd6e9, 0x436b23e8, 0x1cc92596],
+        [0xd932eb16, 0x87912990, 0xf71fc5a9, 0x4798a3f4, 0x745a48de, 0x1e578218, 0x816fd6e9, 0x436b23e8],
+        [0xc0645fde, 0xd932eb16, 0x87912990, 0xf71fc5a9, 0x0b92f20c, 0x745a48de, 0x1e578218, 0x816fd6e9],
+        [0xb0fa238e, 0xc0645fde, 0xd932eb16, 0x87912990, 0x07590dcd, 0x0b92f20c, 0x745a48de, 0x1e578218],
+        [0x21da9a9b, 0xb0fa238e, 0xc0645fde, 0xd932eb16, 0x8034229c, 0x07590dcd, 0x0b92f20c, 0x745a48de],
+        [0xc2fbd9d1, 0x21da9a9b, 0xb0fa238e, 0xc0645fde, 0x846ee454, 0x8034229c, 0x07590dcd, 0x0b92f20c],
+        [0xfe777bbf, 0xc2fbd9d1, 0x21da9a9b, 0xb0fa238e, 0xcc899961, 0x846ee454, 0x8034229c, 0x07590dcd],
+        [0xe1f20c33, 0xfe777bbf, 0xc2fbd9d1, 0x21da9a9b, 0xb0638179, 0xcc899961, 0x846ee454, 0x8034229c],
+        [0x9dc68b63, 0xe1f20c33, 0xfe777bbf, 0xc2fbd9d1, 0x8ada8930, 0xb0638179, 0xcc899961, 0x846ee454],
+        [0xc2606d6d, 0x9dc68b63, 0xe1f20c33, 0xfe777bbf, 0xe1257970, 0x8ada8930, 0xb0638179, 0xcc899961],
+        [0xa7a3623f, 0xc2606d6d, 0x9dc68b63, 0xe1f20c33, 0x49f5114a, 0xe1257970, 0x8ada8930, 0xb0638179],
+        [0xc5d53d8d, 0xa7a3623f, 0xc2606d6d, 0x9dc68b63, 0xaa47c347, 0x49f5114a, 0xe1257970, 0x8ada8930],
+        [0x1c2c2838, 0xc5d53d8d, 0xa7a3623f, 0xc2606d6d, 0x2823ef91, 0xaa47c347, 0x49f5114a, 0xe1257970],
+        [0xcde8037d, 0x1c2c2838, 0xc5d53d8d, 0xa7a3623f, 0x14383d8e, 0x2823ef91, 0xaa47c347, 0x49f5114a],
+        [0xb62ec4bc, 0xcde8037d, 0x1c2c2838, 0xc5d53d8d, 0xc74c6516, 0x14383d8e, 0x2823ef91, 0xaa47c347],
+        [0x77d37528, 0xb62ec4bc, 0xcde8037d, 0x1c2c2838, 0xedffbff8, 0xc74c6516, 0x14383d8e, 0x2823ef91],
+        [0x363482c9, 0x77d37528, 0xb62ec4bc, 0xcde8037d, 0x6112a3b7, 0xedffbff8, 0xc74c6516, 0x14383d8e],
+        [0xa0060b30, 0x363482c9, 0x77d37528, 0xb62ec4bc, 0xade79437, 0x6112a3b7, 0xedffbff8, 0xc74c6516],
+        [0xea992a22, 0xa0060b30, 0x363482c9, 0x77d37528, 0x0109ab3a, 0xade79437, 0x6112a3b7, 0xedffbff8],
+        [0x73b33bf5, 0xea992a22, 0xa0060b30, 0x363482c9, 0xba591112, 0x0109ab3a, 0xade79437, 0x6112a3b7],
+        [0x98e12507, 0x73b33bf5, 0xea992a22, 0xa0060b30, 0x9cd9f5f6, 0xba591112, 0x0109ab3a, 0xade79437],
+        [0xfe604df5, 0x98e12507, 0x73b33bf5, 0xea992a22, 0x59249dd3, 0x9cd9f5f6, 0xba591112, 0x0109ab3a],
+        [0xa9a7738c, 0xfe604df5, 0x98e12507, 0x73b33bf5, 0x085f3833, 0x59249dd3, 0x9cd9f5f6, 0xba591112],
+        [0x65a0cfe4, 0xa9a7738c, 0xfe604df5, 0x98e12507, 0xf4b002d6, 0x085f3833, 0x59249dd3, 0x9cd9f5f6],
+        [0x41a65cb1, 0x65a0cfe4, 0xa9a7738c, 0xfe604df5, 0x0772a26b, 0xf4b002d6, 0x085f3833, 0x59249dd3],
+        [0x34df1604, 0x41a65cb1, 0x65a0cfe4, 0xa9a7738c, 0xa507a53d, 0x0772a26b, 0xf4b002d6, 0x085f3833],
+        [0x6dc57a8a, 0x34df1604, 0x41a65cb1, 0x65a0cfe4, 0xf0781bc8, 0xa507a53d, 0x0772a26b, 0xf4b002d6],
+        [0x79ea687a, 0x6dc57a8a, 0x34df1604, 0x41a65cb1, 0x1efbc0a0, 0xf0781bc8, 0xa507a53d, 0x0772a26b],
+        [0xd6670766, 0x79ea687a, 0x6dc57a8a, 0x34df1604, 0x26352d63, 0x1efbc0a0, 0xf0781bc8, 0xa507a53d],
+        [0xdf46652f, 0xd6670766, 0x79ea687a, 0x6dc57a8a, 0x838b2711, 0x26352d63, 0x1efbc0a0, 0xf0781bc8],
+        [0x17aa0dfe, 0xdf46652f, 0xd6670766, 0x79ea687a, 0xdecd4715, 0x838b2711, 0x26352d63, 0x1efbc0a0],
+        [0x9d4baf93, 0x17aa0dfe, 0xdf46652f, 0xd6670766, 0xfda24c2e, 0xdecd4715, 0x838b2711, 0x26352d63],
+        [0x26628815, 0x9d4baf93, 0x17aa0dfe, 0xdf46652f, 0xa80f11f0, 0xfda24c2e, 0xdecd4715, 0x838b2711],
+        [0x72ab4b91, 0x26628815, 0x9d4baf93, 0x17aa0dfe, 0xb7755da1, 0xa80f11f0, 0xfda24c2e, 0xdecd4715],
+        [0xa14c14b0, 0x72ab4b91, 0x26628815, 0x9d4baf93, 0xd57b94a9, 0xb7755da1, 0xa80f11f0, 0xfda24c2e],
+        [0x4172328d, 0xa14c14b0, 0x72ab4b91, 0x26628815, 0xfecf0bc6, 0xd57b94a9, 0xb7755da1, 0xa80f11f0],
+        [0x05757ceb, 0x4172328d, 0xa14c14b0, 0x72ab4b91, 0xbd714038, 0xfecf0bc6, 0xd57b94a9, 0xb7755da1],
+        [0xf11bfaa8, 0x05757ceb, 0x4172328d, 0xa14c14b0, 0x6e5c390c, 0xbd714038, 0xfecf0bc6, 0xd57b94a9],
+        [0x7a0508a1, 0xf11bfaa8, 0x05757ceb, 0x4172328d, 0x52f1ccf7, 0x6e5c390c, 0xbd714038, 0xfecf0bc6],
+        [0x886e7a22, 0x7a0508a1, 0xf11bfaa8, 0x05757ceb, 0x49231c1e, 0x52f1ccf7, 0x6e5c390c, 0xbd714038],
+        [0x101fd28f, 0x886e7a22, 0x7a0508a1, 0xf11bfaa8, 0x529e7d00, 0x49231c1e, 0x52f1ccf7, 0x6e5c390c],
+        [0xf5702fdb, 0x101fd28f, 0x886e7a22, 0x7a0508a1, 0x9f4787c3, 0x529e7d00, 0x49231c1e, 0x52f1ccf7],
+        [0x3ec45cdb, 0xf5702fdb, 0x101fd28f, 0x886e7a22, 0xe50e1b4f, 0x9f4787c3, 0x529e7d00, 0x49231c1e],
+        [0x38cc9913, 0x3ec45cdb, 0xf5702fdb, 0x101fd28f, 0x54cb266b, 0xe50e1b4f, 0x9f4787c3, 0x529e7d00],
+        [0xfcd1887b, 0x38cc9913, 0x3ec45cdb, 0xf5702fdb, 0x9b5e906c, 0x54cb266b, 0xe50e1b4f, 0x9f4787c3],
+        [0xc062d46f, 0xfcd1887b, 0x38cc9913, 0x3ec45cdb, 0x7e44008e, 0x9b5e906c, 0x54cb266b, 0xe50e1b4f],
+        [0xffb70472, 0xc062d46f, 0xfcd1887b, 0x38cc9913, 0x6d83bfc6, 0x7e44008e, 0x9b5e906c, 0x54cb266b],
+        [0xb6ae8fff, 0xffb70472, 0xc062d46f, 0xfcd1887b, 0xb21bad3d, 0x6d83bfc6, 0x7e44008e, 0x9b5e906c],
+        [0xb85e2ce9, 0xb6ae8fff, 0xffb70472, 0xc062d46f, 0x961f4894, 0xb21bad3d, 0x6d83bfc6, 0x7e44008e],
+        [0x04d24d6c, 0xb85e2ce9, 0xb6ae8fff, 0xffb70472, 0x948d25b6, 0x961f4894, 0xb21bad3d, 0x6d83bfc6],
+        [0xd39a2165, 0x04d24d6c, 0xb85e2ce9, 0xb6ae8fff, 0xfb121210, 0x948d25b6, 0x961f4894, 0xb21bad3d],
+        [0x506e3058, 0xd39a2165, 0x04d24d6c, 0xb85e2ce9, 0x5ef50f24, 0xfb121210, 0x948d25b6, 0x961f4894],
+    ];
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sha256_steps() {
+        let block = pad("abc")[..64].try_into().unwrap();
+        let words = schedule(&block);
+        let mut state = H;
+
+        #[allow(clippy::needless_range_loop)]
+        for i in 0..64 {
+            state = step(state, &words, i);
+            assert_eq!(ABC_STEPS[i], state);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sha256_trace() {
+        let block = pad("abc")[..64].try_into().unwrap();
+        let mut events = Vec::new();
+        trace("abc", &mut events);
+
+        assert!(events.contains(&Event::Schedule {
+            words: schedule(&block).into()
+        }));
+        let steps: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Step { state, .. } => Some(state.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ABC_STEPS.map(Words::from).to_vec(), steps);
+        assert!(events.contains(&Event::ChainingValue {
+            previous: H.into(),
+            working: ABC_STEPS[63].into(),
+            next: compress(H, &block).into(),
+        }));
+    }
+
+    #[test]
+    fn sha256_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hash("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(
+            hash("The quick brown fox jumps over the lazy dog").to_string(),
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+    }
+
+    #[test]
+    fn sha256_streaming() {
+        let message = [0x3cu8; 300];
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Sha256::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha256::new().finalize());
+    }
+}
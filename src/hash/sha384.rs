@@ -0,0 +1,55 @@
+// SHA-384 is SHA-512 with different initial values and a truncated digest, see FIPS 180-4
+
+// initial hash value
+#[rustfmt::skip]
+const H: [u64; 8] = [
+    0xcbbb9d5dc1059ed8, 0x629a292a367cd507, 0x9159015a3070dd17, 0x152fecd8f70e5939,
+    0x67332667ffc00b31, 0x8eb44a8768581511, 0xdb0c2e0d64f98fa7, 0x47b5481dbefa4fa4,
+];
+
+crate::hash::truncated_sha2!(
+    "SHA-384",
+    sha384,
+    Sha384,
+    48,
+    "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7",
+    base: sha512,
+    initial: H,
+    state: [u64; 8],
+    block: 128,
+    words: bytes_to_words64_be,
+    bytes: words64_to_bytes_be,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha384_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"
+        );
+        assert_eq!(
+            hash("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu").to_string(),
+            "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039"
+        );
+    }
+
+    #[test]
+    fn sha384_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, 111, 112, 127, 128, 129, 256, 599, 600] {
+            let mut hasher = Sha384::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha384::new().finalize());
+    }
+}
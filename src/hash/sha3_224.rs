@@ -0,0 +1,50 @@
+// based on FIPS 202, SHA3-224 is the Keccak sponge with a capacity of 448 bits
+
+// the number of bytes absorbed per permutation
+const RATE: usize = 144;
+
+// domain separation bits 01, followed by the first bit of the padding
+const SUFFIX: u8 = 0x06;
+
+crate::hash::keccak::sha3!(
+    "SHA3-224",
+    sha3_224,
+    Sha3_224,
+    28,
+    "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf",
+    rate: RATE,
+    suffix: SUFFIX,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha3_224_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"
+        );
+        assert_eq!(
+            hash("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "8a24108b154ada21c9fd5574494479ba5c7e7ab76ef264ead0fcce33"
+        );
+    }
+
+    #[test]
+    fn sha3_224_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, RATE - 1, RATE, RATE + 1, 2 * RATE, 599, 600] {
+            let mut hasher = Sha3_224::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha3_224::new().finalize());
+    }
+}
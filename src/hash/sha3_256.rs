@@ -0,0 +1,50 @@
+// based on FIPS 202, SHA3-256 is the Keccak sponge with a capacity of 512 bits
+
+// the number of bytes absorbed per permutation
+const RATE: usize = 136;
+
+// domain separation bits 01, followed by the first bit of the padding
+const SUFFIX: u8 = 0x06;
+
+crate::hash::keccak::sha3!(
+    "SHA3-256",
+    sha3_256,
+    Sha3_256,
+    32,
+    "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+    rate: RATE,
+    suffix: SUFFIX,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha3_256_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        assert_eq!(
+            hash("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376"
+        );
+    }
+
+    #[test]
+    fn sha3_256_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, RATE - 1, RATE, RATE + 1, 2 * RATE, 599, 600] {
+            let mut hasher = Sha3_256::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha3_256::new().finalize());
+    }
+}
@@ -0,0 +1,50 @@
+// based on FIPS 202, SHA3-384 is the Keccak sponge with a capacity of 768 bits
+
+// the number of bytes absorbed per permutation
+const RATE: usize = 104;
+
+// domain separation bits 01, followed by the first bit of the padding
+const SUFFIX: u8 = 0x06;
+
+crate::hash::keccak::sha3!(
+    "SHA3-384",
+    sha3_384,
+    Sha3_384,
+    48,
+    "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25",
+    rate: RATE,
+    suffix: SUFFIX,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha3_384_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2ac3713831264adb47fb6bd1e058d5f004"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"
+        );
+        assert_eq!(
+            hash("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "991c665755eb3a4b6bbdfb75c78a492e8c56a22c5c4d7e429bfdbc32b9d4ad5aa04a1f076e62fea19eef51acd0657c22"
+        );
+    }
+
+    #[test]
+    fn sha3_384_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, RATE - 1, RATE, RATE + 1, 2 * RATE, 599, 600] {
+            let mut hasher = Sha3_384::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha3_384::new().finalize());
+    }
+}
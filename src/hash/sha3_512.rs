@@ -0,0 +1,50 @@
+// based on FIPS 202, SHA3-512 is the Keccak sponge with a capacity of 1024 bits
+
+// the number of bytes absorbed per permutation
+const RATE: usize = 72;
+
+// domain separation bits 01, followed by the first bit of the padding
+const SUFFIX: u8 = 0x06;
+
+crate::hash::keccak::sha3!(
+    "SHA3-512",
+    sha3_512,
+    Sha3_512,
+    64,
+    "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+    rate: RATE,
+    suffix: SUFFIX,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha3_512_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"
+        );
+        assert_eq!(
+            hash("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "04a371e84ecfb5b8b77cb48610fca8182dd457ce6f326a0fd3d7ec2f1e91636dee691fbe0c985302ba1b0d8dc78c086346b533b49c030d99a27daf1139d6e75e"
+        );
+    }
+
+    #[test]
+    fn sha3_512_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, RATE - 1, RATE, RATE + 1, 2 * RATE, 599, 600] {
+            let mut hasher = Sha3_512::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha3_512::new().finalize());
+    }
+}
@@ -0,0 +1,55 @@
+// SHA-512/224 is SHA-512 with initial values generated by the SHA-512/t IV generation function, see FIPS 180-4
+
+// initial hash value
+#[rustfmt::skip]
+const H: [u64; 8] = [
+    0x8c3d37c819544da2, 0x73e1996689dcd4d6, 0x1dfab7ae32ff9c82, 0x679dd514582f9fcf,
+    0x0f6d2b697bd44da8, 0x77e36f7304c48942, 0x3f9d85a86a1d36c8, 0x1112e6ad91d692a1,
+];
+
+crate::hash::truncated_sha2!(
+    "SHA-512/224",
+    sha512_224,
+    Sha512_224,
+    28,
+    "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa",
+    base: sha512,
+    initial: H,
+    state: [u64; 8],
+    block: 128,
+    words: bytes_to_words64_be,
+    bytes: words64_to_bytes_be,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha512_224_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa"
+        );
+        assert_eq!(
+            hash("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu").to_string(),
+            "23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9"
+        );
+    }
+
+    #[test]
+    fn sha512_224_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, 111, 112, 127, 128, 129, 256, 599, 600] {
+            let mut hasher = Sha512_224::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha512_224::new().finalize());
+    }
+}
@@ -0,0 +1,55 @@
+// SHA-512/256 is SHA-512 with initial values generated by the SHA-512/t IV generation function, see FIPS 180-4
+
+// initial hash value
+#[rustfmt::skip]
+const H: [u64; 8] = [
+    0x22312194fc2bf72c, 0x9f555fa3c84c64c2, 0x2393b86b6f53b151, 0x963877195940eabd,
+    0x96283ee2a88effe3, 0xbe5e1e2553863992, 0x2b0199fc2c85b8aa, 0x0eb72ddc81c52ca2,
+];
+
+crate::hash::truncated_sha2!(
+    "SHA-512/256",
+    sha512_256,
+    Sha512_256,
+    32,
+    "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+    base: sha512,
+    initial: H,
+    state: [u64; 8],
+    block: 128,
+    words: bytes_to_words64_be,
+    bytes: words64_to_bytes_be,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha512_256_hash() {
+        assert_eq!(
+            hash("").to_string(),
+            "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a"
+        );
+        assert_eq!(
+            hash("abc").to_string(),
+            "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"
+        );
+        assert_eq!(
+            hash("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu").to_string(),
+            "3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a"
+        );
+    }
+
+    #[test]
+    fn sha512_256_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, 111, 112, 127, 128, 129, 256, 599, 600] {
+            let mut hasher = Sha512_256::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash(message), hasher.finalize());
+        }
+        assert_eq!(hash(""), Sha512_256::new().finalize());
+    }
+}
@@ -0,0 +1,68 @@
+// based on FIPS 202, SHAKE128 is the Keccak sponge with a capacity of 256 bits and an output of any length
+
+// the number of bytes absorbed or squeezed per permutation
+const RATE: usize = 168;
+
+// domain separation bits 1111, followed by the first bit of the padding
+const SUFFIX: u8 = 0x1f;
+
+crate::hash::keccak::shake!(
+    "SHAKE128",
+    shake128,
+    Shake128,
+    "5881092dd818bf5cf8a3ddb793fbcba7",
+    rate: RATE,
+    suffix: SUFFIX,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::Digest;
+
+    #[test]
+    fn shake128_hash() {
+        assert_eq!(
+            hash::<32>("").to_string(),
+            "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+        );
+        assert_eq!(
+            hash::<16>("abc").to_string(),
+            "5881092dd818bf5cf8a3ddb793fbcba7"
+        );
+        assert_eq!(
+            hash::<64>("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "1a96182b50fb8c7e74e0a707788f55e98209b8d91fade8f32f8dd5cff7bf21f54ee5f19550825a6e070030519e944263ac1c6765287065621f9fcb3201723e32"
+        );
+    }
+
+    #[test]
+    fn shake128_xof() {
+        // read more than a single block of output, in pieces which don't line up with the rate
+        let mut hasher = Shake128::new();
+        hasher.update([0xa3; 200]);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [0u8; 500];
+        for piece in output.chunks_mut(7) {
+            reader.read(piece);
+        }
+
+        assert_eq!(hash::<500>([0xa3; 200]), Digest(output));
+        assert_eq!(
+            Digest(output).to_string(),
+            "131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037cf232ef7164a6d1eb448c8908186ad852d3f85a5cf28da1ab6fe3438171978467f1c05d58c7ef38c284c41f6c2221a76f12ab1c04082660250802294fb87180213fdef5b0ecb7df50ca1f8555be14d32e10f6edcde892c09424b29f597afc270c904556bfcb47a7d40778d390923642b3cbd0579e60908d5a000c1d08b98ef933f806445bf87f8b009ba9e94f7266122ed7ac24e5e266c42a82fa1bbefb7b8db0066e16a85e0493f07df4809aec084a593748ac3dde5a6d7aae1e8b6e5352b2d71efbb47d4caeed5e6d633805d2d323e6fd81b4684b93a2677d45e7421c2c6aea259b855a698fd7d13477a1fe53e5a4a6197dbec5ce95f505b520bcd9570c4a8265a7e01f89c0c002c59bfec6cd4a5c109258953ee5ee70cd577ee217af21fa70178f0946c9bf6ca8751793479f6b537737e40b6ed28511d8a2d7e73eb75f8daac912ff906e0ab955b083bac45a8e5e9b744c8506f37e9b4e749a184b30f43eb188d855f1b70d71ff3e50c537ac1b0f8974f0fe1a6ad295ba42f6aec74d123a7abedde6e2c0711cab36be5acb1a5a11a4b1db08ba6982efccd716929a7741cfc63aa4435e0b69a9063e880795c3dc5ef3272e11c497a91acf699fefee206227a44c9fb359fd56ac0a9a75a743cff6862f17d7259"
+        );
+    }
+
+    #[test]
+    fn shake128_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, RATE - 1, RATE, RATE + 1, 2 * RATE, 599, 600] {
+            let mut hasher = Shake128::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash::<64>(message), hasher.finalize());
+        }
+        assert_eq!(hash::<64>(""), Shake128::new().finalize());
+    }
+}
@@ -0,0 +1,68 @@
+// based on FIPS 202, SHAKE256 is the Keccak sponge with a capacity of 512 bits and an output of any length
+
+// the number of bytes absorbed or squeezed per permutation
+const RATE: usize = 136;
+
+// domain separation bits 1111, followed by the first bit of the padding
+const SUFFIX: u8 = 0x1f;
+
+crate::hash::keccak::shake!(
+    "SHAKE256",
+    shake256,
+    Shake256,
+    "483366601360a8771c6863080cc4114d",
+    rate: RATE,
+    suffix: SUFFIX,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::Digest;
+
+    #[test]
+    fn shake256_hash() {
+        assert_eq!(
+            hash::<32>("").to_string(),
+            "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f"
+        );
+        assert_eq!(
+            hash::<16>("abc").to_string(),
+            "483366601360a8771c6863080cc4114d"
+        );
+        assert_eq!(
+            hash::<64>("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "4d8c2dd2435a0128eefbb8c36f6f87133a7911e18d979ee1ae6be5d4fd2e332940d8688a4e6a59aa8060f1f9bc996c05aca3c696a8b66279dc672c740bb224ec"
+        );
+    }
+
+    #[test]
+    fn shake256_xof() {
+        // read more than a single block of output, in pieces which don't line up with the rate
+        let mut hasher = Shake256::new();
+        hasher.update([0xa3; 200]);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [0u8; 500];
+        for piece in output.chunks_mut(7) {
+            reader.read(piece);
+        }
+
+        assert_eq!(hash::<500>([0xa3; 200]), Digest(output));
+        assert_eq!(
+            Digest(output).to_string(),
+            "cd8a920ed141aa0407a22d59288652e9d9f1a7ee0c1e7c1ca699424da84a904d2d700caae7396ece96604440577da4f3aa22aeb8857f961c4cd8e06f0ae6610b1048a7f64e1074cd629e85ad7566048efc4fb500b486a3309a8f26724c0ed628001a1099422468de726f1061d99eb9e93604d5aa7467d4b1bd6484582a384317d7f47d750b8f5499512bb85a226c4243556e696f6bd072c5aa2d9b69730244b56853d16970ad817e213e470618178001c9fb56c54fefa5fee67d2da524bb3b0b61ef0e9114a92cdbb6cccb98615cfe76e3510dd88d1cc28ff99287512f24bfafa1a76877b6f37198e3a641c68a7c42d45fa7acc10dae5f3cefb7b735f12d4e589f7a456e78c0f5e4c4471fffa5e4fa0514ae974d8c2648513b5db494cea847156d277ad0e141c24c7839064cd08851bc2e7ca109fd4e251c35bb0a04fb05b364ff8c4d8b59bc303e25328c09a882e952518e1a8ae0ff265d61c465896973d7490499dc639fb8502b39456791b1b6ec5bcc5d9ac36a6df622a070d43fed781f5f149f7b62675e7d1a4d6dec48c1c7164586eae06a51208c0b791244d307726505c3ad4b26b6822377257aa152037560a739714a3ca79bd605547c9b78dd1f596f2d4f1791bc689a0e9b799a37339c04275733740143ef5d2b58b96a363d4e08076a1a9d7846436e4dca5728b6f760eef0ca92bf0b"
+        );
+    }
+
+    #[test]
+    fn shake256_streaming() {
+        let message = [0x3cu8; 600];
+        for split in [0, 1, RATE - 1, RATE, RATE + 1, 2 * RATE, 599, 600] {
+            let mut hasher = Shake256::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hash::<64>(message), hasher.finalize());
+        }
+        assert_eq!(hash::<64>(""), Shake256::new().finalize());
+    }
+}
@@ -0,0 +1,367 @@
+use crate::hash::{constant_time_eq, Hasher};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// based on RFC 2104, HMAC: Keyed-Hashing for Message Authentication
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+/// Computes the HMAC (hash-based message authentication code) of the message, using the hash function `H`.
+///
+/// Returns the same type of digest as the hash function, e.g. a 16-byte `Digest` for [`crate::Md5`].
+/// To check a received tag, use [`Hmac::verify`] instead of comparing the tags directly, since it runs in constant time.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let tag = lore::hmac::<lore::Md5>("key", "The quick brown fox jumps over the lazy dog");
+///
+/// assert_eq!(tag.to_string(), "80070713463e7749b90c2dc24911e275");
+/// ```
+pub fn hmac<H: Hasher>(key: impl AsRef<[u8]>, message: impl AsRef<[u8]>) -> H::Output {
+    let mut hmac = Hmac::<H>::new(key);
+    hmac.update(message);
+    hmac.finalize()
+}
+
+/// A streaming HMAC over the hash function `H`, for authenticating messages which are not available all at once.
+///
+/// Both the inner and the outer hasher are keyed when the HMAC is created, so only the message is processed afterwards.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let mut hmac = lore::Hmac::<lore::Sha1>::new("key");
+/// hmac.update("The quick brown fox ");
+/// hmac.update("jumps over the lazy dog");
+///
+/// assert_eq!(
+///     hmac.finalize().to_string(),
+///     "de7c9b85b8b78aa6bc8a7a36f70a90701c9db4d9"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Hmac<H: Hasher> {
+    inner: H,
+    outer: H,
+}
+
+impl<H: Hasher> Hmac<H> {
+    /// Create a new HMAC with the given key, which may be of any length.
+    #[must_use]
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        // keys which are longer than a block are hashed first
+        let key = key.as_ref();
+        let digest = (key.len() > H::BLOCK_SIZE).then(|| H::digest(key));
+        let key = digest.as_ref().map_or(key, AsRef::as_ref);
+
+        let mut inner = H::default();
+        absorb_key(&mut inner, key, IPAD);
+        let mut outer = H::default();
+        absorb_key(&mut outer, key, OPAD);
+
+        Self { inner, outer }
+    }
+
+    /// The key padded to the block size of the hash function, which is hashed first if it is longer than a block.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn block_key(key: impl AsRef<[u8]>) -> Vec<u8> {
+        let key = key.as_ref();
+        let mut block_key = if key.len() > H::BLOCK_SIZE {
+            H::digest(key).as_ref().to_vec()
+        } else {
+            key.to_vec()
+        };
+        block_key.resize(H::BLOCK_SIZE, 0);
+
+        block_key
+    }
+
+    /// The padded key xored with the inner padding (0x36 repeated), which is prepended to the message.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn inner_key(key: impl AsRef<[u8]>) -> Vec<u8> {
+        Self::block_key(key).iter().map(|b| b ^ IPAD).collect()
+    }
+
+    /// The padded key xored with the outer padding (0x5c repeated), which is prepended to the inner digest.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn outer_key(key: impl AsRef<[u8]>) -> Vec<u8> {
+        Self::block_key(key).iter().map(|b| b ^ OPAD).collect()
+    }
+
+    /// Append data to the message being authenticated.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.inner.update(data);
+    }
+
+    /// Return the tag of everything passed to [`Hmac::update`].
+    #[must_use]
+    pub fn finalize(self) -> H::Output {
+        let Self { inner, mut outer } = self;
+        outer.update(inner.finalize());
+        outer.finalize()
+    }
+
+    /// Check whether the tag matches the message, in time which doesn't depend on where the tags differ.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let tag = lore::hmac::<lore::Sha256>("key", "message");
+    ///
+    /// let mut hmac = lore::Hmac::<lore::Sha256>::new("key");
+    /// hmac.update("message");
+    /// assert!(hmac.verify(&tag));
+    /// ```
+    #[must_use]
+    pub fn verify(self, tag: impl AsRef<[u8]>) -> bool {
+        constant_time_eq(self.finalize().as_ref(), tag.as_ref())
+    }
+}
+
+// feed the key to the hasher, padded with zeros to a full block and xored with the pad, a piece at a time so that
+// nothing has to be allocated
+fn absorb_key<H: Hasher>(hasher: &mut H, key: &[u8], pad: u8) {
+    let mut piece = [0u8; 64];
+    for offset in (0..H::BLOCK_SIZE).step_by(piece.len()) {
+        let piece = &mut piece[..(H::BLOCK_SIZE - offset).min(64)];
+        for (i, byte) in piece.iter_mut().enumerate() {
+            *byte = key.get(offset + i).copied().unwrap_or(0) ^ pad;
+        }
+        hasher.update(&*piece);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Blake2b, Md2, Md5, Ripemd160, Sha1, Sha224, Sha256, Sha384, Sha3_256, Sha512, Sha512_256,
+    };
+
+    // the keys and messages of RFC 2202, where the keys of the first, third and fifth test case are 16 bytes long for MD5
+    fn rfc2202(key_length: usize) -> [(Vec<u8>, Vec<u8>); 7] {
+        [
+            (vec![0x0b; key_length], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; key_length], vec![0xdd; 50]),
+            ((1..=25).collect(), vec![0xcd; 50]),
+            (vec![0x0c; key_length], b"Test With Truncation".to_vec()),
+            (
+                vec![0xaa; 80],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+            ),
+            (
+                vec![0xaa; 80],
+                b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data"
+                    .to_vec(),
+            ),
+        ]
+    }
+
+    // the keys and messages of RFC 4231
+    fn rfc4231() -> [(Vec<u8>, Vec<u8>); 7] {
+        [
+            (vec![0x0b; 20], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; 20], vec![0xdd; 50]),
+            ((1..=25).collect(), vec![0xcd; 50]),
+            (vec![0x0c; 20], b"Test With Truncation".to_vec()),
+            (
+                vec![0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+            ),
+            (
+                vec![0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.".to_vec(),
+            ),
+        ]
+    }
+
+    // the tags of the fifth test cases are listed in full, rather than truncated
+    fn check<H: Hasher>(cases: &[(Vec<u8>, Vec<u8>); 7], tags: [&str; 7]) {
+        for ((key, message), tag) in cases.iter().zip(tags) {
+            assert_eq!(tag, hmac::<H>(key, message).to_string());
+        }
+    }
+
+    #[test]
+    fn hmac_rfc2202() {
+        check::<Md5>(
+            &rfc2202(16),
+            [
+                "9294727a3638bb1c13f48ef8158bfc9d",
+                "750c783e6ab0b503eaa86e310a5db738",
+                "56be34521d144c88dbb8c733f0e8b3f6",
+                "697eaf0aca3a3aea3a75164746ffaa79",
+                "56461ef2342edc00f9bab995690efd4c",
+                "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+                "6f630fad67cda0ee1fb1f562db3aa53e",
+            ],
+        );
+        check::<Sha1>(
+            &rfc2202(20),
+            [
+                "b617318655057264e28bc0b6fb378c8ef146be00",
+                "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+                "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+                "4c9007f4026250c6bc8414f9bf50c86c2d7235da",
+                "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04",
+                "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+                "e8e99d0f45237d786d6bbaa7965c7808bbff1a91",
+            ],
+        );
+    }
+
+    #[test]
+    fn hmac_rfc4231() {
+        check::<Sha224>(
+            &rfc4231(),
+            [
+                "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+                "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+                "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+                "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+                "0e2aea68a90c8d37c988bcdb9fca6fa8099cd857c7ec4a1815cac54c",
+                "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+                "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+            ],
+        );
+        check::<Sha256>(
+            &rfc4231(),
+            [
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+                "a3b6167473100ee06e0c796c2955552bfa6f7c0a6a8aef8b93f860aab0cd20c5",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            ],
+        );
+        check::<Sha384>(
+            &rfc4231(),
+            [
+                "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6",
+                "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+                "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b2a5ab39dc13814b94e3ab6e101a34f27",
+                "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e6801dd23c4a7d679ccf8a386c674cffb",
+                "3abf34c3503b2a23a46efc619baef897f4c8e42c934ce55ccbae9740fcbc1af4ca62269e2a37cd88ba926341efe4aeea",
+                "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+                "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e",
+            ],
+        );
+        check::<Sha512>(
+            &rfc4231(),
+            [
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+                "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+                "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+                "415fad6271580a531d4179bc891d87a650188707922a4fbb36663a1eb16da008711c5b50ddd0fc235084eb9d3364a1454fb2ef67cd1d29fe6773068ea266e96b",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+                "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+            ],
+        );
+    }
+
+    #[test]
+    fn hmac_other_hashes() {
+        let (key, message) = ("key", "The quick brown fox jumps over the lazy dog");
+        assert_eq!(
+            "50278a77d4d7670561ab72e867383aef6ce50b3e",
+            hmac::<Ripemd160>(key, message).to_string()
+        );
+        assert_eq!(
+            "8c6e0683409427f8931711b10ca92a506eb1fafa48fadd66d76126f47ac2c333",
+            hmac::<Sha3_256>(key, message).to_string()
+        );
+        assert_eq!(
+            "7fb65e03577da9151a1016e9c2e514d4d48842857f13927f348588173dca6d89",
+            hmac::<Sha512_256>(key, message).to_string()
+        );
+        assert_eq!(
+            "92294f92c0dfb9b00ec9ae8bd94d7e7d8a036b885a499f149dfe2fd2199394aaaf6b8894a1730cccb2cd050f9bcf5062a38b51b0dab33207f8ef35ae2c9df51b",
+            hmac::<Blake2b<64>>(key, message).to_string()
+        );
+
+        // md2 has a block size of only 16 bytes, so longer keys are hashed
+        for key in [&b"key"[..], &[0x2a; 16], &[0x2a; 17]] {
+            let block_key = if key.len() > 16 {
+                crate::md2(key).as_ref().to_vec()
+            } else {
+                [key, &[0; 16][key.len()..]].concat()
+            };
+            let inner = crate::md2(
+                [
+                    &block_key.iter().map(|b| b ^ 0x36).collect::<Vec<_>>()[..],
+                    message.as_ref(),
+                ]
+                .concat(),
+            );
+            let outer = crate::md2(
+                [
+                    &block_key.iter().map(|b| b ^ 0x5c).collect::<Vec<_>>()[..],
+                    inner.as_ref(),
+                ]
+                .concat(),
+            );
+            assert_eq!(outer, hmac::<Md2>(key, message));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn hmac_keys() {
+        assert_eq!(
+            b"key\0\0\0\0\0\0\0\0\0\0\0\0\0",
+            &Hmac::<Md2>::block_key("key")[..]
+        );
+        assert_eq!(vec![0x36; 64], Hmac::<Md5>::inner_key(""));
+        assert_eq!(vec![0x5c; 64], Hmac::<Md5>::outer_key(""));
+        assert_eq!(
+            Vec::from(crate::sha1([0xaa; 80])),
+            Hmac::<Sha1>::block_key([0xaa; 80])[..20]
+        );
+        assert_eq!(128, Hmac::<Sha512>::inner_key([0xaa; 131]).len());
+        assert_eq!(
+            [0x3c ^ 0x36, 0x2a ^ 0x36, 0x36],
+            Hmac::<Md5>::inner_key([0x3c, 0x2a])[..3]
+        );
+    }
+
+    #[test]
+    fn hmac_streaming() {
+        let message = [0x5au8; 300];
+        for split in [0, 1, 63, 64, 65, 299, 300] {
+            let mut hasher = Hmac::<Sha256>::new("key");
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hmac::<Sha256>("key", message), hasher.finalize());
+        }
+    }
+
+    #[test]
+    fn hmac_verify() {
+        let tag = hmac::<Md5>("key", "message");
+
+        let mut hasher = Hmac::<Md5>::new("key");
+        hasher.update("message");
+        assert!(hasher.clone().verify(&tag));
+        assert!(!hasher.clone().verify(&tag.as_ref()[..15]));
+        assert!(!Hmac::<Md5>::new("key").verify(&tag));
+
+        let mut forged = tag.as_ref().to_vec();
+        forged[15] ^= 1;
+        assert!(!hasher.verify(forged));
+    }
+}
@@ -0,0 +1,505 @@
+//! Hashing data while it is read or written, with the `std::io` traits.
+//!
+//! Every streaming hasher implements [`Write`], so it can be the destination of [`std::io::copy`], and
+//! [`Hasher::hash_reader`] hashes everything a reader produces, like [`crate::Shake128::hash_reader`] and
+//! [`crate::Shake256::hash_reader`] for the SHAKE functions, which aren't a [`Hasher`].
+//! [`HashingReader`] and [`HashingWriter`] pass the data through to another reader or writer, and hash it on the
+//! way, e.g. to check the digest of a download while it is being parsed or saved, with any of the streaming hashers.
+//! Files are hashed with [`Hasher::hash_file`], or by mapping them into memory with `Hasher::hash_file_mmap`,
+//! which is gated behind the `mmap` feature, and the SHAKE functions have the same methods of their own.
+//!
+//! # Examples
+//!
+//! Hashing a message while it is copied:
+//!
+//! ```
+//! use lore::io::HashingReader;
+//! use std::io;
+//!
+//! let mut reader = HashingReader::new("lol xd".as_bytes(), lore::Sha256::new());
+//! let mut copy = Vec::new();
+//! io::copy(&mut reader, &mut copy).unwrap();
+//!
+//! assert_eq!(copy, b"lol xd");
+//! assert_eq!(reader.finalize(), lore::sha256("lol xd"));
+//! ```
+
+use crate::hash::{keccak::Sponge, Hasher};
+use crate::{
+    Blake2b, Blake2s, Blake3, Hmac, Md2, Md4, Md5, Ripemd128, Ripemd160, Ripemd256, Ripemd320,
+    Sha1, Sha1Dc, Sha224, Sha256, Sha384, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Sha512,
+    Sha512_224, Sha512_256, Shake128, Shake256,
+};
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// A reader which hashes all the data read through it.
+///
+/// Any streaming hasher can be used, including [`Shake128`] and [`Shake256`], which aren't a [`Hasher`], so their
+/// output is computed by taking the hasher back with [`HashingReader::into_parts`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use lore::io::HashingReader;
+/// use std::io::Read;
+///
+/// let mut reader = HashingReader::new("lol xd".as_bytes(), lore::Md5::new());
+/// let mut first = [0; 3];
+/// reader.read_exact(&mut first).unwrap();
+/// reader.read_exact(&mut first).unwrap();
+///
+/// assert_eq!(&first, b" xd");
+/// assert_eq!(reader.finalize(), lore::md5("lol xd"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashingReader<R, H> {
+    inner: R,
+    hasher: H,
+}
+
+impl<R: Read, H: Write> HashingReader<R, H> {
+    /// Wrap a reader, hashing the data read from it with the hasher.
+    pub fn new(inner: R, hasher: H) -> Self {
+        Self { inner, hasher }
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    ///
+    /// Data read directly from the underlying reader is not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Return the underlying reader and the hasher, which has seen all the data read so far.
+    pub fn into_parts(self) -> (R, H) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<R: Read, H: Hasher> HashingReader<R, H> {
+    /// Return the digest of all the data read so far.
+    pub fn finalize(self) -> H::Output {
+        self.hasher.finalize()
+    }
+}
+
+impl<R: Read, H: Write> Read for HashingReader<R, H> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.write_all(&buf[..read])?;
+
+        Ok(read)
+    }
+}
+
+/// A writer which hashes all the data written through it.
+///
+/// Only the data which the underlying writer accepts is hashed.
+/// Like [`HashingReader`], this works with the SHAKE functions too, through [`HashingWriter::into_parts`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use lore::io::HashingWriter;
+/// use std::io::Write;
+///
+/// let mut writer = HashingWriter::new(Vec::new(), lore::Sha1::new());
+/// write!(writer, "lol {}", "xd").unwrap();
+///
+/// let (written, hasher) = writer.into_parts();
+/// assert_eq!(written, b"lol xd");
+/// assert_eq!(hasher.finalize(), lore::sha1("lol xd"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashingWriter<W, H> {
+    inner: W,
+    hasher: H,
+}
+
+impl<W: Write, H: Write> HashingWriter<W, H> {
+    /// Wrap a writer, hashing the data written to it with the hasher.
+    pub fn new(inner: W, hasher: H) -> Self {
+        Self { inner, hasher }
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    ///
+    /// Data written directly to the underlying writer is not hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Return the underlying writer and the hasher, which has seen all the data written so far.
+    pub fn into_parts(self) -> (W, H) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<W: Write, H: Hasher> HashingWriter<W, H> {
+    /// Return the digest of all the data written so far.
+    pub fn finalize(self) -> H::Output {
+        self.hasher.finalize()
+    }
+}
+
+impl<W: Write, H: Write> Write for HashingWriter<W, H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.write_all(&buf[..written])?;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// An error which occurred while opening or reading a file to hash it.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use lore::Hasher;
+/// use std::io::ErrorKind;
+///
+/// let error = lore::Sha256::hash_file("missing.txt").unwrap_err();
+///
+/// assert_eq!(error.kind(), ErrorKind::NotFound);
+/// assert_eq!(error.path(), std::path::Path::new("missing.txt"));
+/// ```
+#[derive(Debug)]
+pub struct HashFileError {
+    path: PathBuf,
+    source: io::Error,
+}
+
+impl HashFileError {
+    pub(crate) fn new(path: &Path, source: io::Error) -> Self {
+        Self {
+            path: path.to_owned(),
+            source,
+        }
+    }
+
+    /// The path of the file which could not be hashed.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The kind of the underlying I/O error.
+    #[must_use]
+    pub fn kind(&self) -> io::ErrorKind {
+        self.source.kind()
+    }
+}
+
+impl Display for HashFileError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "failed to hash {}: {}", self.path.display(), self.source)
+    }
+}
+
+impl Error for HashFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<HashFileError> for io::Error {
+    fn from(error: HashFileError) -> Self {
+        io::Error::new(error.kind(), error)
+    }
+}
+
+// map a file into memory, so that it can be hashed in one go
+#[cfg(feature = "mmap")]
+pub(crate) fn map(path: &Path) -> Result<memmap2::Mmap, HashFileError> {
+    let file = std::fs::File::open(path).map_err(|source| HashFileError::new(path, source))?;
+
+    // SAFETY: the map is only read while hashing, and like any memory-mapped I/O, this relies on no other process
+    // truncating or modifying the file in the meantime, which is documented on `Hasher::hash_file_mmap`
+    unsafe { memmap2::Mmap::map(&file) }.map_err(|source| HashFileError::new(path, source))
+}
+
+// write to any hasher through `Hasher::update`, which doesn't require it to implement `Write` itself
+pub(crate) struct Update<H>(pub(crate) H);
+
+impl<H: Hasher> Write for Update<H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// the streaming hashers accept everything written to them, and have nothing to flush, where the generic ones are
+// preceded by their generic parameters in brackets
+macro_rules! impl_write {
+    ($([$($generics:tt)*] $hasher:ty),*) => {
+        $(
+            impl<$($generics)*> Write for $hasher {
+                fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                    self.update(buf);
+                    Ok(buf.len())
+                }
+
+                fn flush(&mut self) -> io::Result<()> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+    ($($hasher:ty),*) => {
+        impl_write!($([] $hasher),*);
+    };
+}
+
+impl_write!(
+    Md2, Md4, Md5, Ripemd128, Ripemd160, Ripemd256, Ripemd320, Sha1, Sha1Dc, Sha224, Sha256,
+    Sha384, Sha512, Sha512_224, Sha512_256, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Shake128,
+    Shake256, Blake3
+);
+
+impl_write!(
+    [const S: usize] Blake2b<S>,
+    [const S: usize] Blake2s<S>,
+    [H: Hasher] Hmac<H>,
+    [const RATE: usize] Sponge<RATE>
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a reader which hands out at most a few bytes at a time, and is interrupted in between
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupted: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupted = !self.interrupted;
+            if self.interrupted {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+
+            let length = buf.len().min(self.data.len()).min(3);
+            buf[..length].copy_from_slice(&self.data[..length]);
+            self.data = &self.data[length..];
+
+            Ok(length)
+        }
+    }
+
+    #[test]
+    fn io_write() {
+        let message = [0x61u8; 1000];
+
+        let mut hasher = Sha256::new();
+        io::copy(&mut &message[..], &mut hasher).unwrap();
+        assert_eq!(crate::sha256(message), hasher.finalize());
+
+        let mut hasher = Blake2b::<64>::new();
+        hasher.write_all(&message).unwrap();
+        assert_eq!(crate::blake2b(message), hasher.finalize());
+
+        let mut hmac = Hmac::<Sha1>::new("key");
+        hmac.write_all(&message).unwrap();
+        assert_eq!(crate::hmac::<Sha1>("key", message), hmac.finalize());
+    }
+
+    #[test]
+    fn io_hash_reader() {
+        let message = [0x62u8; 200];
+        let reader = Trickle {
+            data: &message,
+            interrupted: false,
+        };
+
+        assert_eq!(crate::md5(message), Md5::hash_reader(reader).unwrap());
+        assert_eq!(
+            crate::sha3_256(""),
+            Sha3_256::hash_reader(io::empty()).unwrap()
+        );
+        assert_eq!(
+            crate::sha1dc(message).digest,
+            Sha1Dc::hash_reader(&message[..]).unwrap()
+        );
+
+        // the SHAKE functions aren't a `Hasher`, so they have their own method
+        let reader = Trickle {
+            data: &message,
+            interrupted: false,
+        };
+        assert_eq!(
+            crate::shake128::<300>(message),
+            Shake128::hash_reader(reader).unwrap()
+        );
+        assert_eq!(
+            crate::shake256::<32>(""),
+            Shake256::hash_reader(io::empty()).unwrap()
+        );
+    }
+
+    #[test]
+    fn io_hashing_reader() {
+        let message = [0x63u8; 200];
+        let mut reader = HashingReader::new(
+            Trickle {
+                data: &message,
+                interrupted: false,
+            },
+            Md4::new(),
+        );
+
+        let mut first = [0; 100];
+        reader.read_exact(&mut first).unwrap();
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+
+        assert_eq!(100, rest.len());
+        assert!(reader.get_ref().data.is_empty());
+        assert_eq!(crate::md4(message), reader.finalize());
+
+        // the SHAKE functions aren't a `Hasher`, so they are finalized after taking them back
+        let mut reader = HashingReader::new(&message[..], Shake256::new());
+        io::copy(&mut reader, &mut io::sink()).unwrap();
+        let (_, hasher) = reader.into_parts();
+        assert_eq!(crate::shake256::<100>(message), hasher.finalize());
+    }
+
+    #[test]
+    fn io_hashing_writer() {
+        // a writer which accepts at most a few bytes at a time
+        struct Slow(Vec<u8>);
+
+        impl Write for Slow {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                let length = buf.len().min(7);
+                self.0.extend_from_slice(&buf[..length]);
+
+                Ok(length)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let message = [0x64u8; 200];
+        let mut writer = HashingWriter::new(Slow(Vec::new()), Ripemd160::new());
+        assert_eq!(7, writer.write(&message).unwrap());
+        writer.write_all(&message[7..]).unwrap();
+        writer.flush().unwrap();
+
+        let (inner, hasher) = writer.into_parts();
+        assert_eq!(&message[..], &inner.0[..]);
+        assert_eq!(crate::ripemd160(message), hasher.finalize());
+
+        let mut writer = HashingWriter::new(Vec::new(), Shake128::new());
+        writer.write_all(&message).unwrap();
+        let (_, hasher) = writer.into_parts();
+        assert_eq!(crate::shake128::<20>(message), hasher.finalize());
+    }
+
+    // write a file which is removed again when it goes out of scope
+    struct TemporaryFile(PathBuf);
+
+    impl TemporaryFile {
+        fn new(name: &str, contents: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!("lore-{}-{name}", std::process::id()));
+            std::fs::write(&path, contents).unwrap();
+
+            Self(path)
+        }
+    }
+
+    impl Drop for TemporaryFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn io_hash_file() {
+        // longer than the chunks which the file is read in
+        let message: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let file = TemporaryFile::new("hash_file", &message);
+        assert_eq!(crate::sha1(&message), Sha1::hash_file(&file.0).unwrap());
+        assert_eq!(crate::blake3(&message), Blake3::hash_file(&file.0).unwrap());
+        assert_eq!(
+            crate::shake128::<64>(&message),
+            Shake128::hash_file(&file.0).unwrap()
+        );
+
+        let empty = TemporaryFile::new("hash_file_empty", b"");
+        assert_eq!(crate::md5(""), Md5::hash_file(&empty.0).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn io_hash_file_mmap() {
+        let message: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let file = TemporaryFile::new("hash_file_mmap", &message);
+        assert_eq!(
+            crate::sha256(&message),
+            Sha256::hash_file_mmap(&file.0).unwrap()
+        );
+
+        assert_eq!(
+            crate::shake256::<64>(&message),
+            Shake256::hash_file_mmap(&file.0).unwrap()
+        );
+
+        let empty = TemporaryFile::new("hash_file_mmap_empty", b"");
+        assert_eq!(crate::sha256(""), Sha256::hash_file_mmap(&empty.0).unwrap());
+
+        let error = Sha256::hash_file_mmap("missing/file").unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+    }
+
+    #[test]
+    fn io_hash_file_errors() {
+        let error = Md4::hash_file("missing/file").unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+        assert_eq!(Path::new("missing/file"), error.path());
+        assert!(error
+            .to_string()
+            .starts_with("failed to hash missing/file: "));
+        assert!(error.source().is_some());
+
+        // a directory can be opened, but not read
+        let error = Md4::hash_file(std::env::temp_dir()).unwrap_err();
+        assert_eq!(std::env::temp_dir(), error.path());
+
+        let error: io::Error = Md4::hash_file("missing/file").unwrap_err().into();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+    }
+}
@@ -0,0 +1,241 @@
+//! Length extension attacks on MD4, MD5 and SHA-1.
+//!
+//! The digest of these Merkle–Damgård hash functions is their whole internal state after the padded message.
+//! Knowing only `H(secret || message)` and the length of `secret || message`, anyone can compute
+//! `H(secret || message || glue || suffix)` for a suffix of their choice, where the glue is the padding which was
+//! hashed after the original message.
+//! This is why `H(secret || message)` must not be used as a message authentication code, see [`crate::Hmac`] instead.
+//!
+//! # Examples
+//!
+//! Forging a signed request without knowing the secret:
+//!
+//! ```
+//! use lore::length_extension;
+//!
+//! // only the server knows the secret
+//! let secret = b"hunter2";
+//! let message = b"user=alice";
+//! let mut signed = secret.to_vec();
+//! signed.extend(message);
+//! let signature = lore::sha1(&signed);
+//!
+//! // the attacker knows the message and its signature, and guesses the length of the secret
+//! let length = (7 + message.len()) as u64;
+//! let forgery = length_extension::sha1(&signature, length, "&admin=true");
+//! let forged = forgery.message(message);
+//!
+//! // the server accepts the forged message, since its signature checks out
+//! let mut signed = secret.to_vec();
+//! signed.extend(&forged);
+//! assert_eq!(lore::sha1(signed), forgery.digest);
+//! ```
+
+use crate::hash::{md4::Md4, md5::Md5, sha1::Sha1, Digest};
+use alloc::vec::Vec;
+
+/// The result of a length extension attack: a suffix, the glue which goes before it, and the forged digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extension<const S: usize> {
+    /// The padding of the original message, which has to be placed between the original message and the suffix.
+    pub glue: Vec<u8>,
+    /// The data appended by the attacker.
+    pub suffix: Vec<u8>,
+    /// The digest of the original message, followed by the glue and the suffix.
+    pub digest: Digest<S>,
+}
+
+impl<const S: usize> Extension<S> {
+    /// Builds the forged message from the known part of the original message, i.e. without any secret prefix.
+    #[must_use]
+    pub fn message(&self, known: impl AsRef<[u8]>) -> Vec<u8> {
+        let mut message = known.as_ref().to_vec();
+        message.extend(&self.glue);
+        message.extend(&self.suffix);
+
+        message
+    }
+}
+
+/// The padding which MD4 appends to a message of `length` bytes.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let glue = lore::length_extension::md4_padding(3);
+///
+/// assert_eq!(glue.len(), 61);
+/// assert_eq!(glue[0], 0x80);
+/// assert_eq!(glue[53..], [24, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[must_use]
+pub fn md4_padding(length: u64) -> Vec<u8> {
+    crate::hash::md4::padding(length).as_ref().to_vec()
+}
+
+/// The padding which MD5 appends to a message of `length` bytes, which is the same as for MD4.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let glue = lore::length_extension::md5_padding(3);
+///
+/// assert_eq!(glue, lore::length_extension::md4_padding(3));
+/// ```
+#[must_use]
+pub fn md5_padding(length: u64) -> Vec<u8> {
+    crate::hash::md4::padding(length).as_ref().to_vec()
+}
+
+/// The padding which SHA-1 appends to a message of `length` bytes.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let glue = lore::length_extension::sha1_padding(3);
+///
+/// assert_eq!(glue.len(), 61);
+/// assert_eq!(glue[0], 0x80);
+/// assert_eq!(glue[53..], [0, 0, 0, 0, 0, 0, 0, 24]);
+/// ```
+#[must_use]
+pub fn sha1_padding(length: u64) -> Vec<u8> {
+    crate::hash::sha1::padding(length).as_ref().to_vec()
+}
+
+/// Computes the MD4 digest of a message followed by its glue padding and the suffix, from the digest and the length
+/// (in bytes) of the message alone.
+#[must_use]
+pub fn md4(digest: &Digest<16>, length: u64, suffix: impl AsRef<[u8]>) -> Extension<16> {
+    let suffix = suffix.as_ref().to_vec();
+    let mut hasher = Md4::resume(digest, length);
+    hasher.update(&suffix);
+
+    Extension {
+        glue: md4_padding(length),
+        suffix,
+        digest: hasher.finalize(),
+    }
+}
+
+/// Computes the MD5 digest of a message followed by its glue padding and the suffix, from the digest and the length
+/// (in bytes) of the message alone.
+#[must_use]
+pub fn md5(digest: &Digest<16>, length: u64, suffix: impl AsRef<[u8]>) -> Extension<16> {
+    let suffix = suffix.as_ref().to_vec();
+    let mut hasher = Md5::resume(digest, length);
+    hasher.update(&suffix);
+
+    Extension {
+        glue: md5_padding(length),
+        suffix,
+        digest: hasher.finalize(),
+    }
+}
+
+/// Computes the SHA-1 digest of a message followed by its glue padding and the suffix, from the digest and the
+/// length (in bytes) of the message alone.
+#[must_use]
+pub fn sha1(digest: &Digest<20>, length: u64, suffix: impl AsRef<[u8]>) -> Extension<20> {
+    let suffix = suffix.as_ref().to_vec();
+    let mut hasher = Sha1::resume(digest, length);
+    hasher.update(&suffix);
+
+    Extension {
+        glue: sha1_padding(length),
+        suffix,
+        digest: hasher.finalize(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"correct horse battery staple";
+
+    fn signed(message: &[u8]) -> Vec<u8> {
+        [SECRET, message].concat()
+    }
+
+    #[test]
+    fn length_extension_glue() {
+        // the glue is exactly what padding the original message produces
+        for length in [0, 1, 55, 56, 63, 64, 65, 200] {
+            let message = vec![0x61u8; length];
+            let glue = md4_padding(length as u64);
+            assert_eq!(
+                crate::hash::md4::pad(&message),
+                [&message[..], &glue].concat()
+            );
+            assert_eq!(md4_padding(length as u64), md5_padding(length as u64));
+
+            let glue = sha1_padding(length as u64);
+            assert_eq!(0, (length + glue.len()) % 64);
+            assert_eq!((length as u64 * 8).to_be_bytes(), glue[glue.len() - 8..]);
+        }
+    }
+
+    #[test]
+    fn length_extension_md4() {
+        for message in [&b""[..], b"count=10&lat=37.351", &[0x42; 100]] {
+            let digest = crate::md4(signed(message));
+            let length = (SECRET.len() + message.len()) as u64;
+            let forgery = md4(&digest, length, "&waffle=liege");
+
+            assert_eq!(
+                crate::md4(signed(&forgery.message(message))),
+                forgery.digest
+            );
+        }
+    }
+
+    #[test]
+    fn length_extension_md5() {
+        for message in [&b""[..], b"count=10&lat=37.351", &[0x42; 100]] {
+            let digest = crate::md5(signed(message));
+            let length = (SECRET.len() + message.len()) as u64;
+            let forgery = md5(&digest, length, "&waffle=liege");
+
+            assert_eq!(
+                crate::md5(signed(&forgery.message(message))),
+                forgery.digest
+            );
+        }
+    }
+
+    #[test]
+    fn length_extension_sha1() {
+        for message in [&b""[..], b"count=10&lat=37.351", &[0x42; 100]] {
+            let digest = crate::sha1(signed(message));
+            let length = (SECRET.len() + message.len()) as u64;
+            let forgery = sha1(&digest, length, [0x2a; 150]);
+
+            assert_eq!(
+                crate::sha1(signed(&forgery.message(message))),
+                forgery.digest
+            );
+        }
+    }
+
+    #[test]
+    fn length_extension_guess() {
+        // only the right guess of the secret length produces a valid forgery
+        let message = b"user=alice";
+        let digest = crate::sha1(signed(message));
+        let valid: Vec<_> = (0..64)
+            .filter(|secret_length| {
+                let forgery = sha1(&digest, (secret_length + message.len()) as u64, "&admin=1");
+                crate::sha1(signed(&forgery.message(message))) == forgery.digest
+            })
+            .collect();
+
+        assert_eq!(vec![SECRET.len()], valid);
+    }
+}
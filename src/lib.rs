@@ -1,5 +1,6 @@
-// rust nightly features
-#![feature(array_chunks)]
+// without the standard library, hashing only needs `core`, and everything else needs the `alloc` feature
+// (the tests always have the standard library, for the test harness)
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 // lints
 #![deny(missing_docs)]
 #![warn(clippy::all, clippy::pedantic, clippy::cargo)]
@@ -12,10 +13,107 @@
 // docs
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+mod algorithm;
+#[cfg(feature = "alloc")]
+pub mod cavp;
+#[cfg(feature = "alloc")]
+pub mod collision;
+#[cfg(feature = "alloc")]
+mod encoding;
 mod hash;
+mod hmac;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "alloc")]
+pub mod length_extension;
+#[cfg(feature = "alloc")]
+pub mod trace;
+#[cfg(feature = "alloc")]
+pub mod variant;
+
+// events need to allocate, so without `alloc` the hash functions can only be observed by `()`, which ignores them
+#[cfg(not(feature = "alloc"))]
+mod trace {
+    pub trait Observer {}
+
+    impl Observer for () {}
+
+    macro_rules! emit {
+        ($observer:expr, $event:expr) => {
+            let _ = &$observer;
+        };
+    }
+    pub(crate) use emit;
+}
+
+#[cfg(feature = "alloc")]
+pub use algorithm::{Algorithm, DynHasher, ParseAlgorithmError};
 
+pub use hash::blake2b::hash as blake2b;
+pub use hash::blake2b::Blake2b;
+pub use hash::blake2s::hash as blake2s;
+pub use hash::blake2s::Blake2s;
+pub use hash::blake3::derive_key as blake3_derive_key;
+pub use hash::blake3::hash as blake3;
+pub use hash::blake3::keyed_hash as blake3_keyed_hash;
+pub use hash::blake3::Blake3;
+pub use hash::blake3::Node as Blake3Node;
+pub use hash::blake3::Reader as Blake3Reader;
+pub use hash::keccak::Reader as ShakeReader;
 pub use hash::md2::hash as md2;
+pub use hash::md2::hash_const as md2_const;
+pub use hash::md2::Md2;
 pub use hash::md4::hash as md4;
+pub use hash::md4::hash_bits as md4_bits;
+pub use hash::md4::hash_const as md4_const;
+pub use hash::md4::Md4;
 pub use hash::md5::hash as md5;
+pub use hash::md5::hash_bits as md5_bits;
+pub use hash::md5::hash_const as md5_const;
+pub use hash::md5::Md5;
+pub use hash::ripemd128::hash as ripemd128;
+pub use hash::ripemd128::Ripemd128;
+pub use hash::ripemd160::hash as ripemd160;
+pub use hash::ripemd160::Ripemd160;
+pub use hash::ripemd256::hash as ripemd256;
+pub use hash::ripemd256::Ripemd256;
+pub use hash::ripemd320::hash as ripemd320;
+pub use hash::ripemd320::Ripemd320;
 pub use hash::sha1::hash as sha1;
-pub use hash::Digest;
+pub use hash::sha1::hash_bits as sha1_bits;
+pub use hash::sha1::hash_const as sha1_const;
+pub use hash::sha1::Sha1;
+pub use hash::sha1dc::hash as sha1dc;
+pub use hash::sha1dc::safe_hash as sha1dc_safe;
+pub use hash::sha1dc::Detection as Sha1Detection;
+pub use hash::sha1dc::Sha1Dc;
+pub use hash::sha224::hash as sha224;
+pub use hash::sha224::Sha224;
+pub use hash::sha256::hash as sha256;
+pub use hash::sha256::Sha256;
+pub use hash::sha384::hash as sha384;
+pub use hash::sha384::Sha384;
+pub use hash::sha3_224::hash as sha3_224;
+pub use hash::sha3_224::Sha3_224;
+pub use hash::sha3_256::hash as sha3_256;
+pub use hash::sha3_256::Sha3_256;
+pub use hash::sha3_384::hash as sha3_384;
+pub use hash::sha3_384::Sha3_384;
+pub use hash::sha3_512::hash as sha3_512;
+pub use hash::sha3_512::Sha3_512;
+pub use hash::sha512::hash as sha512;
+pub use hash::sha512::Sha512;
+pub use hash::sha512_224::hash as sha512_224;
+pub use hash::sha512_224::Sha512_224;
+pub use hash::sha512_256::hash as sha512_256;
+pub use hash::sha512_256::Sha512_256;
+pub use hash::shake128::hash as shake128;
+pub use hash::shake128::Shake128;
+pub use hash::shake256::hash as shake256;
+pub use hash::shake256::Shake256;
+pub use hash::{Digest, Hasher, ParseDigestError};
+pub use hmac::{hmac, Hmac};
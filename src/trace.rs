@@ -0,0 +1,415 @@
+//! Tracing of the intermediate values of a hash computation.
+//!
+//! Every algorithm has a `trace` function in this module, which computes the same digest as the corresponding hash
+//! function, but reports each intermediate value to an [`Observer`] as an [`Event`].
+//! The normal hash functions and streaming hashers never report anything.
+//!
+//! # Examples
+//!
+//! Collecting every event into a `Vec`:
+//!
+//! ```
+//! use lore::trace::{self, Event};
+//!
+//! let mut events = Vec::new();
+//! let digest = trace::sha1("abc", &mut events);
+//!
+//! assert_eq!(digest, lore::sha1("abc"));
+//!
+//! // the 80 words of the SHA-1 message schedule
+//! let schedule = events.iter().find_map(|event| match event {
+//!     Event::Schedule { words } => Some(words),
+//!     _ => None,
+//! });
+//! assert_eq!(schedule.unwrap().len(), 80);
+//! ```
+//!
+//! Closures are observers as well:
+//!
+//! ```
+//! use lore::trace::{self, Event};
+//!
+//! let mut steps = 0;
+//! trace::md5("abc", &mut |event| {
+//!     if let Event::Step { .. } = event {
+//!         steps += 1;
+//!     }
+//! });
+//!
+//! assert_eq!(steps, 64);
+//! ```
+
+mod export;
+
+use alloc::vec::Vec;
+
+pub use export::Trace;
+
+pub use crate::hash::blake2b::trace as blake2b;
+pub use crate::hash::blake2s::trace as blake2s;
+pub use crate::hash::blake3::trace as blake3;
+pub use crate::hash::md2::trace as md2;
+pub use crate::hash::md4::trace as md4;
+pub use crate::hash::md5::trace as md5;
+pub use crate::hash::ripemd128::trace as ripemd128;
+pub use crate::hash::ripemd160::trace as ripemd160;
+pub use crate::hash::ripemd256::trace as ripemd256;
+pub use crate::hash::ripemd320::trace as ripemd320;
+pub use crate::hash::sha1::trace as sha1;
+pub use crate::hash::sha224::trace as sha224;
+pub use crate::hash::sha256::trace as sha256;
+pub use crate::hash::sha384::trace as sha384;
+pub use crate::hash::sha3_224::trace as sha3_224;
+pub use crate::hash::sha3_256::trace as sha3_256;
+pub use crate::hash::sha3_384::trace as sha3_384;
+pub use crate::hash::sha3_512::trace as sha3_512;
+pub use crate::hash::sha512::trace as sha512;
+pub use crate::hash::sha512_224::trace as sha512_224;
+pub use crate::hash::sha512_256::trace as sha512_256;
+pub use crate::hash::shake128::trace as shake128;
+pub use crate::hash::shake256::trace as shake256;
+
+/// An intermediate value of a hash computation, in the order in which it is computed.
+///
+/// A trace starts with the padded message, followed by the events of each block in turn, and ends with the digest.
+/// MD2 additionally reports its checksum before the padded message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Event {
+    /// The whole message after padding, before it is split into blocks.
+    ///
+    /// For algorithms which do not pad in this way (BLAKE2 and BLAKE3), the final block is filled with zeros.
+    /// For MD2, the checksum is included as the last block.
+    Padded {
+        /// The padded message.
+        message: Vec<u8>,
+    },
+    /// A block of the padded message is about to be compressed.
+    Block {
+        /// The position of the block, counted from zero.
+        index: usize,
+        /// The block, split into the words which the compression function operates on.
+        words: Words,
+    },
+    /// The message schedule of the current block, e.g. the 80 expanded words of SHA-1.
+    Schedule {
+        /// The expanded words, one per step.
+        words: Words,
+    },
+    /// The working state after a single step (or round) of the compression function.
+    ///
+    /// This is the value returned by the algorithm's step function.
+    /// For RIPEMD, the state of the left line is followed by the state of the right line.
+    Step {
+        /// The position of the step within the current block, counted from zero.
+        index: usize,
+        /// The working state.
+        state: Words,
+    },
+    /// The working state is added to (or for BLAKE, xored with) the chaining value, producing the next chaining value.
+    ///
+    /// MD2, SHA-3 and SHAKE have no such addition, so this is never reported for them.
+    ChainingValue {
+        /// The chaining value before the current block.
+        previous: Words,
+        /// The working state after the last step.
+        working: Words,
+        /// The chaining value after the current block.
+        next: Words,
+    },
+    /// The MD2 checksum, which is appended to the message as its final block.
+    Checksum {
+        /// The checksum bytes.
+        checksum: Vec<u8>,
+    },
+    /// The final digest.
+    Digest {
+        /// The digest bytes.
+        bytes: Vec<u8>,
+    },
+}
+
+/// A sequence of words, in the width used by the algorithm that produced them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Words {
+    /// 8-bit words, as used by MD2.
+    U8(Vec<u8>),
+    /// 32-bit words, as used by MD4, MD5, RIPEMD, SHA-1, SHA-224, SHA-256, BLAKE2s and BLAKE3.
+    U32(Vec<u32>),
+    /// 64-bit words, as used by SHA-384, SHA-512, SHA-3, SHAKE and BLAKE2b.
+    U64(Vec<u64>),
+}
+
+impl Words {
+    /// Returns the number of words.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Words::U8(words) => words.len(),
+            Words::U32(words) => words.len(),
+            Words::U64(words) => words.len(),
+        }
+    }
+
+    /// Returns `true` if there are no words.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the width of each word in bits.
+    #[must_use]
+    pub fn bits(&self) -> u32 {
+        match self {
+            Words::U8(_) => u8::BITS,
+            Words::U32(_) => u32::BITS,
+            Words::U64(_) => u64::BITS,
+        }
+    }
+
+    /// Returns the words widened to 64 bits.
+    #[must_use]
+    pub fn to_u64s(&self) -> Vec<u64> {
+        match self {
+            Words::U8(words) => words.iter().map(|&w| w.into()).collect(),
+            Words::U32(words) => words.iter().map(|&w| w.into()).collect(),
+            Words::U64(words) => words.clone(),
+        }
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Words {
+    fn from(words: [u8; N]) -> Self {
+        Words::U8(words.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u32; N]> for Words {
+    fn from(words: [u32; N]) -> Self {
+        Words::U32(words.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u64; N]> for Words {
+    fn from(words: [u64; N]) -> Self {
+        Words::U64(words.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for Words {
+    fn from(words: Vec<u8>) -> Self {
+        Words::U8(words)
+    }
+}
+
+impl From<Vec<u32>> for Words {
+    fn from(words: Vec<u32>) -> Self {
+        Words::U32(words)
+    }
+}
+
+impl From<Vec<u64>> for Words {
+    fn from(words: Vec<u64>) -> Self {
+        Words::U64(words)
+    }
+}
+
+impl From<&[u8]> for Words {
+    fn from(words: &[u8]) -> Self {
+        Words::U8(words.to_vec())
+    }
+}
+
+impl From<&[u32]> for Words {
+    fn from(words: &[u32]) -> Self {
+        Words::U32(words.to_vec())
+    }
+}
+
+impl From<&[u64]> for Words {
+    fn from(words: &[u64]) -> Self {
+        Words::U64(words.to_vec())
+    }
+}
+
+/// Receives the events of a traced hash computation.
+///
+/// Implemented for `Vec<Event>`, which collects every event, for closures taking an `Event`, and for `()`, which
+/// ignores every event.
+pub trait Observer {
+    /// Called with each event, in the order in which the values are computed.
+    fn observe(&mut self, event: Event);
+
+    /// Whether events should be produced at all.
+    ///
+    /// When this returns `false`, no events are constructed, so observing with `()` costs nothing.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+impl Observer for () {
+    fn observe(&mut self, _event: Event) {}
+
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+impl Observer for Vec<Event> {
+    fn observe(&mut self, event: Event) {
+        self.push(event);
+    }
+}
+
+impl<F: FnMut(Event)> Observer for F {
+    fn observe(&mut self, event: Event) {
+        self(event);
+    }
+}
+
+// construct and report an event, but only if the observer is interested
+macro_rules! emit {
+    ($observer:expr, $event:expr) => {
+        if $crate::trace::Observer::is_enabled(&*$observer) {
+            $crate::trace::Observer::observe(&mut *$observer, $event);
+        }
+    };
+}
+pub(crate) use emit;
+
+// report the padded message, then compress each of its blocks in turn, reporting the words of each block first
+pub(crate) fn compress_blocks<const N: usize, S, W: Into<Words>, O: Observer + ?Sized>(
+    padded: &[u8],
+    initial: S,
+    observer: &mut O,
+    words: impl Fn([u8; N]) -> W,
+    mut compress: impl FnMut(S, &[u8; N], &mut O) -> S,
+) -> S {
+    emit!(
+        observer,
+        Event::Padded {
+            message: padded.to_vec(),
+        }
+    );
+
+    padded
+        .as_chunks::<N>()
+        .0
+        .iter()
+        .enumerate()
+        .fold(initial, |state, (index, block)| {
+            emit!(
+                observer,
+                Event::Block {
+                    index,
+                    words: words(*block).into(),
+                }
+            );
+            compress(state, block, observer)
+        })
+}
+
+// report the final digest
+pub(crate) fn digest<const N: usize, O: Observer + ?Sized>(
+    digest: crate::Digest<N>,
+    observer: &mut O,
+) -> crate::Digest<N> {
+    emit!(
+        observer,
+        Event::Digest {
+            bytes: digest.as_ref().to_vec(),
+        }
+    );
+
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Algorithm;
+
+    #[test]
+    fn trace_digest() {
+        // lengths around the block sizes, and enough for several BLAKE3 chunks
+        for length in [
+            0, 1, 55, 56, 64, 111, 112, 128, 135, 136, 168, 1024, 1025, 3000,
+        ] {
+            let message = vec![0xa5u8; length];
+            for algorithm in Algorithm::ALL {
+                let mut events = Vec::new();
+                let digest = algorithm.trace(&message, &mut events);
+
+                assert_eq!(algorithm.digest(&message), digest, "{algorithm}");
+                assert_eq!(digest, algorithm.trace(&message, &mut ()));
+                assert_eq!(Some(&Event::Digest { bytes: digest }), events.last());
+            }
+        }
+    }
+
+    #[test]
+    fn trace_blocks() {
+        let message = [0x42u8; 200];
+        for algorithm in Algorithm::ALL {
+            let mut events = Vec::new();
+            algorithm.trace(message, &mut events);
+
+            let padded = events.iter().find_map(|event| match event {
+                Event::Padded { message } => Some(message.len()),
+                _ => None,
+            });
+            let padded = padded.unwrap();
+            assert_eq!(0, padded % algorithm.block_size(), "{algorithm}");
+
+            // blocks are numbered in order
+            let blocks: Vec<_> = events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::Block { index, .. } => Some(*index),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!((0..blocks.len()).collect::<Vec<_>>(), blocks);
+            if algorithm != Algorithm::Blake3 {
+                assert_eq!(padded / algorithm.block_size(), blocks.len(), "{algorithm}");
+            }
+        }
+    }
+
+    #[test]
+    fn trace_md2_checksum() {
+        let mut events = Vec::new();
+        crate::hash::md2::trace("abc", &mut events);
+
+        assert!(matches!(events[0], Event::Checksum { ref checksum } if checksum.len() == 16));
+        assert!(matches!(events[1], Event::Padded { ref message } if message.len() == 32));
+    }
+
+    #[test]
+    fn trace_shake_squeeze() {
+        // 400 bytes of output need two more permutations after absorbing the single block
+        let mut events = Vec::new();
+        let digest = shake128::<400, _>("abc", &mut events);
+        let steps = events
+            .iter()
+            .filter(|event| matches!(event, Event::Step { .. }))
+            .count();
+
+        assert_eq!(3 * 24, steps);
+        assert_eq!(crate::shake128::<400>("abc"), digest);
+    }
+
+    #[test]
+    fn trace_observers() {
+        let mut count = 0;
+        md4("abc", &mut |_| count += 1);
+
+        let mut events = Vec::new();
+        md4("abc", &mut events);
+        assert_eq!(events.len(), count);
+
+        assert!(!().is_enabled());
+        assert!(events.is_enabled());
+    }
+}
@@ -0,0 +1,472 @@
+use crate::trace::{Event, Words};
+use crate::Algorithm;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+/// A complete trace of a hash computation, which can be exported as JSON or as a standalone HTML page.
+///
+/// The trace is recorded from the crate's own step functions, see the [`trace`](crate::trace) module.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use lore::{trace::Trace, Algorithm};
+///
+/// let trace = Trace::new(Algorithm::Md5, "abc");
+/// assert_eq!(trace.digest(), lore::md5("abc").as_ref());
+///
+/// let json = trace.to_json();
+/// assert!(json.contains(r#""digest": "900150983cd24fb0d6963f7d28e17f72""#));
+///
+/// let html = trace.to_html();
+/// assert!(html.starts_with("<!DOCTYPE html>"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    algorithm: Algorithm,
+    message: Vec<u8>,
+    events: Vec<Event>,
+    digest: Vec<u8>,
+}
+
+impl Trace {
+    /// Hash the message with the algorithm, recording every event.
+    #[must_use]
+    pub fn new(algorithm: Algorithm, message: impl AsRef<[u8]>) -> Self {
+        let message = message.as_ref().to_vec();
+        let mut events = Vec::new();
+        let digest = algorithm.trace(&message, &mut events);
+
+        Self {
+            algorithm,
+            message,
+            events,
+            digest,
+        }
+    }
+
+    /// The algorithm which was traced.
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// The message which was hashed, before padding.
+    #[must_use]
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// The recorded events, in the order in which they were reported.
+    #[must_use]
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// The digest of the message.
+    #[must_use]
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// The bits of each step's state which differ from the state the step started from, as one mask per word.
+    ///
+    /// The result has one entry per event, which is `None` for events other than steps, and for steps whose
+    /// starting state is not part of the trace (e.g. the first round of BLAKE2, which starts from the IV).
+    #[must_use]
+    pub fn changed_bits(&self) -> Vec<Option<Vec<u64>>> {
+        // MD4 and MD5 rotate the registers between steps, rather than in the step function
+        let rotates = matches!(self.algorithm, Algorithm::Md4 | Algorithm::Md5);
+
+        let mut input: Option<Vec<u64>> = None;
+        self.events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| match event {
+                Event::Block { .. } => {
+                    // the first step of a block starts from the chaining value, if the working state is that wide
+                    input = self.events[i..].iter().find_map(|event| match event {
+                        Event::ChainingValue { previous, .. } => Some(previous.to_u64s()),
+                        _ => None,
+                    });
+                    None
+                }
+                Event::Step { state, .. } => {
+                    let state = state.to_u64s();
+                    let changed = input
+                        .as_ref()
+                        .filter(|input| input.len() == state.len())
+                        .map(|input| input.iter().zip(&state).map(|(a, b)| a ^ b).collect());
+
+                    let mut next = state;
+                    if rotates {
+                        next.rotate_right(1);
+                    }
+                    input = Some(next);
+
+                    changed
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Export the trace as a JSON document.
+    ///
+    /// Bytes are written as hexadecimal strings, and words as arrays of hexadecimal strings of the word's width.
+    /// Each step also lists the bits which changed, in the same format as its state, see [`Trace::changed_bits`].
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let _ = writeln!(json, "{{");
+        let _ = writeln!(json, r#"  "algorithm": "{}","#, self.algorithm);
+        let _ = writeln!(json, r#"  "message": "{}","#, hex(&self.message));
+        let _ = writeln!(json, r#"  "digest": "{}","#, hex(&self.digest));
+        let _ = writeln!(json, r#"  "events": ["#);
+
+        let changed = self.changed_bits();
+        for (i, (event, changed)) in self.events.iter().zip(changed).enumerate() {
+            let separator = if i + 1 == self.events.len() { "" } else { "," };
+            let event = match event {
+                Event::Padded { message } => {
+                    format!(r#"{{"event": "padded", "message": "{}"}}"#, hex(message))
+                }
+                Event::Block { index, words } => format!(
+                    r#"{{"event": "block", "index": {index}, "words": {}}}"#,
+                    json_words(words)
+                ),
+                Event::Schedule { words } => {
+                    format!(r#"{{"event": "schedule", "words": {}}}"#, json_words(words))
+                }
+                Event::Step { index, state } => {
+                    let changed = match changed {
+                        Some(masks) => json_words(&widen(state, masks)),
+                        None => "null".to_string(),
+                    };
+                    format!(
+                        r#"{{"event": "step", "index": {index}, "state": {}, "changed": {changed}}}"#,
+                        json_words(state)
+                    )
+                }
+                Event::ChainingValue {
+                    previous,
+                    working,
+                    next,
+                } => format!(
+                    r#"{{"event": "chaining_value", "previous": {}, "working": {}, "next": {}}}"#,
+                    json_words(previous),
+                    json_words(working),
+                    json_words(next)
+                ),
+                Event::Checksum { checksum } => {
+                    format!(
+                        r#"{{"event": "checksum", "checksum": "{}"}}"#,
+                        hex(checksum)
+                    )
+                }
+                Event::Digest { bytes } => {
+                    format!(r#"{{"event": "digest", "digest": "{}"}}"#, hex(bytes))
+                }
+            };
+            let _ = writeln!(json, "    {event}{separator}");
+        }
+
+        let _ = writeln!(json, "  ]");
+        let _ = writeln!(json, "}}");
+        json
+    }
+
+    /// Export the trace as a self-contained HTML page, which walks through the padding, the blocks and their steps.
+    ///
+    /// The bits of each step which changed are highlighted, see [`Trace::changed_bits`].
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let name = self.algorithm.name().to_uppercase();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{name} trace</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{name}</h1>\n"
+        );
+
+        let message = match core::str::from_utf8(&self.message) {
+            Ok(text) => format!("&quot;{}&quot;", escape(text)),
+            Err(_) => format!("<code>{}</code>", hex(&self.message)),
+        };
+        let _ = writeln!(
+            html,
+            "<p>Message: {message} ({} bytes)</p>",
+            self.message.len()
+        );
+
+        let changed = self.changed_bits();
+        let mut steps = String::new();
+        for (event, changed) in self.events.iter().zip(changed) {
+            // consecutive steps are collected into a single table
+            if !matches!(event, Event::Step { .. }) && !steps.is_empty() {
+                let _ = writeln!(html, "<h3>Steps</h3>\n<table>\n{steps}</table>");
+                steps.clear();
+            }
+
+            match event {
+                Event::Padded { message } => self.html_padding(&mut html, message),
+                Event::Block { index, words } => {
+                    let _ = writeln!(html, "<h2>Block {index}</h2>\n<h3>Words</h3>");
+                    let _ = writeln!(html, "{}", html_words(words));
+                }
+                Event::Schedule { words } => {
+                    let _ = writeln!(html, "<h3>Message schedule</h3>");
+                    let _ = writeln!(html, "{}", html_words(words));
+                }
+                Event::Step { index, state } => {
+                    let _ = write!(steps, "<tr><th>{index}</th>");
+                    for (i, word) in words(state).into_iter().enumerate() {
+                        let mask = changed.as_ref().map_or(0, |masks| masks[i]);
+                        let _ = write!(
+                            steps,
+                            "<td><code>{word:0width$x}</code><br><code class=\"bits\">{}</code></td>",
+                            html_bits(word, mask, state.bits()),
+                            width = state.bits() as usize / 4
+                        );
+                    }
+                    let _ = writeln!(steps, "</tr>");
+                }
+                Event::ChainingValue {
+                    previous,
+                    working,
+                    next,
+                } => {
+                    let _ = writeln!(html, "<h3>Chaining value</h3>\n<table>");
+                    for (label, words) in
+                        [("Previous", previous), ("Working", working), ("Next", next)]
+                    {
+                        let _ = write!(html, "<tr><th>{label}</th>");
+                        for word in self::words(words) {
+                            let _ = write!(
+                                html,
+                                "<td><code>{word:0width$x}</code></td>",
+                                width = words.bits() as usize / 4
+                            );
+                        }
+                        let _ = writeln!(html, "</tr>");
+                    }
+                    let _ = writeln!(html, "</table>");
+                }
+                Event::Checksum { checksum } => {
+                    let _ = writeln!(
+                        html,
+                        "<h2>Checksum</h2>\n<p><code>{}</code></p>",
+                        hex(checksum)
+                    );
+                }
+                Event::Digest { bytes } => {
+                    let _ = writeln!(html, "<h2>Digest</h2>\n<p><code>{}</code></p>", hex(bytes));
+                }
+            }
+        }
+
+        let _ = writeln!(html, "</body>\n</html>");
+        html
+    }
+
+    // the padded message, with the message, the padding and the MD2 checksum told apart
+    fn html_padding(&self, html: &mut String, padded: &[u8]) {
+        let checksum = if self.algorithm == Algorithm::Md2 {
+            16
+        } else {
+            0
+        };
+        let block_size = self.algorithm.block_size();
+        let _ = writeln!(
+            html,
+            "<h2>Padding</h2>\n<p>The message is padded to {} bytes, which are split into {} blocks of {block_size} bytes.</p>",
+            padded.len(),
+            padded.len() / block_size
+        );
+
+        let _ = write!(html, "<p class=\"bytes\">");
+        for (i, byte) in padded.iter().enumerate() {
+            let class = if i < self.message.len() {
+                "message"
+            } else if i + checksum < padded.len() {
+                "padding"
+            } else {
+                "checksum"
+            };
+            if i > 0 && i % block_size == 0 {
+                let _ = write!(html, "<br>");
+            }
+            let _ = write!(html, "<span class=\"{class}\">{byte:02x}</span> ");
+        }
+        let _ = writeln!(html, "</p>");
+    }
+}
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; } \
+table { border-collapse: collapse; margin-bottom: 1em; } \
+th, td { border: 1px solid #ccc; padding: 0.2em 0.4em; text-align: right; vertical-align: top; } \
+code { font-family: monospace; } \
+.bits { font-size: 0.7em; color: #888; } \
+.bits mark { background: #fd5; color: #000; } \
+.bytes { font-family: monospace; line-height: 1.6; } \
+.message { background: #def; } \
+.padding { background: #fed; } \
+.checksum { background: #dfd; }";
+
+fn words(words: &Words) -> Vec<u64> {
+    words.to_u64s()
+}
+
+// masks have the same width as the words they belong to
+fn widen(words: &Words, masks: Vec<u64>) -> Words {
+    match words {
+        Words::U8(_) => Words::U8(masks.into_iter().map(|mask| mask as u8).collect()),
+        Words::U32(_) => Words::U32(masks.into_iter().map(|mask| mask as u32).collect()),
+        Words::U64(_) => Words::U64(masks),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+fn json_words(words: &Words) -> String {
+    let width = words.bits() as usize / 4;
+    let words: Vec<_> = self::words(words)
+        .into_iter()
+        .map(|word| format!("\"{word:0width$x}\""))
+        .collect();
+
+    format!("[{}]", words.join(", "))
+}
+
+fn html_words(words: &Words) -> String {
+    let width = words.bits() as usize / 4;
+    let mut html = String::from("<table>\n<tr>");
+    for i in 0..words.len() {
+        let _ = write!(html, "<th>{i}</th>");
+    }
+    let _ = write!(html, "</tr>\n<tr>");
+    for word in self::words(words) {
+        let _ = write!(html, "<td><code>{word:0width$x}</code></td>");
+    }
+    html.push_str("</tr>\n</table>");
+
+    html
+}
+
+// the bits of a word from the most significant one, with runs of changed bits marked
+fn html_bits(word: u64, mask: u64, bits: u32) -> String {
+    let mut html = String::new();
+    let mut marked = false;
+    for i in (0..bits).rev() {
+        let changed = (mask >> i) & 1 == 1;
+        if changed != marked {
+            html.push_str(if changed { "<mark>" } else { "</mark>" });
+            marked = changed;
+        }
+        html.push(if (word >> i) & 1 == 1 { '1' } else { '0' });
+    }
+    if marked {
+        html.push_str("</mark>");
+    }
+
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_changed_bits() {
+        let trace = Trace::new(Algorithm::Md5, "tihi xd");
+        let changed: Vec<_> = trace.changed_bits().into_iter().flatten().collect();
+        assert_eq!(64, changed.len());
+
+        // only the register which the step computes changes
+        assert_eq!(vec![0x5954a129 ^ 0x67452301, 0, 0, 0], changed[0]);
+        assert_eq!(vec![0x3171555d ^ 0x10325476, 0, 0, 0], changed[1]);
+        assert!(changed.iter().all(|masks| masks[1..] == [0, 0, 0]));
+
+        // the SHA-1 registers shift by one each step
+        let trace = Trace::new(Algorithm::Sha1, "abc");
+        let changed = trace.changed_bits().into_iter().flatten().next().unwrap();
+        assert_eq!(0x67452301 ^ 0xefcdab89, changed[1]);
+    }
+
+    #[test]
+    fn export_json() {
+        let trace = Trace::new(Algorithm::Md5, "tihi xd");
+        let json = trace.to_json();
+
+        assert!(json.contains(r#""message": "74696869207864""#));
+        assert!(json.contains(
+            r#"{"event": "step", "index": 0, "state": ["5954a129", "efcdab89", "98badcfe", "10325476"], "changed": ["3e118228", "00000000", "00000000", "00000000"]}"#
+        ));
+        assert!(
+            json.contains(r#"{"event": "digest", "digest": "73e51861b65c1e83d6136fb6a002585e"}"#)
+        );
+        assert!(json.ends_with("  ]\n}\n"));
+
+        // brackets are balanced and events are separated by commas
+        assert_eq!(json.matches('[').count(), json.matches(']').count());
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+        assert_eq!(trace.events().len() - 1, json.matches("},\n").count());
+
+        let json = Trace::new(Algorithm::Md2, "abc").to_json();
+        assert!(json.contains(r#"{"event": "checksum", "#));
+        assert!(json.contains(r#""state": ["#));
+    }
+
+    #[test]
+    fn export_html() {
+        let html = Trace::new(Algorithm::Sha1, "<abc>").to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+        assert!(html.contains("Message: &quot;&lt;abc&gt;&quot; (5 bytes)"));
+        assert!(html.contains("<h2>Block 0</h2>"));
+        assert!(html.contains("<h3>Message schedule</h3>"));
+        assert_eq!(1, html.matches("<h3>Steps</h3>").count());
+        assert_eq!(80 * 5, html.matches("<code class=\"bits\">").count());
+        assert!(html.contains("<mark>"));
+        assert!(html.contains(&crate::sha1("<abc>").to_string()));
+
+        let html = Trace::new(Algorithm::Md2, [0xff]).to_html();
+        assert!(html.contains("Message: <code>ff</code> (1 bytes)"));
+        assert!(html.contains("<span class=\"checksum\">"));
+        assert_eq!(2, html.matches("<h3>Steps</h3>").count());
+    }
+
+    #[test]
+    fn export_bits() {
+        assert_eq!("0<mark>11</mark>0", html_bits(0b0110, 0b0110, 4));
+        assert_eq!(
+            "<mark>1</mark>00<mark>1</mark>",
+            html_bits(0b1001, 0b1001, 4)
+        );
+        assert_eq!("1010", html_bits(0b1010, 0, 4));
+    }
+}
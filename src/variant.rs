@@ -0,0 +1,383 @@
+//! Reduced-round and tweaked variants of MD4, MD5 and SHA-1, as well as SHA-0.
+//!
+//! Attacks on hash functions are usually first found, and easiest to study, on versions with fewer steps than the
+//! full function, like 32-step MD5 or 58-step SHA-1.
+//! A [`Variant`] runs the compression function of one of these hash functions with any number of steps, up to the
+//! full number, and from any initial value (IV) instead of the standard one.
+//! SHA-0 is the original version of SHA-1, which lacks the rotation in its message schedule.
+//!
+//! The variants use the same step functions as the full hash functions, and report the same events when traced,
+//! see the [`trace`] module.
+//!
+//! # Examples
+//!
+//! Hashing with 32-step MD5 and a custom IV:
+//!
+//! ```
+//! use lore::variant::Variant;
+//!
+//! let reduced = Variant::md5().steps(32).iv([0, 0, 0, 0]);
+//! let digest = reduced.hash("abc");
+//!
+//! assert_ne!(digest, lore::md5("abc"));
+//! assert_eq!(Variant::md5().hash("abc"), lore::md5("abc"));
+//! ```
+
+use crate::hash::{
+    bytes_to_words_be, bytes_to_words_le, md4, md5, sha1, words_to_bytes_be, words_to_bytes_le,
+    Digest,
+};
+use crate::trace::{self, compress_blocks, Observer};
+use core::convert::TryInto;
+
+// the hash functions which variants can be built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Md4,
+    Md5,
+    Sha0,
+    Sha1,
+}
+
+impl Function {
+    // the number of steps of the full compression function
+    fn steps(self) -> usize {
+        match self {
+            Function::Md4 => 48,
+            Function::Md5 => 64,
+            Function::Sha0 | Function::Sha1 => 80,
+        }
+    }
+}
+
+/// A hash function built from the compression function of MD4, MD5, SHA-0 or SHA-1, with a configurable number of
+/// steps and initial value.
+///
+/// `N` is the number of 32-bit words in the state: 4 for MD4 and MD5, 5 for SHA-0 and SHA-1.
+/// Messages are padded like for the original function, so a variant with all steps and the standard IV computes the
+/// original digest.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use lore::variant::Variant;
+///
+/// let sha0 = Variant::sha0();
+///
+/// assert_eq!(sha0.hash("abc").to_string(), "0164b8a914cd2a5e74c4f7ff082c4d97f1edf880");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Variant<const N: usize> {
+    function: Function,
+    steps: usize,
+    iv: [u32; N],
+}
+
+impl Variant<4> {
+    /// MD4 with all 48 steps and the standard IV.
+    #[must_use]
+    pub fn md4() -> Self {
+        Self::new(Function::Md4, [md4::A, md4::B, md4::C, md4::D])
+    }
+
+    /// MD5 with all 64 steps and the standard IV.
+    #[must_use]
+    pub fn md5() -> Self {
+        Self::new(Function::Md5, [md5::A, md5::B, md5::C, md5::D])
+    }
+
+    /// Computes the digest of the input bytes with this variant.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::variant::Variant;
+    ///
+    /// assert_eq!(Variant::md4().hash("abc"), lore::md4("abc"));
+    /// ```
+    #[must_use]
+    pub fn hash(&self, message: impl AsRef<[u8]>) -> Digest<16> {
+        self.trace(message, &mut ())
+    }
+
+    /// Computes the digest of the input bytes with this variant, reporting every intermediate value to the observer.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::{trace::Event, variant::Variant};
+    ///
+    /// let mut events = Vec::new();
+    /// Variant::md5().steps(16).trace("abc", &mut events);
+    /// let steps = events.iter().filter(|event| matches!(event, Event::Step { .. }));
+    ///
+    /// assert_eq!(steps.count(), 16);
+    /// ```
+    pub fn trace<O: Observer + ?Sized>(
+        &self,
+        message: impl AsRef<[u8]>,
+        observer: &mut O,
+    ) -> Digest<16> {
+        let digest: [u8; 16] = self.digest(message, observer);
+
+        trace::digest(digest[..].try_into().unwrap(), observer)
+    }
+}
+
+impl Variant<5> {
+    /// SHA-0 with all 80 steps and the standard IV, which is the same as the one of SHA-1.
+    #[must_use]
+    pub fn sha0() -> Self {
+        Self::new(
+            Function::Sha0,
+            [sha1::H0, sha1::H1, sha1::H2, sha1::H3, sha1::H4],
+        )
+    }
+
+    /// SHA-1 with all 80 steps and the standard IV.
+    #[must_use]
+    pub fn sha1() -> Self {
+        Self::new(
+            Function::Sha1,
+            [sha1::H0, sha1::H1, sha1::H2, sha1::H3, sha1::H4],
+        )
+    }
+
+    /// Computes the digest of the input bytes with this variant.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::variant::Variant;
+    ///
+    /// assert_eq!(Variant::sha1().hash("abc"), lore::sha1("abc"));
+    /// ```
+    #[must_use]
+    pub fn hash(&self, message: impl AsRef<[u8]>) -> Digest<20> {
+        self.trace(message, &mut ())
+    }
+
+    /// Computes the digest of the input bytes with this variant, reporting every intermediate value to the observer.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::{trace::Event, variant::Variant};
+    ///
+    /// let mut events = Vec::new();
+    /// Variant::sha1().steps(58).trace("abc", &mut events);
+    /// let steps = events.iter().filter(|event| matches!(event, Event::Step { .. }));
+    ///
+    /// assert_eq!(steps.count(), 58);
+    /// ```
+    pub fn trace<O: Observer + ?Sized>(
+        &self,
+        message: impl AsRef<[u8]>,
+        observer: &mut O,
+    ) -> Digest<20> {
+        let digest: [u8; 20] = self.digest(message, observer);
+
+        trace::digest(digest[..].try_into().unwrap(), observer)
+    }
+}
+
+impl<const N: usize> Variant<N> {
+    fn new(function: Function, iv: [u32; N]) -> Self {
+        Self {
+            function,
+            steps: function.steps(),
+            iv,
+        }
+    }
+
+    /// Only compute the first `steps` steps of the compression function.
+    ///
+    /// Panics if `steps` is larger than the number of steps of the full compression function.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lore::variant::Variant;
+    ///
+    /// let block = [0; 64];
+    /// let iv = [1, 2, 3, 4];
+    ///
+    /// // without any steps, the compression function only adds the IV to itself
+    /// assert_eq!(Variant::md5().steps(0).compress(iv, &block), [2, 4, 6, 8]);
+    /// ```
+    #[must_use]
+    pub fn steps(self, steps: usize) -> Self {
+        assert!(
+            steps <= self.function.steps(),
+            "The number of steps must be at most {}",
+            self.function.steps()
+        );
+
+        Self { steps, ..self }
+    }
+
+    /// Start hashing from the given initial value instead of the standard one.
+    #[must_use]
+    pub fn iv(self, iv: [u32; N]) -> Self {
+        Self { iv, ..self }
+    }
+
+    /// Process a single 512-bit block, starting from the given chaining value.
+    ///
+    /// The IV of the variant is not used, so that blocks can be chained or compressed from any state.
+    #[must_use]
+    pub fn compress(&self, buffer: [u32; N], block: &[u8; 64]) -> [u32; N] {
+        self.compress_observed(buffer, block, &mut ())
+    }
+
+    fn compress_observed<O: Observer + ?Sized>(
+        &self,
+        buffer: [u32; N],
+        block: &[u8; 64],
+        observer: &mut O,
+    ) -> [u32; N] {
+        // the width of the state always matches the function, since variants are only created from their standard IV
+        let steps = self.steps;
+        match self.function {
+            Function::Md4 => resize(&md4::compress_observed(
+                resize(&buffer),
+                block,
+                steps,
+                observer,
+            )),
+            Function::Md5 => resize(&md5::compress_observed(
+                resize(&buffer),
+                block,
+                steps,
+                observer,
+            )),
+            Function::Sha0 | Function::Sha1 => {
+                let words = if self.function == Function::Sha0 {
+                    sha1::schedule_sha0(block)
+                } else {
+                    sha1::schedule(block)
+                };
+                resize(&sha1::compress_observed(
+                    resize(&buffer),
+                    &words,
+                    steps,
+                    observer,
+                ))
+            }
+        }
+    }
+
+    // pad the message like the original function, and compress all of its blocks
+    fn digest<const M: usize, O: Observer + ?Sized>(
+        &self,
+        message: impl AsRef<[u8]>,
+        observer: &mut O,
+    ) -> [u8; M] {
+        let compress = |buffer, block: &[u8; 64], observer: &mut O| {
+            self.compress_observed(buffer, block, observer)
+        };
+
+        match self.function {
+            Function::Md4 | Function::Md5 => {
+                let padded = md4::pad(message);
+                let buffer = compress_blocks(
+                    &padded,
+                    self.iv,
+                    observer,
+                    bytes_to_words_le::<16>,
+                    compress,
+                );
+                words_to_bytes_le(buffer)
+            }
+            Function::Sha0 | Function::Sha1 => {
+                let padded = sha1::pad(message);
+                let buffer = compress_blocks(
+                    &padded,
+                    self.iv,
+                    observer,
+                    bytes_to_words_be::<16>,
+                    compress,
+                );
+                words_to_bytes_be(buffer)
+            }
+        }
+    }
+}
+
+fn resize<const N: usize>(words: &[u32]) -> [u32; N] {
+    words.try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variant_full() {
+        for message in ["", "abc", "The quick brown fox jumps over the lazy dog"] {
+            assert_eq!(crate::md4(message), Variant::md4().hash(message));
+            assert_eq!(crate::md5(message), Variant::md5().hash(message));
+            assert_eq!(crate::sha1(message), Variant::sha1().hash(message));
+        }
+    }
+
+    #[test]
+    fn variant_sha0() {
+        let expected: Digest<20> = "0164b8a914cd2a5e74c4f7ff082c4d97f1edf880".parse().unwrap();
+        assert_eq!(expected, Variant::sha0().hash("abc"));
+
+        let expected: Digest<20> = "d2516ee1acfa5baf33dfc1c471e438449ef134c8".parse().unwrap();
+        assert_eq!(
+            expected,
+            Variant::sha0().hash("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")
+        );
+    }
+
+    #[test]
+    fn variant_steps() {
+        let block = [0x61; 64];
+        let iv = [md5::A, md5::B, md5::C, md5::D];
+        let full = Variant::md5();
+
+        // the reduced compression function is the full one, stopped early
+        let mut events = Vec::new();
+        md5::compress_observed(iv, &block, 64, &mut events);
+        for steps in [0, 1, 16, 32, 63] {
+            let mut reduced = Vec::new();
+            full.steps(steps)
+                .compress_observed(iv, &block, &mut reduced);
+
+            assert_eq!(events[..steps], reduced[..steps]);
+        }
+        assert_eq!(md5::compress(iv, &block), full.compress(iv, &block));
+    }
+
+    #[test]
+    fn variant_iv() {
+        let iv = [1, 2, 3, 4, 5];
+        let block: [u8; 64] = sha1::pad("abc").try_into().unwrap();
+
+        assert_eq!(
+            Variant::sha1().compress(iv, &block),
+            bytes_to_words_be(Variant::sha1().iv(iv).hash("abc"))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The number of steps must be at most 48")]
+    fn variant_too_many_steps() {
+        let _ = Variant::md4().steps(49);
+    }
+}